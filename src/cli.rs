@@ -0,0 +1,748 @@
+use std::{env, ffi::OsString, path::PathBuf, time::Duration};
+
+use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum, ValueHint};
+use clap_complete::Shell;
+
+use roblox_studio::deploy;
+use roblox_studio::roblox_install::{LocateOptions, ROBLOX_STUDIO_PATH_VARIABLE};
+
+use crate::config::GlobalConfig;
+use crate::monitor;
+use crate::window::Geometry;
+
+/// A platform-agnostic CLI app for opening place files in Roblox Studio.
+///
+/// Running `roblox-studio [PLACE]` without a subcommand is shorthand for
+/// `roblox-studio open [PLACE]`. Without a place file, an empty Studio
+/// session is started.
+///
+/// Roblox Studio is located through the `ROBLOX_STUDIO_PATH` environment
+/// variable if it is set (a list of paths like `PATH`, the first installation
+/// found is used), and through the usual install locations otherwise.
+/// Under WSL, `ROBLOX_STUDIO_WINDOWS_USER` selects the Windows user whose
+/// installation is used.
+/// `ROBLOX_STUDIO_APPLICATION_PATH`, `ROBLOX_STUDIO_CONTENT_PATH` and
+/// `ROBLOX_STUDIO_PLUGINS_PATH` override individual paths of the installation.
+///
+/// Defaults for the Studio path, the channel, extra Studio arguments and the
+/// verbosity can be set in `config.toml`, in the `roblox-studio` directory of
+/// the platform's configuration directory (like `~/.config/roblox-studio` on
+/// Linux). Command line flags take precedence over environment variables, which
+/// take precedence over the configuration file.
+#[derive(Debug, Parser)]
+#[command(
+	name = "roblox-studio",
+	version,
+	args_conflicts_with_subcommands = true,
+	after_long_help = EXIT_CODES_HELP
+)]
+pub struct Cli {
+	/// Don't print error messages; rely on the exit code instead
+	#[arg(short, long, global = true)]
+	pub quiet: bool,
+
+	/// Print diagnostics about how Studio is located (-vv for more detail).
+	/// `RUST_LOG` takes precedence when set
+	#[arg(short, long, global = true, action = ArgAction::Count)]
+	pub verbose: u8,
+
+	/// Load environment variables from a dotenv-style file (`KEY=value` lines)
+	/// before anything else. Its variables override the environment's; can be
+	/// given several times, later files override earlier ones
+	#[arg(long, global = true, value_name = "PATH", value_hint = ValueHint::FilePath)]
+	pub env_file: Vec<PathBuf>,
+
+	#[command(flatten)]
+	pub discovery: DiscoveryArgs,
+
+	#[command(subcommand)]
+	pub command: Option<Command>,
+
+	#[command(flatten)]
+	pub open: OpenArgs,
+}
+
+impl Cli {
+	/// Fills in the options that weren't given on the command line or through
+	/// the environment from the global configuration.
+	pub fn apply_config(&mut self, config: &GlobalConfig) {
+		if self.verbose == 0
+			&& !self.quiet
+			&& let Some(verbose) = config.verbose
+		{
+			self.verbose = verbose;
+		}
+
+		if self.discovery.studio_path.is_none()
+			&& (self.discovery.no_env || env::var_os(ROBLOX_STUDIO_PATH_VARIABLE).is_none())
+		{
+			self.discovery.studio_path = config.studio_path.clone();
+		}
+
+		if self.discovery.channel.is_none() {
+			self.discovery.channel = config.channel.clone();
+		}
+
+		if !config.studio_args.is_empty() {
+			let open = match &mut self.command {
+				Some(Command::Open(open)) => open,
+				_ => &mut self.open,
+			};
+
+			open.studio_args.splice(0..0, config.studio_args.iter().map(OsString::from));
+		}
+	}
+}
+
+const EXIT_CODES_HELP: &str = "\
+Exit codes:
+  0   Success, or Studio's own exit code with --wait
+  2   Roblox Studio couldn't be found
+  64  Invalid command line arguments
+  69  This platform isn't supported
+  70  Roblox Studio couldn't be started or waited on, or couldn't open
+      the place
+  74  An I/O or network error occurred
+  78  The environment is misconfigured (like ROBLOX_STUDIO_PATH), or
+      `doctor` found a problem
+  124 Roblox Studio was killed for running past --timeout, or didn't
+      load the place in time";
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+	/// Open a place file in Roblox Studio, or start an empty session
+	Open(Box<OpenArgs>),
+
+	/// Start a local test server for a place file and clients joining it, like
+	/// Studio's "Start Server and Players"
+	Playtest(PlaytestArgs),
+
+	/// List the places recently opened in Roblox Studio
+	Recent(RecentArgs),
+
+	/// List the sessions started by `open`, most recent first, with the Studio
+	/// version they ran and how they ended
+	History(HistoryArgs),
+
+	/// Summarize the sessions recorded by `open`: load times per Studio version,
+	/// crash rates and the most opened places
+	Stats(StatsArgs),
+
+	/// Stop running Roblox Studio processes, asking them to exit first and
+	/// killing the ones that don't in time
+	Kill(KillArgs),
+
+	/// Read the logs Roblox Studio writes for each session
+	Logs(LogsArgs),
+
+	/// Measure how long Roblox Studio takes to do something, over several runs
+	Bench(BenchArgs),
+
+	/// Print the paths of the Roblox Studio installation
+	Locate(LocateArgs),
+
+	/// Open a directory of the Roblox Studio installation, like its plugins or
+	/// logs, in the file manager
+	OpenFolder(OpenFolderArgs),
+
+	/// Print the version of this tool and of Roblox Studio
+	Version(VersionArgs),
+
+	/// Manage installed Roblox Studio versions
+	Versions(VersionsArgs),
+
+	/// Download and install a Roblox Studio build from Roblox's deployment CDN
+	Install(InstallArgs),
+
+	/// Check for a newer Roblox Studio build and install it
+	Update(UpdateArgs),
+
+	/// Check the Roblox Studio installation and the environment for problems
+	Doctor,
+
+	/// Inspect or clear Roblox's HTTP cache of downloaded assets
+	Cache(CacheArgs),
+
+	/// Print a `roblox-studio:` URL that opens Studio to perform an action, for
+	/// links in documentation, dashboards or chat
+	Deeplink(DeeplinkArgs),
+
+	/// Generate a shell completion script and print it to stdout
+	Completions(CompletionsArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct OpenArgs {
+	/// Place file to open (.rbxl or .rbxlx), or the roblox.com URL of a published
+	/// place to edit, like https://www.roblox.com/games/1818/Classic-Crossroads
+	#[arg(value_hint = ValueHint::FilePath)]
+	pub place: Option<PathBuf>,
+
+	/// Open a place published to Roblox for editing, by its place ID, instead of
+	/// a place file
+	#[arg(long, value_name = "ID", conflicts_with = "place")]
+	pub place_id: Option<u64>,
+
+	/// Reopen the place most recently opened in Studio, see `recent list`
+	#[arg(long, conflicts_with_all = ["place", "place_id"])]
+	pub recent: bool,
+
+	/// Universe (experience) ID of the place opened with --place-id
+	#[arg(long, value_name = "ID", requires = "place_id")]
+	pub universe_id: Option<u64>,
+
+	/// Join the place's collaborative editing (Team Create) session. Studio only
+	/// joins it when it's given the universe ID too, which is looked up on
+	/// roblox.com without --universe-id
+	#[arg(long, requires = "place_id")]
+	pub team_create: bool,
+
+	/// Wait for Roblox Studio to exit and exit with its status code. Ctrl-C is
+	/// forwarded to Studio instead of detaching from it
+	#[arg(long)]
+	pub wait: bool,
+
+	/// Tie Studio to this process, so killing it (Ctrl-C, CI cancellation)
+	/// kills Studio too instead of leaving it running. Implies --wait
+	#[arg(long)]
+	pub attached: bool,
+
+	/// Kill Studio if it hasn't exited after this long, like `15m`, and exit with
+	/// status 124. Implies --wait
+	#[arg(long, value_name = "DURATION", value_parser = humantime::parse_duration)]
+	pub timeout: Option<Duration>,
+
+	/// Sample Studio's memory and CPU use while it runs, and print a summary
+	/// once it exits. Implies --wait
+	#[arg(long)]
+	pub monitor_resources: bool,
+
+	/// Kill Studio when its memory use goes over this size, like `8G`. Implies
+	/// --monitor-resources
+	#[arg(long, value_name = "SIZE", value_parser = monitor::parse_size)]
+	pub max_memory: Option<u64>,
+
+	/// Start Studio again with the same place when it exits abnormally, up to N
+	/// times (3 when not given). Implies --wait
+	#[arg(long, value_name = "N", num_args = 0..=1, default_missing_value = "3")]
+	pub restart_on_crash: Option<u32>,
+
+	/// Copy the crash dumps and logs Studio writes when it exits abnormally to
+	/// this directory, like a CI artifacts directory. Only applies when the CLI
+	/// waits for Studio to exit
+	#[arg(long, value_name = "DIR", value_hint = ValueHint::DirPath)]
+	pub crash_artifacts: Option<PathBuf>,
+
+	/// Print what Studio shows in its Output window, like what scripts print, as
+	/// it's written to Studio's log: messages on stdout, and warnings and errors
+	/// on stderr. Implies --wait
+	#[arg(long, conflicts_with = "events")]
+	pub stream_output: bool,
+
+	/// Write the lifecycle of the session to stdout as newline-delimited JSON
+	/// events: located, spawned, ready, place-loaded, crashed and exited. Implies
+	/// --wait
+	#[arg(long, conflicts_with = "dry_run")]
+	pub events: bool,
+
+	/// Show a desktop notification when the place finished loading, couldn't be
+	/// opened, or when Studio crashes, to switch away while it loads. Implies
+	/// --wait
+	#[arg(long, conflicts_with = "dry_run")]
+	pub notify: bool,
+
+	/// How long to keep retrying when Studio's executable is locked, which
+	/// happens while the bootstrapper installs an update. 0 fails right away
+	#[arg(long, value_name = "DURATION", default_value = "2m", value_parser = humantime::parse_duration)]
+	pub update_wait: Duration,
+
+	/// Print the command line that would be run instead of launching Studio
+	#[arg(long, conflicts_with = "wait")]
+	pub dry_run: bool,
+
+	/// When Studio is already running, hand the place file to it through the
+	/// file association, or a place published to Roblox through its
+	/// `roblox-studio:` link, instead of starting another instance
+	#[arg(long, conflicts_with_all = ["wait", "attached", "timeout", "monitor_resources", "max_memory", "restart_on_crash", "events", "stream_output", "notify", "fresh_profile", "no_plugins", "only_plugins"])]
+	pub reuse: bool,
+
+	/// Start another instance of Studio next to the running ones, to edit two
+	/// places side by side. On Windows, Roblox's single instance mutex is held
+	/// while it starts so it doesn't defer to the running instance
+	#[arg(long, conflicts_with = "reuse")]
+	pub new_instance: bool,
+
+	/// Apply a profile from the configuration to this session. Profiles that
+	/// override FFlags or plugins make the CLI wait for Studio to exit, then
+	/// revert their changes. Changes left behind by a CLI that was killed are
+	/// reverted the next time it opens Studio
+	#[arg(long, value_name = "NAME")]
+	pub profile: Option<String>,
+
+	/// Start Studio as if it was just installed: its settings and plugins are
+	/// moved aside next to where they are (as `*.shadowed-by-roblox-studio`) for
+	/// this session, and restored once Studio exits, on Ctrl-C, or the next time
+	/// the CLI opens Studio if it was killed. Implies waiting for Studio to exit,
+	/// and refuses to start while Studio is running
+	#[arg(long)]
+	pub fresh_profile: bool,
+
+	/// Disable the user's plugins, local and installed from the marketplace, for
+	/// this session. They're moved aside and restored like with
+	/// --fresh-profile, which implies waiting for Studio to exit
+	#[arg(long, conflicts_with = "fresh_profile")]
+	pub no_plugins: bool,
+
+	/// Only load these local plugins, by file name, for this session (like
+	/// `MyPlugin.rbxm,Other.rbxm`). The other plugins are moved aside (to
+	/// `Plugins.disabled-by-roblox-studio`) and restored like with
+	/// --fresh-profile, which implies waiting for Studio to exit. Takes
+	/// precedence over the profile's plugins
+	#[arg(long, value_name = "PLUGINS", value_delimiter = ',', conflicts_with_all = ["no_plugins", "fresh_profile"])]
+	pub only_plugins: Option<Vec<String>>,
+
+	/// Minimize Studio's window once it appears
+	#[arg(long)]
+	pub minimized: bool,
+
+	/// Maximize Studio's window once it appears
+	#[arg(long, conflicts_with = "minimized")]
+	pub maximized: bool,
+
+	/// Move Studio's window to this monitor, starting at 1. A --geometry is
+	/// relative to it. Not supported on MacOS
+	#[arg(long, value_name = "N")]
+	pub monitor: Option<usize>,
+
+	/// Move and resize Studio's window, like 1600x900+0+0 (WIDTHxHEIGHT+X+Y).
+	/// On Linux, this needs wmctrl, and xdotool for --minimized
+	#[arg(long, value_name = "GEOMETRY")]
+	pub geometry: Option<Geometry>,
+
+	/// Extra arguments forwarded verbatim to Roblox Studio (after `--`)
+	#[arg(last = true, value_name = "STUDIO_ARGS")]
+	pub studio_args: Vec<OsString>,
+}
+
+#[derive(Debug, Args)]
+pub struct PlaytestArgs {
+	/// Place file to test. Defaults to the project's place
+	#[arg(value_hint = ValueHint::FilePath)]
+	pub place: Option<PathBuf>,
+
+	/// Number of clients to start, up to 8
+	#[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(0..=8))]
+	pub clients: u8,
+
+	/// How long to give the server to start before starting the clients
+	#[arg(long, value_name = "DURATION", default_value = "5s", value_parser = humantime::parse_duration)]
+	pub client_delay: Duration,
+
+	/// Print the command lines that would be run instead of starting Studio
+	#[arg(long)]
+	pub dry_run: bool,
+}
+
+/// Options that control which Roblox Studio installation is used.
+#[derive(Debug, Args)]
+pub struct DiscoveryArgs {
+	/// Use the installation at this path, ignoring `ROBLOX_STUDIO_PATH` and the
+	/// usual install locations. It can be a version directory, the Roblox
+	/// directory containing `Versions`, or the Studio executable
+	#[arg(long, global = true, value_name = "PATH", value_hint = ValueHint::AnyPath)]
+	pub studio_path: Option<PathBuf>,
+
+	/// Ignore `ROBLOX_STUDIO_PATH` and the variables overriding individual paths
+	/// (like `ROBLOX_STUDIO_PLUGINS_PATH`), and find Studio in the usual install
+	/// locations
+	#[arg(long, global = true)]
+	pub no_env: bool,
+
+	/// Use a specific installed version, by its `version-<hash>` directory or its
+	/// product version (like 0.612.0.6120532)
+	#[arg(long, global = true, value_name = "VERSION")]
+	pub studio_version: Option<String>,
+
+	/// Use an installation from this deployment channel, like zcanary. `install`
+	/// and `update` download from this channel, LIVE by default
+	#[arg(long, global = true)]
+	pub channel: Option<String>,
+
+	/// Under WSL, use the installation of this Windows user. Overrides
+	/// `ROBLOX_STUDIO_WINDOWS_USER`; by default, the current Windows user's
+	/// installation is used, or the only one found
+	#[arg(long, global = true, value_name = "USER")]
+	pub windows_user: Option<String>,
+}
+
+impl DiscoveryArgs {
+	pub fn options(&self) -> LocateOptions {
+		let mut options = self.installation_options();
+
+		if let Some(version) = &self.studio_version {
+			options = options.version(version);
+		}
+
+		if let Some(channel) = &self.channel {
+			options = options.channel(channel);
+		}
+
+		options
+	}
+
+	/// Options finding the installation without selecting one of its versions
+	/// or channels, to go through all of them.
+	pub fn installation_options(&self) -> LocateOptions {
+		let mut options = LocateOptions::new().ignore_env(self.no_env);
+
+		if let Some(path) = &self.studio_path {
+			options = options.path(path);
+		}
+
+		if let Some(windows_user) = &self.windows_user {
+			options = options.windows_user(windows_user);
+		}
+
+		options
+	}
+
+	/// The channel to download builds from.
+	pub fn deployment_channel(&self) -> &str {
+		self.channel.as_deref().unwrap_or(deploy::LIVE_CHANNEL)
+	}
+}
+
+#[derive(Debug, Args)]
+pub struct RecentArgs {
+	#[command(subcommand)]
+	pub command: RecentCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum RecentCommand {
+	/// List recent place files and published places, most recent first
+	List(RecentListArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct RecentListArgs {
+	/// Print the places as a JSON array
+	#[arg(long)]
+	pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct HistoryArgs {
+	/// Only list the sessions of this place file
+	#[arg(long, value_hint = ValueHint::FilePath)]
+	pub place: Option<PathBuf>,
+
+	/// Number of sessions to list
+	#[arg(short = 'n', long, default_value_t = 20)]
+	pub limit: usize,
+
+	/// Print the sessions as a JSON array
+	#[arg(long)]
+	pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct StatsArgs {
+	/// Only count the sessions started this recently, like `30d`
+	#[arg(long, value_name = "DURATION", value_parser = humantime::parse_duration)]
+	pub since: Option<Duration>,
+
+	/// Number of most opened places to list
+	#[arg(long, default_value_t = 5)]
+	pub places: usize,
+
+	/// Print the statistics as JSON
+	#[arg(long)]
+	pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct KillArgs {
+	/// Stop every running Studio process. Without --all or --pid, the only
+	/// running process is stopped
+	#[arg(long)]
+	pub all: bool,
+
+	/// Stop this Studio process
+	#[arg(long, value_name = "PID", conflicts_with = "all")]
+	pub pid: Option<u32>,
+
+	/// How long Studio is given to exit before it's killed
+	#[arg(long, value_name = "DURATION", default_value = "10s", value_parser = humantime::parse_duration)]
+	pub timeout: Duration,
+
+	/// Don't ask for confirmation
+	#[arg(short, long)]
+	pub yes: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct LocateArgs {
+	/// Print only this path, for use in scripts
+	#[arg(long, value_enum, conflicts_with = "json")]
+	pub path: Option<StudioPath>,
+
+	/// Print all paths as a JSON object
+	#[arg(long)]
+	pub json: bool,
+
+	/// Create the user's plugins directory if it doesn't exist yet
+	#[arg(long)]
+	pub ensure_plugins_dir: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct OpenFolderArgs {
+	/// The directory to open. For the executable, its directory is opened
+	#[arg(value_enum)]
+	pub folder: StudioPath,
+
+	/// Create the plugins directory first when it doesn't exist yet
+	#[arg(long)]
+	pub ensure_plugins_dir: bool,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum StudioPath {
+	/// The Roblox Studio executable
+	Application,
+	/// The content directory
+	Content,
+	/// The user's plugins directory
+	Plugins,
+	/// The directory of plugins installed from the marketplace
+	ManagedPlugins,
+	/// The built-in plugins directory
+	BuiltInPlugins,
+	/// The installation directory
+	Root,
+	/// The directory Studio writes its logs to
+	Logs,
+	/// The directory Studio saves crash-recovery copies of places to
+	#[value(alias = "autosaves")]
+	AutoSaves,
+	/// The directory Studio keeps its settings and local storage in
+	AppData,
+}
+
+#[derive(Debug, Args)]
+pub struct InstallArgs {
+	/// Version to install, like version-1a2b3c4d5e6f4a7b. Defaults to the latest
+	/// version deployed to the channel selected with --channel
+	#[arg(value_name = "VERSION")]
+	pub version_hash: Option<String>,
+
+	/// Roblox directory to install into; the build is placed in its `Versions`
+	/// directory. Defaults to the directory of the current installation, or to
+	/// the usual Roblox directory on Windows
+	#[arg(long, value_hint = ValueHint::DirPath)]
+	pub dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub struct UpdateArgs {
+	/// Only report whether the installed version is outdated
+	#[arg(long)]
+	pub check: bool,
+
+	/// Print the result of --check as a JSON object
+	#[arg(long, requires = "check")]
+	pub json: bool,
+
+	/// Roblox directory to install into, like for `install`
+	#[arg(long, value_hint = ValueHint::DirPath, conflicts_with = "check")]
+	pub dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub struct LogsArgs {
+	#[command(subcommand)]
+	pub command: LogsCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum LogsCommand {
+	/// Print the path of the logs directory
+	Path,
+
+	/// Print the end of the latest session's log and follow it as Studio writes
+	/// to it, switching to the log of a newer session when one starts
+	Tail(LogsTailArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct LogsTailArgs {
+	/// Number of lines to print from the end of the log before following it
+	#[arg(short = 'n', long, default_value_t = 10)]
+	pub lines: usize,
+
+	/// Print the end of the log and exit instead of following it
+	#[arg(long)]
+	pub no_follow: bool,
+
+	/// Wait for a session that starts after this command, like one launched
+	/// right before it, instead of reading the latest log
+	#[arg(long, conflicts_with = "no_follow")]
+	pub new: bool,
+
+	/// Only print errors, with the lines continuing them like stack traces
+	#[arg(long)]
+	pub errors: bool,
+
+	/// Only print the records of this log channel, like `FLog::Output` or
+	/// `Output`. Can be given several times
+	#[arg(long, value_name = "CHANNEL")]
+	pub log_channel: Vec<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct BenchArgs {
+	#[command(subcommand)]
+	pub command: BenchCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum BenchCommand {
+	/// Open a place file several times and measure how long it takes to load,
+	/// from starting Studio to its log reporting the place loaded. Studio is
+	/// closed after each run
+	Open(BenchOpenArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct BenchOpenArgs {
+	/// Place file to open. Defaults to the project's place
+	#[arg(value_hint = ValueHint::FilePath)]
+	pub place: Option<PathBuf>,
+
+	/// Number of times to open the place
+	#[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u32).range(1..))]
+	pub runs: u32,
+
+	/// Give up on a run when the place hasn't loaded after this long
+	#[arg(long, value_name = "DURATION", default_value = "5m", value_parser = humantime::parse_duration)]
+	pub timeout: Duration,
+
+	/// Print the durations of the runs and their summary as JSON, in seconds
+	#[arg(long)]
+	pub json: bool,
+
+	/// Show a desktop notification when the benchmark is done
+	#[arg(long)]
+	pub notify: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct CacheArgs {
+	#[command(subcommand)]
+	pub command: CacheCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum CacheCommand {
+	/// Print the path of the HTTP cache directory
+	Path,
+
+	/// List the cached assets with their size and age, oldest first
+	List(CacheListArgs),
+
+	/// Remove cached assets, so Studio downloads them again
+	Clear(CacheClearArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct CacheListArgs {
+	/// Print the entries as a JSON array
+	#[arg(long)]
+	pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct CacheClearArgs {
+	/// Only remove assets downloaded longer ago than this, like `7d` or `12h`
+	#[arg(long, value_name = "AGE", value_parser = humantime::parse_duration)]
+	pub older_than: Option<Duration>,
+
+	/// Print what would be removed without removing it
+	#[arg(long)]
+	pub dry_run: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct DeeplinkArgs {
+	#[command(subcommand)]
+	pub command: DeeplinkCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum DeeplinkCommand {
+	/// Edit a place published to Roblox
+	EditPlace {
+		/// ID of the place
+		place_id: u64,
+
+		/// Universe (experience) ID of the place, needed to join its Team Create
+		/// session
+		#[arg(long, value_name = "ID")]
+		universe_id: Option<u64>,
+	},
+
+	/// Install a plugin from the marketplace
+	InstallPlugin {
+		/// Asset ID of the plugin
+		plugin_id: u64,
+	},
+
+	/// Open a marketplace asset, like a model, in a new place
+	TryAsset {
+		/// ID of the asset
+		asset_id: u64,
+	},
+}
+
+#[derive(Debug, Args)]
+pub struct CompletionsArgs {
+	/// Shell to generate completions for
+	#[arg(value_enum)]
+	pub shell: Shell,
+}
+
+#[derive(Debug, Args)]
+pub struct VersionArgs {
+	/// Print only the Roblox Studio product version, for use in scripts
+	#[arg(long)]
+	pub studio: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct VersionsArgs {
+	#[command(subcommand)]
+	pub command: VersionsCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum VersionsCommand {
+	/// List installed Roblox Studio versions with their install date and size
+	List,
+
+	/// Remove old Roblox Studio versions that are no longer in use, keeping the
+	/// current, pinned and running ones
+	Gc(GcArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct GcArgs {
+	/// Number of installed versions of each channel to keep, including the
+	/// current one
+	#[arg(long, default_value_t = 2, value_parser = clap::value_parser!(u32).range(1..))]
+	pub keep: u32,
+
+	/// Print the directories that would be removed without removing them
+	#[arg(long)]
+	pub dry_run: bool,
+}
@@ -0,0 +1,160 @@
+use std::{
+	io,
+	process::ExitCode,
+	time::{Duration, Instant},
+};
+
+use serde::Serialize;
+use tracing::warn;
+
+use roblox_studio::LaunchOptions;
+use roblox_studio::logs::{LoadFailure, SessionLog};
+use roblox_studio::roblox_install::RobloxStudio;
+
+use crate::cli::{BenchArgs, BenchCommand, BenchOpenArgs, DiscoveryArgs};
+use crate::commands::open;
+use crate::config::{GlobalConfig, Project};
+use crate::error::{Error, Result};
+use crate::notify;
+use crate::supervisor;
+use crate::webhook::Webhook;
+
+#[derive(Serialize)]
+struct BenchOutput {
+	runs: Vec<f64>,
+	min: f64,
+	median: f64,
+	max: f64,
+}
+
+pub fn run(args: &BenchArgs, discovery: &DiscoveryArgs, config: &GlobalConfig) -> Result<ExitCode> {
+	match &args.command {
+		BenchCommand::Open(args) => {
+			let mut webhook = Webhook::new(config.webhook.as_deref());
+			let result = bench_open(args, discovery, &mut webhook);
+
+			if let Err(err) = &result {
+				webhook.send(&format!("Benchmark failed: {}", err));
+
+				if args.notify {
+					notify::send("Roblox Studio benchmark failed", &err.to_string());
+				}
+			}
+
+			result
+		}
+	}
+}
+
+fn bench_open(args: &BenchOpenArgs, discovery: &DiscoveryArgs, webhook: &mut Webhook) -> Result<ExitCode> {
+	let project = Project::discover()?;
+	let roblox_studio = open::locate(discovery, project.as_ref(), false)?;
+
+	let place = args
+		.place
+		.clone()
+		.or_else(|| project.as_ref()?.place())
+		.ok_or_else(|| Error::Usage("no place file was given, and the project doesn't set one".into()))?;
+
+	roblox_studio
+		.check_launchable()
+		.map_err(Error::Launch)?;
+
+	for issue in roblox_studio.validate() {
+		warn!("{}", issue);
+	}
+
+	let place_name = place.file_name().unwrap_or(place.as_os_str()).to_string_lossy().into_owned();
+	let launch = LaunchOptions::new().place(place);
+	let mut durations = Vec::new();
+
+	for run in 1..=args.runs {
+		let duration = time_load(&roblox_studio, &launch, args.timeout)?;
+
+		eprintln!("Run {}/{}: {:.2}s", run, args.runs, duration.as_secs_f64());
+		durations.push(duration);
+	}
+
+	let mut sorted = durations.clone();
+	sorted.sort();
+
+	let min = sorted[0];
+	let max = sorted[sorted.len() - 1];
+	let middle = sorted.len() / 2;
+	let median = if sorted.len() % 2 == 0 {
+		(sorted[middle - 1] + sorted[middle]) / 2
+	} else {
+		sorted[middle]
+	};
+
+	let summary = format!(
+		"min {:.2}s, median {:.2}s, max {:.2}s over {} runs",
+		min.as_secs_f64(),
+		median.as_secs_f64(),
+		max.as_secs_f64(),
+		durations.len()
+	);
+
+	webhook.send(&format!("Benchmark of {}: {}", place_name, summary));
+
+	if args.notify {
+		notify::send("Roblox Studio benchmark done", &summary);
+	}
+
+	if args.json {
+		let output = BenchOutput {
+			runs: durations.iter().map(Duration::as_secs_f64).collect(),
+			min: min.as_secs_f64(),
+			median: median.as_secs_f64(),
+			max: max.as_secs_f64(),
+		};
+
+		println!("{}", serde_json::to_string_pretty(&output)?);
+		return Ok(ExitCode::SUCCESS);
+	}
+
+	println!("{}", summary);
+
+	Ok(ExitCode::SUCCESS)
+}
+
+/// Starts Studio, and measures how long it takes for its log to report the place
+/// loaded. Studio is killed once it has, since it's started again for the next
+/// run.
+fn time_load(roblox_studio: &RobloxStudio, launch: &LaunchOptions, timeout: Duration) -> Result<Duration> {
+	let mut log = SessionLog::new(roblox_studio);
+	let mut child = roblox_studio.launch_with(launch).map_err(Error::Spawn)?;
+	let started = Instant::now();
+	let mut outcome = None;
+
+	let status = supervisor::wait_watching(&mut child, |_| {
+		if started.elapsed() > timeout {
+			outcome = Some(Err(Error::LoadTimeout(timeout)));
+			return Ok(true);
+		}
+
+		for record in log.read(roblox_studio)? {
+			if let Some(failure) = LoadFailure::from_record(&record) {
+				outcome = Some(Err(Error::LoadFailed(failure)));
+				break;
+			}
+
+			if record.reports_place_loaded() {
+				outcome = Some(Ok(started.elapsed()));
+				break;
+			}
+		}
+
+		Ok(outcome.is_some())
+	})
+	.map_err(Error::Wait)?;
+
+	outcome.unwrap_or_else(|| {
+		let status = status.map_or_else(|| "killed".to_owned(), |status| status.to_string());
+
+		Err(Error::Io(
+			"Roblox Studio exited before the place loaded".into(),
+			io::Error::other(status),
+		))
+	})
+}
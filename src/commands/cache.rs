@@ -0,0 +1,105 @@
+use std::{
+	path::{Path, PathBuf},
+	process::ExitCode,
+};
+
+use serde::Serialize;
+
+use roblox_studio::http_cache::{self, CacheEntry};
+use roblox_studio::roblox_install::RobloxStudio;
+
+use crate::cli::{CacheArgs, CacheClearArgs, CacheCommand, CacheListArgs, DiscoveryArgs};
+use crate::error::{Error, Result};
+use crate::format;
+
+#[derive(Serialize)]
+struct EntryOutput<'a> {
+	hash: &'a str,
+	size: u64,
+	modified: String,
+	age_seconds: u64,
+}
+
+pub fn run(args: &CacheArgs, discovery: &DiscoveryArgs) -> Result<ExitCode> {
+	let roblox_studio = RobloxStudio::locate_with(&discovery.options())?;
+	let directory = roblox_studio
+		.http_cache_path()
+		.ok_or_else(|| Error::Usage("the HTTP cache directory of this installation is unknown".into()))?;
+
+	match &args.command {
+		CacheCommand::Path => {
+			println!("{}", directory.display());
+			Ok(ExitCode::SUCCESS)
+		}
+		CacheCommand::List(args) => list(args, directory),
+		CacheCommand::Clear(args) => clear(args, directory),
+	}
+}
+
+fn list(args: &CacheListArgs, directory: PathBuf) -> Result<ExitCode> {
+	let entries = read_entries(&directory)?;
+
+	if args.json {
+		let output: Vec<EntryOutput> = entries
+			.iter()
+			.map(|entry| EntryOutput {
+				hash: &entry.hash,
+				size: entry.size,
+				modified: format::timestamp(entry.modified),
+				age_seconds: entry.age().as_secs(),
+			})
+			.collect();
+
+		println!("{}", serde_json::to_string_pretty(&output)?);
+		return Ok(ExitCode::SUCCESS);
+	}
+
+	let hash_width = entries
+		.iter()
+		.map(|entry| entry.hash.len())
+		.max()
+		.unwrap_or(0)
+		.max("HASH".len());
+
+	println!("{:<hash_width$}  {:>10}  AGE", "HASH", "SIZE");
+
+	for entry in &entries {
+		println!(
+			"{:<hash_width$}  {:>10}  {}",
+			entry.hash,
+			format::size(entry.size),
+			format::age(entry.age())
+		);
+	}
+
+	let total: u64 = entries.iter().map(|entry| entry.size).sum();
+	println!("{} file(s), {}", entries.len(), format::size(total));
+
+	Ok(ExitCode::SUCCESS)
+}
+
+fn clear(args: &CacheClearArgs, directory: PathBuf) -> Result<ExitCode> {
+	if args.dry_run {
+		let entries: Vec<CacheEntry> = read_entries(&directory)?
+			.into_iter()
+			.filter(|entry| args.older_than.is_none_or(|older_than| entry.age() >= older_than))
+			.collect();
+
+		let total: u64 = entries.iter().map(|entry| entry.size).sum();
+		println!("Would remove {} file(s) ({})", entries.len(), format::size(total));
+
+		return Ok(ExitCode::SUCCESS);
+	}
+
+	let removed = http_cache::clear(&directory, args.older_than)
+		.map_err(|err| Error::Io(format!("Failed to clear {}", directory.display()), err))?;
+
+	let total: u64 = removed.iter().map(|entry| entry.size).sum();
+	println!("Removed {} file(s) ({})", removed.len(), format::size(total));
+
+	Ok(ExitCode::SUCCESS)
+}
+
+fn read_entries(directory: &Path) -> Result<Vec<CacheEntry>> {
+	http_cache::entries(directory).map_err(|err| Error::Io(format!("Failed to read {}", directory.display()), err))
+}
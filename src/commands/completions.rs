@@ -0,0 +1,15 @@
+use std::{io, process::ExitCode};
+
+use clap::CommandFactory;
+
+use crate::cli::{Cli, CompletionsArgs};
+use crate::error::Result;
+
+pub fn run(args: &CompletionsArgs) -> Result<ExitCode> {
+	let mut command = Cli::command();
+	let name = command.get_name().to_owned();
+
+	clap_complete::generate(args.shell, &mut command, name, &mut io::stdout());
+
+	Ok(ExitCode::SUCCESS)
+}
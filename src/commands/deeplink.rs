@@ -0,0 +1,18 @@
+use std::process::ExitCode;
+
+use roblox_studio::DeepLink;
+
+use crate::cli::{DeeplinkArgs, DeeplinkCommand};
+use crate::error::Result;
+
+pub fn run(args: &DeeplinkArgs) -> Result<ExitCode> {
+	let link = match args.command {
+		DeeplinkCommand::EditPlace { place_id, universe_id } => DeepLink::EditPlace { place_id, universe_id },
+		DeeplinkCommand::InstallPlugin { plugin_id } => DeepLink::InstallPlugin { plugin_id },
+		DeeplinkCommand::TryAsset { asset_id } => DeepLink::TryAsset { asset_id },
+	};
+
+	println!("{}", link);
+
+	Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,149 @@
+use std::{fs, process::Command, process::ExitCode};
+
+use roblox_studio::roblox_install::{self, RobloxStudio, WslVersion};
+
+use crate::cli::DiscoveryArgs;
+use crate::error::{EXIT_CONFIG, Result};
+
+/// Collects the results of the checks, printing them as they're made.
+#[derive(Default)]
+struct Report {
+	problems: usize,
+}
+
+impl Report {
+	fn pass(&mut self, message: impl AsRef<str>) {
+		println!("ok    {}", message.as_ref());
+	}
+
+	fn fail(&mut self, message: impl AsRef<str>, remediation: impl AsRef<str>) {
+		self.problems += 1;
+		println!("fail  {}", message.as_ref());
+
+		for line in remediation.as_ref().lines() {
+			println!("      {}", line);
+		}
+	}
+}
+
+pub fn run(discovery: &DiscoveryArgs) -> Result<ExitCode> {
+	let mut report = Report::default();
+
+	check_studio(&mut report, discovery);
+
+	if let Some(version) = roblox_install::wsl_version() {
+		check_wsl(&mut report, version);
+	}
+
+	println!();
+
+	if report.problems == 0 {
+		println!("No problems found");
+		Ok(ExitCode::SUCCESS)
+	} else {
+		println!("{} problem(s) found", report.problems);
+		Ok(ExitCode::from(EXIT_CONFIG))
+	}
+}
+
+fn check_studio(report: &mut Report, discovery: &DiscoveryArgs) {
+	let studio = match RobloxStudio::locate_with(&discovery.options()) {
+		Ok(studio) => studio,
+		Err(err) => {
+			report.fail(
+				format!("Roblox Studio wasn't found: {}", err),
+				"Install Roblox Studio, or set ROBLOX_STUDIO_PATH to its directory",
+			);
+			return;
+		}
+	};
+
+	report.pass(format!("Roblox Studio found at {}", studio.application_path().display()));
+
+	for issue in studio.validate() {
+		report.fail(
+			issue.to_string(),
+			"The installation may be incomplete, reinstall it with `roblox-studio install`",
+		);
+	}
+
+	match studio.version() {
+		Some(version) => report.pass(format!("Roblox Studio version is {}", version)),
+		None => report.fail(
+			"Couldn't read the version of Roblox Studio",
+			"The installation may be incomplete, reinstall it with `roblox-studio install`",
+		),
+	}
+}
+
+fn check_wsl(report: &mut Report, version: WslVersion) {
+	let version_name = match version {
+		WslVersion::Wsl1 => "WSL1",
+		WslVersion::Wsl2 => "WSL2",
+	};
+
+	report.pass(format!("Running under {}", version_name));
+
+	if roblox_install::wsl_interop_enabled() {
+		report.pass("WSL interop is enabled");
+	} else {
+		report.fail(
+			"WSL interop is disabled, Windows programs like Roblox Studio can't be started",
+			"Add `enabled = true` to the [interop] section of /etc/wsl.conf, then run\n\
+			`wsl --shutdown` from Windows and open a new WSL shell",
+		);
+	}
+
+	let drive = roblox_install::wsl_mount_root().join("c");
+
+	match fs::read_dir(&drive) {
+		Ok(_) => report.pass(format!("The C: drive is mounted at {}", drive.display())),
+		Err(err) => report.fail(
+			format!("The C: drive isn't readable at {}: {}", drive.display(), err),
+			"Make sure automount is enabled and that the `root` setting of the [automount]\n\
+			section of /etc/wsl.conf matches where Windows drives are mounted",
+		),
+	}
+
+	let users = drive.join("Users");
+	let mut roblox_users: Vec<String> = fs::read_dir(&users)
+		.into_iter()
+		.flatten()
+		.filter_map(|entry| entry.ok())
+		.filter(|entry| entry.path().join("AppData").join("Local").join("Roblox").is_dir())
+		.filter_map(|entry| entry.file_name().into_string().ok())
+		.collect();
+
+	roblox_users.sort();
+
+	if roblox_users.is_empty() {
+		report.fail(
+			format!("No Windows user in {} has a Roblox directory", users.display()),
+			"Install Roblox Studio on Windows, or set ROBLOX_STUDIO_PATH to your Roblox directory\n\
+			(like C:\\Users\\<you>\\AppData\\Local\\Roblox)",
+		);
+	} else {
+		report.pass(format!("Found a Roblox directory for {}", roblox_users.join(", ")));
+	}
+
+	match Command::new("wslpath").arg("-w").arg(&drive).output() {
+		Ok(output) if output.status.success() => report.pass(format!(
+			"wslpath works ({} is {})",
+			drive.display(),
+			String::from_utf8_lossy(&output.stdout).trim()
+		)),
+		Ok(output) => report.fail(
+			format!("wslpath failed: {}", String::from_utf8_lossy(&output.stderr).trim()),
+			"Place file paths can't be converted for Studio, check that automount is enabled",
+		),
+		Err(err) => report.fail(
+			format!("wslpath couldn't be run: {}", err),
+			"WSL provides wslpath as a link to /init, restart the distribution with\n\
+			`wsl --terminate <distribution>` from Windows to recreate it",
+		),
+	}
+
+	if let Some(host) = roblox_install::wsl_windows_host() {
+		report.pass(format!("Windows is reachable from this distribution at {}", host));
+	}
+}
@@ -0,0 +1,75 @@
+use std::{path, process::ExitCode, time::Duration};
+
+use crate::cli::HistoryArgs;
+use crate::error::Result;
+use crate::format;
+use crate::journal::{self, Entry};
+
+pub fn run(args: &HistoryArgs) -> Result<ExitCode> {
+	let place = args
+		.place
+		.as_deref()
+		.map(|place| path::absolute(place).unwrap_or_else(|_| place.to_owned()));
+
+	let entries: Vec<Entry> = journal::read()?
+		.into_iter()
+		.rev()
+		.filter(|entry| place.is_none() || entry.place == place)
+		.take(args.limit)
+		.collect();
+
+	if args.json {
+		println!("{}", serde_json::to_string_pretty(&entries)?);
+		return Ok(ExitCode::SUCCESS);
+	}
+
+	if entries.is_empty() {
+		eprintln!("No session was recorded");
+		return Ok(ExitCode::SUCCESS);
+	}
+
+	let version_width = entries
+		.iter()
+		.map(|entry| version(entry).len())
+		.max()
+		.unwrap_or(0);
+
+	for entry in &entries {
+		let duration = entry
+			.duration
+			.map_or_else(|| "-".to_owned(), |duration| format::age(Duration::from_secs_f64(duration)));
+
+		let exit = match (entry.duration, entry.exit_code) {
+			(_, Some(code)) => format!("exit {}", code),
+			(Some(_), None) => "killed".to_owned(),
+			(None, None) => "detached".to_owned(),
+		};
+
+		let place = match (&entry.place, entry.place_id) {
+			(Some(place), _) => place.display().to_string(),
+			(None, Some(place_id)) => format!("place {}", place_id),
+			(None, None) => "-".to_owned(),
+		};
+
+		println!(
+			"{}  {:<version_width$}  {:>5}  {:<8}  {}",
+			entry.started,
+			version(entry),
+			duration,
+			exit,
+			place,
+		);
+	}
+
+	Ok(ExitCode::SUCCESS)
+}
+
+/// The product version of the session's Studio, or its version hash when it's
+/// unknown.
+fn version(entry: &Entry) -> &str {
+	entry
+		.version
+		.as_deref()
+		.or(entry.version_hash.as_deref())
+		.unwrap_or("-")
+}
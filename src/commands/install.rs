@@ -0,0 +1,71 @@
+use std::{
+	path::{Path, PathBuf},
+	process::ExitCode,
+};
+
+use roblox_studio::deploy;
+use roblox_studio::roblox_install::RobloxStudio;
+
+use crate::cli::{DiscoveryArgs, InstallArgs};
+use crate::error::{Error, Result};
+use crate::format;
+
+pub fn run(args: &InstallArgs, discovery: &DiscoveryArgs) -> Result<ExitCode> {
+	let root = match &args.dir {
+		Some(dir) => dir.clone(),
+		None => default_root(discovery)?,
+	};
+
+	let channel = discovery.deployment_channel();
+
+	let version_hash = match &args.version_hash {
+		Some(version_hash) => version_hash.clone(),
+		None => {
+			let latest = deploy::latest_version(channel).map_err(Error::Install)?;
+			eprintln!("Latest version on {} is {} ({})", channel, latest.version, latest.version_hash);
+			latest.version_hash
+		}
+	};
+
+	install(channel, &version_hash, &root)?;
+
+	Ok(ExitCode::SUCCESS)
+}
+
+/// Installs a version, printing progress to stderr and the version directory
+/// to stdout.
+pub fn install(channel: &str, version_hash: &str, root: &Path) -> Result<PathBuf> {
+	eprintln!("Installing {} into {}", version_hash, root.display());
+
+	let destination = deploy::install(channel, version_hash, root, |index, count, package| {
+		eprintln!("[{}/{}] {} ({})", index + 1, count, package.name, format::size(package.size));
+	})
+	.map_err(Error::Install)?;
+
+	println!("{}", destination.display());
+
+	Ok(destination)
+}
+
+/// The Roblox directory containing the current installation's `Versions`
+/// directory, or the usual Roblox directory on Windows.
+pub fn default_root(discovery: &DiscoveryArgs) -> Result<PathBuf> {
+	if let Ok(studio) = RobloxStudio::locate_with(&discovery.options()) {
+		#[allow(deprecated)]
+		let versions = studio.root_path().parent();
+
+		if let Some(versions) = versions.filter(|versions| versions.ends_with("Versions"))
+			&& let Some(root) = versions.parent()
+		{
+			return Ok(root.to_owned());
+		}
+	}
+
+	if cfg!(target_os = "windows")
+		&& let Some(local) = dirs::data_local_dir()
+	{
+		return Ok(local.join("Roblox"));
+	}
+
+	Err(Error::Usage("Couldn't find a Roblox directory to install into, use --dir".to_owned()))
+}
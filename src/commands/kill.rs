@@ -0,0 +1,107 @@
+use std::{
+	io::{self, BufRead, IsTerminal, Write},
+	process::ExitCode,
+	thread,
+	time::{Duration, Instant},
+};
+
+use roblox_studio::process::{self, StudioProcess};
+
+use crate::cli::KillArgs;
+use crate::error::{Error, Result};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+pub fn run(args: &KillArgs) -> Result<ExitCode> {
+	let running = process::running();
+
+	let targets: Vec<StudioProcess> = match args.pid {
+		Some(pid) => {
+			let process = running
+				.into_iter()
+				.find(|process| process.pid == pid)
+				.ok_or_else(|| Error::Usage(format!("process {} isn't a running Roblox Studio process", pid)))?;
+
+			vec![process]
+		}
+		None if running.len() > 1 && !args.all => {
+			return Err(Error::Usage(format!(
+				"{} Roblox Studio processes are running ({}), pass --all to stop them all or --pid to choose one",
+				running.len(),
+				pids(&running)
+			)));
+		}
+		None => running,
+	};
+
+	if targets.is_empty() {
+		eprintln!("Roblox Studio isn't running");
+		return Ok(ExitCode::SUCCESS);
+	}
+
+	if !args.yes && !confirm(&targets)? {
+		return Ok(ExitCode::FAILURE);
+	}
+
+	for process in &targets {
+		process
+			.terminate()
+			.map_err(|err| Error::Io(format!("Failed to stop process {}", process.pid), err))?;
+	}
+
+	let started = Instant::now();
+	let mut remaining = targets;
+
+	while !remaining.is_empty() && started.elapsed() < args.timeout {
+		thread::sleep(POLL_INTERVAL);
+
+		remaining.retain(|process| {
+			let running = process.is_running();
+
+			if !running {
+				eprintln!("Stopped process {}", process.pid);
+			}
+
+			running
+		});
+	}
+
+	for process in &remaining {
+		process
+			.kill()
+			.map_err(|err| Error::Io(format!("Failed to kill process {}", process.pid), err))?;
+
+		eprintln!("Killed process {}, which didn't exit within {}", process.pid, humantime::format_duration(args.timeout));
+	}
+
+	Ok(ExitCode::SUCCESS)
+}
+
+/// Asks for confirmation on the terminal. Without one, --yes is required so
+/// scripts don't hang waiting for an answer.
+fn confirm(targets: &[StudioProcess]) -> Result<bool> {
+	if !io::stdin().is_terminal() {
+		return Err(Error::Usage("pass --yes to stop Roblox Studio without a terminal to confirm it".into()));
+	}
+
+	eprint!("Stop Roblox Studio (process {})? Unsaved changes will be lost [y/N] ", pids(targets));
+	io::stderr()
+		.flush()
+		.map_err(|err| Error::Io("Failed to write to the terminal".into(), err))?;
+
+	let mut answer = String::new();
+	io::stdin()
+		.lock()
+		.read_line(&mut answer)
+		.map_err(|err| Error::Io("Failed to read the answer".into(), err))?;
+
+	Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
+fn pids(processes: &[StudioProcess]) -> String {
+	processes
+		.iter()
+		.map(|process| process.pid.to_string())
+		.collect::<Vec<_>>()
+		.join(", ")
+}
@@ -0,0 +1,130 @@
+use std::{
+	path::{Path, PathBuf},
+	process::ExitCode,
+};
+
+use serde::Serialize;
+
+use roblox_studio::roblox_install::RobloxStudio;
+
+use crate::cli::{DiscoveryArgs, LocateArgs, StudioPath};
+use crate::error::{Error, Result};
+
+#[derive(Serialize)]
+struct LocateOutput<'a> {
+	application: &'a Path,
+	content: &'a Path,
+	plugins: &'a Path,
+	managed_plugins: Option<PathBuf>,
+	built_in_plugins: &'a Path,
+	root: &'a Path,
+	logs: Option<PathBuf>,
+	auto_saves: Option<PathBuf>,
+	app_data: Option<PathBuf>,
+	/// The product version, like `0.612.0.6120532`.
+	version: Option<String>,
+	/// The `version-<hash>` directory of the installation.
+	version_hash: Option<&'a str>,
+	channel: String,
+	base_url: Option<String>,
+}
+
+pub fn run(args: &LocateArgs, discovery: &DiscoveryArgs) -> Result<ExitCode> {
+	let roblox_studio = RobloxStudio::locate_with(&discovery.options())?;
+
+	if args.ensure_plugins_dir {
+		roblox_studio
+			.ensure_plugins_dir()
+			.map_err(|err| Error::Io("Failed to create the plugins directory".into(), err))?;
+	}
+
+	if let Some(path) = args.path {
+		let path = studio_path(&roblox_studio, path)?;
+
+		println!("{}", path.display());
+		return Ok(ExitCode::SUCCESS);
+	}
+
+	if args.json {
+		let output = LocateOutput {
+			application: roblox_studio.application_path(),
+			content: roblox_studio.content_path(),
+			plugins: roblox_studio.plugins_path(),
+			managed_plugins: roblox_studio.managed_plugins_path(),
+			built_in_plugins: roblox_studio.built_in_plugins_path(),
+			#[allow(deprecated)]
+			root: roblox_studio.root_path(),
+			logs: roblox_studio.logs_path(),
+			auto_saves: roblox_studio.auto_saves_path(),
+			app_data: roblox_studio.studio_app_data_path(),
+			version: roblox_studio.version().map(|version| version.to_string()),
+			version_hash: roblox_studio.version_hash(),
+			channel: roblox_studio.channel(),
+			base_url: roblox_studio.base_url(),
+		};
+
+		println!("{}", serde_json::to_string_pretty(&output)?);
+		return Ok(ExitCode::SUCCESS);
+	}
+
+	println!("Application:      {}", roblox_studio.application_path().display());
+	println!("Content:          {}", roblox_studio.content_path().display());
+	println!("Plugins:          {}", roblox_studio.plugins_path().display());
+
+	if let Some(managed_plugins) = roblox_studio.managed_plugins_path() {
+		println!("Managed plugins:  {}", managed_plugins.display());
+	}
+
+	println!("Built-in plugins: {}", roblox_studio.built_in_plugins_path().display());
+
+	if let Some(logs) = roblox_studio.logs_path() {
+		println!("Logs:             {}", logs.display());
+	}
+
+	if let Some(auto_saves) = roblox_studio.auto_saves_path() {
+		println!("Auto-saves:       {}", auto_saves.display());
+	}
+
+	if let Some(app_data) = roblox_studio.studio_app_data_path() {
+		println!("App data:         {}", app_data.display());
+	}
+
+	match (roblox_studio.version(), roblox_studio.version_hash()) {
+		(Some(version), Some(hash)) => println!("Version:          {} ({})", version, hash),
+		(Some(version), None) => println!("Version:          {}", version),
+		(None, Some(hash)) => println!("Version:          {}", hash),
+		(None, None) => {}
+	}
+
+	println!("Channel:          {}", roblox_studio.channel());
+
+	if let Some(base_url) = roblox_studio.base_url() {
+		println!("Base URL:         {}", base_url);
+	}
+
+	Ok(ExitCode::SUCCESS)
+}
+
+/// The path of the installation chosen on the command line.
+pub fn studio_path(roblox_studio: &RobloxStudio, path: StudioPath) -> Result<PathBuf> {
+	Ok(match path {
+		StudioPath::Application => roblox_studio.application_path().to_owned(),
+		StudioPath::Content => roblox_studio.content_path().to_owned(),
+		StudioPath::Plugins => roblox_studio.plugins_path().to_owned(),
+		StudioPath::ManagedPlugins => roblox_studio
+			.managed_plugins_path()
+			.ok_or_else(|| Error::Usage("no plugin was installed from the marketplace".into()))?,
+		StudioPath::BuiltInPlugins => roblox_studio.built_in_plugins_path().to_owned(),
+		#[allow(deprecated)]
+		StudioPath::Root => roblox_studio.root_path().to_owned(),
+		StudioPath::Logs => roblox_studio
+			.logs_path()
+			.ok_or_else(|| Error::Usage("the logs directory of this installation is unknown".into()))?,
+		StudioPath::AutoSaves => roblox_studio
+			.auto_saves_path()
+			.ok_or_else(|| Error::Usage("the auto-saves directory of this installation is unknown".into()))?,
+		StudioPath::AppData => roblox_studio
+			.studio_app_data_path()
+			.ok_or_else(|| Error::Usage("the app data directory of this installation is unknown".into()))?,
+	})
+}
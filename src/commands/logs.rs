@@ -0,0 +1,190 @@
+use std::{
+	collections::HashSet,
+	fs,
+	io::{self, Read, Seek, SeekFrom, Write},
+	path::{Path, PathBuf},
+	process::ExitCode,
+	thread,
+	time::Duration,
+};
+
+use roblox_studio::logs::{LogFilter, LogRecord, Severity};
+use roblox_studio::roblox_install::RobloxStudio;
+
+use crate::cli::{DiscoveryArgs, LogsArgs, LogsCommand, LogsTailArgs};
+use crate::error::{Error, Result};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+pub fn run(args: &LogsArgs, discovery: &DiscoveryArgs) -> Result<ExitCode> {
+	let roblox_studio = RobloxStudio::locate_with(&discovery.options())?;
+
+	match &args.command {
+		LogsCommand::Path => {
+			let directory = roblox_studio
+				.logs_path()
+				.ok_or_else(|| Error::Usage("the logs directory of this installation is unknown".into()))?;
+
+			println!("{}", directory.display());
+			Ok(ExitCode::SUCCESS)
+		}
+		LogsCommand::Tail(args) => tail(args, &roblox_studio),
+	}
+}
+
+/// Prints the end of the latest log, then follows it. Logs that appear while
+/// following are from newer sessions, which are followed instead. The name of
+/// each log goes to stderr, so stdout only has the log's contents.
+fn tail(args: &LogsTailArgs, roblox_studio: &RobloxStudio) -> Result<ExitCode> {
+	let mut filter = LogFilter::new();
+
+	if args.errors {
+		filter = filter.min_severity(Severity::Error);
+	}
+
+	for channel in &args.log_channel {
+		filter = filter.channel(channel);
+	}
+
+	let mut lines = Lines::new(filter);
+	let logs = roblox_studio.studio_logs();
+	let mut seen: HashSet<PathBuf> = logs.iter().map(|log| log.path.clone()).collect();
+
+	let mut current = if args.new { None } else { logs.last().map(|log| log.path.clone()) };
+	let mut offset = 0;
+	let mut stdout = io::stdout().lock();
+
+	match &current {
+		Some(path) => {
+			eprintln!("==> {} <==", path.display());
+			offset = print_last_lines(path, args.lines, &mut lines, &mut stdout)?;
+		}
+		None if args.no_follow => return Err(Error::Usage("Roblox Studio hasn't written any log".into())),
+		None => eprintln!("Waiting for Roblox Studio to start a session"),
+	}
+
+	if args.no_follow {
+		return Ok(ExitCode::SUCCESS);
+	}
+
+	loop {
+		thread::sleep(POLL_INTERVAL);
+
+		if let Some(newer) = roblox_studio
+			.studio_logs()
+			.into_iter()
+			.rfind(|log| !seen.contains(&log.path))
+		{
+			eprintln!("==> {} <==", newer.path.display());
+			seen.insert(newer.path.clone());
+			current = Some(newer.path);
+			offset = 0;
+			lines.reset();
+		}
+
+		if let Some(path) = &current {
+			offset = copy_from(path, offset, &mut lines, &mut stdout)?;
+		}
+	}
+}
+
+/// Splits what's read from a log into lines, and keeps the lines of the records
+/// the filter keeps. Lines that don't start a record belong to the one before.
+struct Lines {
+	filter: LogFilter,
+	partial: Vec<u8>,
+	keeping: bool,
+}
+
+impl Lines {
+	fn new(filter: LogFilter) -> Lines {
+		Lines {
+			keeping: filter.is_empty(),
+			filter,
+			partial: Vec::new(),
+		}
+	}
+
+	/// Forgets what was read, to read another log.
+	fn reset(&mut self) {
+		self.partial.clear();
+		self.keeping = self.filter.is_empty();
+	}
+
+	/// Adds what was read, and returns the lines it completed that are kept,
+	/// with their newlines. A line missing its newline waits for the rest of it.
+	fn push(&mut self, bytes: &[u8]) -> Vec<Vec<u8>> {
+		self.partial.extend_from_slice(bytes);
+
+		let mut kept = Vec::new();
+
+		while let Some(end) = self.partial.iter().position(|byte| *byte == b'\n') {
+			let line: Vec<u8> = self.partial.drain(..=end).collect();
+
+			if !self.filter.is_empty()
+				&& let Some(record) = LogRecord::parse(String::from_utf8_lossy(&line).trim_end())
+			{
+				self.keeping = self.filter.matches(&record);
+			}
+
+			if self.keeping {
+				kept.push(line);
+			}
+		}
+
+		kept
+	}
+}
+
+/// Prints the last lines of the log that are kept, and returns its size, where
+/// following it starts.
+fn print_last_lines(path: &Path, count: usize, lines: &mut Lines, output: &mut impl Write) -> Result<u64> {
+	let contents = fs::read(path).map_err(|err| Error::Io(format!("Failed to read {}", path.display()), err))?;
+	let kept = lines.push(&contents);
+
+	write_lines(&kept[kept.len().saturating_sub(count)..], output)?;
+
+	Ok(contents.len() as u64)
+}
+
+/// Prints the lines kept from what was written to the log since `offset`, and
+/// returns its new size. A log that shrank was truncated, so it's read from the
+/// start.
+fn copy_from(path: &Path, offset: u64, lines: &mut Lines, output: &mut impl Write) -> Result<u64> {
+	let read_error = |err| Error::Io(format!("Failed to read {}", path.display()), err);
+
+	let mut file = match fs::File::open(path) {
+		Ok(file) => file,
+		// The log was removed, so there's nothing more to follow in it.
+		Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(offset),
+		Err(err) => return Err(read_error(err)),
+	};
+
+	let size = file.metadata().map_err(read_error)?.len();
+	let offset = if size < offset {
+		lines.reset();
+		0
+	} else {
+		offset
+	};
+
+	if size == offset {
+		return Ok(offset);
+	}
+
+	let mut contents = Vec::new();
+	file.seek(SeekFrom::Start(offset)).map_err(read_error)?;
+	file.read_to_end(&mut contents).map_err(read_error)?;
+
+	write_lines(&lines.push(&contents), output)?;
+
+	Ok(offset + contents.len() as u64)
+}
+
+fn write_lines(lines: &[Vec<u8>], output: &mut impl Write) -> Result<()> {
+	lines
+		.iter()
+		.try_for_each(|line| output.write_all(line))
+		.and_then(|()| output.flush())
+		.map_err(|err| Error::Io("Failed to write the log".into(), err))
+}
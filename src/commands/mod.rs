@@ -0,0 +1,18 @@
+pub mod bench;
+pub mod cache;
+pub mod completions;
+pub mod deeplink;
+pub mod doctor;
+pub mod history;
+pub mod install;
+pub mod kill;
+pub mod locate;
+pub mod logs;
+pub mod open;
+pub mod open_folder;
+pub mod playtest;
+pub mod recent;
+pub mod stats;
+pub mod update;
+pub mod version;
+pub mod versions;
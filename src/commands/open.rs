@@ -0,0 +1,802 @@
+use std::{
+	env, fs, io,
+	path::{self, Path, PathBuf},
+	process::{Child, Command, ExitCode},
+	time::{Instant, SystemTime},
+};
+
+use tracing::{debug, warn};
+
+use roblox_studio::crash::CrashArtifact;
+use roblox_studio::deeplink::{self, WebPlace};
+use roblox_studio::deploy;
+use roblox_studio::process::{self, StudioProcess};
+use roblox_studio::recent::RecentPlace;
+use roblox_studio::{DeepLink, LaunchOptions, StudioTask};
+use roblox_studio::roblox_install::{self, RobloxStudio};
+
+use crate::cli::{DiscoveryArgs, OpenArgs};
+use crate::commands::install;
+use crate::config::{self, GlobalConfig, Profile, Project};
+use crate::error::{Error, Result};
+use crate::events::{Event, Events, StartupWatch};
+use crate::format;
+use crate::journal::{self, Entry};
+use crate::log_watch::LogWatch;
+use crate::monitor::Monitor;
+use crate::notify;
+use crate::overrides::{self, Overrides};
+use crate::singleton;
+use crate::supervisor::{self, Attachment};
+use crate::webhook::Webhook;
+use crate::window::{self, Placement};
+
+pub fn run(args: &OpenArgs, discovery: &DiscoveryArgs, config: &GlobalConfig) -> Result<ExitCode> {
+	let project = Project::discover()?;
+	let roblox_studio = locate(discovery, project.as_ref(), args.dry_run)?;
+	let events = Events::new(args.events);
+
+	events.emit(Event::Located {
+		path: roblox_studio.application_path(),
+		version: roblox_studio.version_hash(),
+	});
+
+	let profile_name = args
+		.profile
+		.as_deref()
+		.or_else(|| project.as_ref()?.config.profile.as_deref());
+
+	let profile = match profile_name {
+		Some(name) => Some((name, config::find_profile(name, project.as_ref(), config)?)),
+		None => None,
+	};
+
+	let mut launch = LaunchOptions::new();
+	let mut place_file = None;
+	let mut place_id = None;
+	// How a place published to Roblox is handed to a running Studio with --reuse.
+	let mut deep_link = None;
+
+	if let Some(id) = args.place_id {
+		let universe_id = match args.universe_id {
+			None if args.team_create => Some(deeplink::universe_id(id).map_err(Error::Launch)?),
+			universe_id => universe_id,
+		};
+
+		place_id = Some(id);
+		deep_link = Some(DeepLink::EditPlace {
+			place_id: id,
+			universe_id,
+		});
+		launch = launch.task(StudioTask::EditPlace {
+			place_id: id,
+			universe_id,
+		});
+	} else if args.recent {
+		launch = match roblox_studio.recent_places().into_iter().next() {
+			Some(RecentPlace::File(place)) => {
+				place_file = Some(place.clone());
+				launch.place(place)
+			}
+			Some(RecentPlace::Cloud { place_id: id }) => {
+				place_id = Some(id);
+				deep_link = Some(DeepLink::EditPlace {
+					place_id: id,
+					universe_id: None,
+				});
+				launch.task(StudioTask::EditPlace {
+					place_id: id,
+					universe_id: None,
+				})
+			}
+			_ => return Err(Error::Usage("Roblox Studio hasn't recorded any recent place".into())),
+		};
+	} else if let Some(url) = args.place.as_deref().and_then(web_url) {
+		let task = web_place_task(url)?;
+
+		if let StudioTask::EditPlace { place_id: id, universe_id } = task {
+			place_id = Some(id);
+			deep_link = Some(DeepLink::EditPlace { place_id: id, universe_id });
+		}
+
+		launch = launch.task(task);
+	} else if let Some(place) = args.place.clone().or_else(|| project.as_ref()?.place()) {
+		place_file = Some(place.clone());
+		launch = launch.place(place);
+	}
+
+	let opens_place = args.place_id.is_some() || args.recent || args.place.is_some() || place_file.is_some();
+	let running = process::running();
+
+	if args.reuse && !running.is_empty() {
+		return reuse(&roblox_studio, &running, place_file.as_deref(), deep_link.as_ref(), args.dry_run);
+	}
+
+	if let Some((_, profile)) = profile {
+		launch = launch.args(&profile.studio_args);
+	}
+
+	launch = launch.args(&args.studio_args);
+
+	// Changes a profile makes to files are reverted once Studio exits, so it has
+	// to be waited on.
+	let reverts_profile = profile.is_some_and(|(_, profile)| profile.changes_files());
+	let shadowed = if args.fresh_profile {
+		fresh_profile_paths(&roblox_studio)
+	} else if args.no_plugins {
+		plugin_paths(&roblox_studio)
+	} else {
+		Vec::new()
+	};
+
+	// Plugins allowed on the command line take precedence over the profile's.
+	let allowed_plugins = args
+		.only_plugins
+		.as_deref()
+		.or_else(|| profile?.1.plugins.as_deref());
+
+	let monitors = args.monitor_resources || args.max_memory.is_some();
+	let waits_for_status = args.wait
+		|| args.attached
+		|| args.timeout.is_some()
+		|| monitors
+		|| args.restart_on_crash.is_some()
+		|| args.events
+		|| args.stream_output;
+	let wait = waits_for_status
+		|| reverts_profile
+		|| !shadowed.is_empty()
+		|| args.only_plugins.is_some()
+		|| args.notify;
+
+	let mut studio_env = config.env.clone();
+
+	if let Some(project) = &project {
+		studio_env.extend(project.config.env.clone());
+	}
+
+	if let Some((_, profile)) = profile {
+		studio_env.extend(profile.env.clone());
+	}
+
+	for (name, value) in studio_env {
+		launch = launch.env(name, value);
+	}
+
+	// Waiting needs Studio to be a child process, otherwise it's started in a way
+	// that outlives the terminal.
+	launch = launch.detached(!wait);
+
+	let placement = Placement {
+		minimized: args.minimized,
+		maximized: args.maximized,
+		monitor: args.monitor,
+		geometry: args.geometry,
+	};
+
+	let hooks = project
+		.as_ref()
+		.map(|project| (project.directory(), &project.config.hooks));
+
+	if args.dry_run {
+		print_command(&launch.to_command(&roblox_studio))?;
+
+		for path in &shadowed {
+			println!("Shadowed:          {}", path.display());
+		}
+
+		if let Some((name, profile)) = profile {
+			print_profile(name, profile, &roblox_studio)?;
+		}
+
+		if let Some(plugins) = allowed_plugins {
+			println!("Plugins:           {}", plugins.join(", "));
+		}
+
+		if !placement.is_empty() {
+			println!("Window:            {}", describe_placement(&placement));
+		}
+
+		if let Some((_, hooks)) = hooks
+			&& let Some(hook) = &hooks.before_open
+		{
+			println!("Before open:       {}", hook);
+		}
+
+		return Ok(ExitCode::SUCCESS);
+	}
+
+	roblox_studio
+		.check_launchable()
+		.map_err(Error::Launch)?;
+
+	for issue in roblox_studio.validate() {
+		warn!("{}", issue);
+	}
+
+	// Another Studio would see its settings and plugins disappear while they're
+	// moved out of the way for this session.
+	let moves_user_files = !shadowed.is_empty() || allowed_plugins.is_some();
+
+	if let Some(process) = running.first()
+		&& moves_user_files
+	{
+		return Err(Error::Usage(format!(
+			"Roblox Studio is already running (process {}), close it first: --fresh-profile, --no-plugins and \
+			 --only-plugins move its settings or plugins out of the way for the session",
+			process.pid
+		)));
+	}
+
+	// Another Studio could be running with the changes of its session, so stale
+	// ones are only restored when none is.
+	if running.is_empty() {
+		restore_stale_overrides(&roblox_studio);
+	}
+
+	if let Some(process) = running.first()
+		&& !args.new_instance
+	{
+		warn!(
+			"Roblox Studio is already running (process {}), starting another instance. Pass --reuse to open the place in it instead",
+			process.pid
+		);
+	}
+
+	if let Some((directory, hooks)) = hooks
+		&& let Some(hook) = &hooks.before_open
+	{
+		run_hook("before-open", hook, directory)?;
+	}
+
+	// Ctrl-C is caught before anything is changed, so it can't stop the CLI
+	// before the changes are reverted.
+	supervisor::handle_interrupts().map_err(Error::Wait)?;
+
+	let mut overrides = Overrides::new();
+
+	for path in &shadowed {
+		overrides
+			.shadow(path)
+			.map_err(|err| Error::Io(format!("Failed to shadow {}", path.display()), err))?;
+	}
+
+	if let Some((name, profile)) = profile {
+		apply_profile(name, profile, &roblox_studio, &mut overrides)?;
+	}
+
+	if let Some(plugins) = allowed_plugins {
+		overrides
+			.allow_plugins(roblox_studio.plugins_path(), plugins)
+			.map_err(|err| Error::Io("Failed to disable the other plugins".to_owned(), err))?;
+	}
+
+	if args.fresh_profile && !waits_for_status {
+		eprintln!("Waiting for Roblox Studio to exit to restore its settings and plugins");
+	} else if (args.no_plugins || args.only_plugins.is_some()) && !waits_for_status {
+		eprintln!("Waiting for Roblox Studio to exit to restore its plugins");
+	} else if reverts_profile && !waits_for_status {
+		eprintln!("Waiting for Roblox Studio to exit to revert the {} profile", profile_name.unwrap_or_default());
+	}
+
+	// Dropping the attachment last, after Studio exited, only cleans up what it
+	// left running.
+	// The log is only followed while waiting for Studio, to open the place
+	// without blocking otherwise.
+	let watches_log = wait && (args.stream_output || opens_place);
+	let place_name = match (&place_file, place_id) {
+		(Some(place), _) => place.file_name().unwrap_or(place.as_os_str()).to_string_lossy().into_owned(),
+		(None, Some(place_id)) => format!("place {}", place_id),
+		(None, None) => "no place".to_owned(),
+	};
+	let mut log_watch = watches_log.then(|| LogWatch::new(&roblox_studio, args.stream_output, opens_place));
+	let journals = config.journal != Some(false);
+	let journal_place = place_file.as_deref().map(|place| path::absolute(place).unwrap_or_else(|_| place.to_owned()));
+
+	let mut started = SystemTime::now();
+	let (mut child, mut _attachment) = start(&roblox_studio, &launch, args, &placement, events)?;
+	let mut entry = Entry::new(&roblox_studio, started, journal_place.clone(), place_id);
+	let mut webhook = Webhook::new(config.webhook.as_deref());
+
+	webhook.send(&started_message(&roblox_studio, &place_name));
+
+	if !wait {
+		if journals {
+			journal::record(&entry);
+		}
+
+		return Ok(ExitCode::SUCCESS);
+	}
+
+	let max_restarts = args.restart_on_crash.unwrap_or(0);
+	let mut restarts = 0;
+
+	let mut stopped = None;
+
+	let status = loop {
+		let deadline = args.timeout.map(|timeout| Instant::now() + timeout);
+		let mut monitor = monitors.then(|| Monitor::new(args.max_memory));
+		let mut startup = StartupWatch::new(events, opens_place);
+		let mut notified_load = false;
+
+		let status = supervisor::wait_watching(&mut child, |child| {
+			startup.poll(child);
+
+			if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+				stopped = Some(Error::Timeout(args.timeout.unwrap_or_default()));
+			} else if let Some(monitor) = &mut monitor
+				&& monitor.poll(child)
+			{
+				stopped = Some(Error::MemoryLimit(args.max_memory.unwrap_or_default()));
+			} else if let Some(log_watch) = &mut log_watch
+				&& let Some(failure) = log_watch.poll(&roblox_studio)
+			{
+				if args.notify {
+					notify::send("Roblox Studio couldn't open the place", &format!("{}: {}", place_name, failure));
+				}
+
+				stopped = Some(Error::LoadFailed(failure));
+			}
+
+			if args.notify
+				&& !notified_load
+				&& let Some(loaded_after) = log_watch.as_ref().and_then(LogWatch::loaded_after)
+			{
+				notified_load = true;
+				notify::send(
+					"Roblox Studio loaded the place",
+					&format!("{} loaded in {:.1}s", place_name, loaded_after.as_secs_f64()),
+				);
+			}
+
+			Ok(stopped.is_some())
+		})
+		.map_err(Error::Wait)?;
+
+		if let Some(log_watch) = &mut log_watch {
+			log_watch.finish(&roblox_studio);
+		}
+
+		if let Some(monitor) = &monitor {
+			eprintln!("{}", monitor.summary());
+		}
+
+		let crashed = status.is_some_and(|status| !status.success()) && !supervisor::interrupted();
+		let pid = child.id();
+		let code = status.and_then(|status| status.code());
+
+		if crashed {
+			let artifacts = report_crash(&roblox_studio, started, args.crash_artifacts.as_deref())?;
+
+			events.emit(Event::Crashed {
+				pid,
+				code,
+				artifacts: artifacts.iter().map(|artifact| artifact.path.as_path()).collect(),
+			});
+
+			if args.notify {
+				let code = code.map_or_else(|| "no exit code".to_owned(), |code| format!("exit code {}", code));
+				let restarting = if restarts < max_restarts { ", restarting it" } else { "" };
+
+				notify::send("Roblox Studio crashed", &format!("{} ({}){}", place_name, code, restarting));
+			}
+		}
+
+		events.emit(Event::Exited { pid, code });
+
+		let ran_for = format::age(started.elapsed().unwrap_or_default());
+		webhook.send(&match (&stopped, code) {
+			(Some(err), _) => format!("{} (after {})", err, ran_for),
+			(None, Some(0)) => format!("Roblox Studio exited after {} ({})", ran_for, place_name),
+			(None, Some(code)) => format!("Roblox Studio crashed with exit code {} after {} ({})", code, ran_for, place_name),
+			(None, None) => format!("Roblox Studio was stopped after {} ({})", ran_for, place_name),
+		});
+
+		if journals {
+			let load_time = log_watch.as_ref().and_then(LogWatch::loaded_after);
+
+			entry.finish(started.elapsed().unwrap_or_default(), load_time, status);
+			journal::record(&entry);
+		}
+
+		if crashed && restarts < max_restarts {
+			restarts += 1;
+			warn!("Roblox Studio exited abnormally, restarting it ({}/{})", restarts, max_restarts);
+
+			log_watch = watches_log.then(|| LogWatch::new(&roblox_studio, args.stream_output, opens_place));
+			started = SystemTime::now();
+			(child, _attachment) = start(&roblox_studio, &launch, args, &placement, events)?;
+			entry = Entry::new(&roblox_studio, started, journal_place.clone(), place_id);
+			webhook.send(&started_message(&roblox_studio, &place_name));
+		} else {
+			break status;
+		}
+	};
+
+	overrides
+		.revert()
+		.map_err(|err| Error::Io("Failed to revert the changes made for this session".to_owned(), err))?;
+
+	if let Some((directory, hooks)) = hooks
+		&& let Some(hook) = &hooks.after_exit
+	{
+		run_hook("after-exit", hook, directory)?;
+	}
+
+	let Some(status) = status else {
+		return Err(stopped.unwrap_or(Error::Timeout(args.timeout.unwrap_or_default())));
+	};
+
+	if waits_for_status {
+		Ok(supervisor::exit_code(status))
+	} else {
+		Ok(ExitCode::SUCCESS)
+	}
+}
+
+fn started_message(roblox_studio: &RobloxStudio, place_name: &str) -> String {
+	match roblox_studio.version() {
+		Some(version) => format!("Roblox Studio {} started with {}", version, place_name),
+		None => format!("Roblox Studio started with {}", place_name),
+	}
+}
+
+/// Lists the crash dumps and logs Studio wrote since it was started, and copies
+/// them to the artifacts directory when one is given.
+fn report_crash(
+	roblox_studio: &RobloxStudio,
+	started: SystemTime,
+	artifacts_directory: Option<&Path>,
+) -> Result<Vec<CrashArtifact>> {
+	let artifacts = roblox_studio.crash_artifacts(started);
+
+	if artifacts.is_empty() {
+		eprintln!("Roblox Studio exited abnormally without leaving a crash dump or log");
+		return Ok(artifacts);
+	}
+
+	eprintln!("Roblox Studio exited abnormally, it left:");
+
+	for artifact in &artifacts {
+		match &artifact.signature {
+			Some(signature) => eprintln!("  {} ({})", artifact.path.display(), signature),
+			None => eprintln!("  {}", artifact.path.display()),
+		}
+	}
+
+	let Some(directory) = artifacts_directory else {
+		return Ok(artifacts);
+	};
+
+	fs::create_dir_all(directory)
+		.map_err(|err| Error::Io(format!("Failed to create {}", directory.display()), err))?;
+
+	for artifact in &artifacts {
+		let Some(name) = artifact.path.file_name() else {
+			continue;
+		};
+
+		fs::copy(&artifact.path, directory.join(name))
+			.map_err(|err| Error::Io(format!("Failed to copy {}", artifact.path.display()), err))?;
+	}
+
+	eprintln!("Copied them to {}", directory.display());
+
+	Ok(artifacts)
+}
+
+/// Starts Studio for the session, attached to this process and with its window
+/// placed as requested.
+fn start(
+	roblox_studio: &RobloxStudio,
+	launch: &LaunchOptions,
+	args: &OpenArgs,
+	placement: &Placement,
+	events: Events,
+) -> Result<(Child, Option<Attachment>)> {
+	let singleton = if args.new_instance {
+		singleton::hold().map_err(|err| Error::Io("Failed to hold Roblox's single instance mutex".to_owned(), err))?
+	} else {
+		None
+	};
+
+	let mut command = launch.to_command(roblox_studio);
+
+	if args.attached {
+		if roblox_install::wsl_version().is_some() {
+			warn!("Roblox Studio runs outside of WSL, so it can't be killed along with this process");
+		}
+
+		supervisor::prepare_attached(&mut command);
+	}
+
+	let mut child = supervisor::spawn_retrying(&mut command, args.update_wait).map_err(Error::Spawn)?;
+
+	events.emit(Event::Spawned { pid: child.id() });
+
+	let attachment = if args.attached {
+		Some(supervisor::attach(&child).map_err(|err| Error::Io("Failed to attach to Roblox Studio".to_owned(), err))?)
+	} else {
+		None
+	};
+
+	// Studio checks for a running instance as it starts, so the mutex can be
+	// released once its window is shown, at the end of this block.
+	if let Some(_singleton) = singleton
+		&& let Err(err) = window::wait_for_window(&mut child)
+	{
+		warn!("Couldn't wait for Roblox Studio to start: {}", err);
+	}
+
+	if !placement.is_empty()
+		&& let Err(err) = window::place(&mut child, placement)
+	{
+		warn!("Couldn't place Roblox Studio's window: {}", err);
+	}
+
+	Ok((child, attachment))
+}
+
+/// Hands the place file, or the deep link of a place published to Roblox, to
+/// the running instance of Studio instead of starting another one. Without a
+/// place, there's nothing to do.
+fn reuse(
+	roblox_studio: &RobloxStudio,
+	running: &[StudioProcess],
+	place: Option<&Path>,
+	deep_link: Option<&DeepLink>,
+	dry_run: bool,
+) -> Result<ExitCode> {
+	let pids: Vec<String> = running.iter().map(|process| process.pid.to_string()).collect();
+
+	let (command, place) = match (place, deep_link) {
+		(Some(place), _) => (roblox_studio.open_file_command(place), place.display().to_string()),
+		(None, Some(deep_link)) => (roblox_studio.open_url_command(&deep_link.to_url()), deep_link.to_url()),
+		(None, None) => {
+			eprintln!("Roblox Studio is already running (process {})", pids.join(", "));
+			return Ok(ExitCode::SUCCESS);
+		}
+	};
+
+	let mut command = command.ok_or_else(|| {
+		Error::Usage("--reuse isn't supported when Roblox Studio runs through a launcher".into())
+	})?;
+
+	if dry_run {
+		println!("Running:           {}", pids.join(", "));
+		print_command(&command)?;
+		return Ok(ExitCode::SUCCESS);
+	}
+
+	let status = command.status().map_err(Error::Spawn)?;
+
+	if !status.success() {
+		return Err(Error::Io(
+			format!("Failed to open {} in the running Roblox Studio", place),
+			io::Error::other(format!("the file or URL association exited with {}", status)),
+		));
+	}
+
+	eprintln!("Opened {} in the running Roblox Studio", place);
+
+	Ok(ExitCode::SUCCESS)
+}
+
+/// The place argument as a URL, when it's one rather than a place file. Only
+/// arguments with a scheme are URLs, since a path like `roblox.com/place.rbxl`
+/// can be a place file too. Whether it's a Roblox URL is checked when it's read.
+fn web_url(place: &Path) -> Option<&str> {
+	let place = place.to_str()?;
+	let scheme = place.split_once("://")?.0;
+
+	(scheme.eq_ignore_ascii_case("https") || scheme.eq_ignore_ascii_case("http")).then_some(place)
+}
+
+/// Edits the place from a roblox.com URL. Its universe ID is looked up when the
+/// URL doesn't have it, which is only needed to join a Team Create session.
+fn web_place_task(url: &str) -> Result<StudioTask> {
+	let place = WebPlace::from_url(url).ok_or_else(|| Error::Usage(format!("{} isn't the URL of a Roblox place", url)))?;
+
+	let universe_id = place.universe_id.or_else(|| {
+		deeplink::universe_id(place.place_id)
+			.map_err(|err| warn!("Couldn't find the universe of place {}: {}", place.place_id, err))
+			.ok()
+	});
+
+	Ok(StudioTask::EditPlace {
+		place_id: place.place_id,
+		universe_id,
+	})
+}
+
+fn describe_placement(placement: &Placement) -> String {
+	let mut parts = Vec::new();
+
+	if let Some(monitor) = placement.monitor {
+		parts.push(format!("monitor {}", monitor));
+	}
+
+	if let Some(geometry) = placement.geometry {
+		parts.push(geometry.to_string());
+	}
+
+	if placement.maximized {
+		parts.push("maximized".to_owned());
+	} else if placement.minimized {
+		parts.push("minimized".to_owned());
+	}
+
+	parts.join(", ")
+}
+
+/// Applies the FFlag overrides of a profile, which are reverted when the
+/// overrides are dropped. Its plugins are applied along with --only-plugins.
+fn apply_profile(name: &str, profile: &Profile, roblox_studio: &RobloxStudio, overrides: &mut Overrides) -> Result<()> {
+	if !profile.fflags.is_empty() {
+		overrides
+			.set_fflags(&client_app_settings_path(roblox_studio)?, &profile.fflags)
+			.map_err(|err| Error::Io(format!("Failed to apply the {} profile", name), err))?;
+	}
+
+	Ok(())
+}
+
+/// The directories of the user's plugins, local and installed from the
+/// marketplace.
+fn plugin_paths(roblox_studio: &RobloxStudio) -> Vec<PathBuf> {
+	let mut paths = vec![roblox_studio.plugins_path().to_owned()];
+
+	paths.extend(roblox_studio.managed_plugins_path());
+	paths
+}
+
+/// The settings and plugins moved out of the way for a session with a fresh
+/// profile, so Studio starts as if it was just installed.
+fn fresh_profile_paths(roblox_studio: &RobloxStudio) -> Vec<PathBuf> {
+	let mut paths = plugin_paths(roblox_studio);
+
+	if let Some(app_data) = roblox_studio.studio_app_data_path() {
+		paths.push(app_data.join("GlobalSettings_13.xml"));
+	}
+
+	paths
+}
+
+/// Restores the settings, plugins and FFlags a session that was killed before
+/// Studio exited left moved aside or changed.
+fn restore_stale_overrides(roblox_studio: &RobloxStudio) {
+	let mut paths = fresh_profile_paths(roblox_studio);
+
+	// `ClientSettings` is next to the executable, and isn't created for this.
+	if let Some(directory) = roblox_studio.application_path().parent() {
+		paths.push(directory.join("ClientSettings").join("ClientAppSettings.json"));
+	}
+
+	for path in paths {
+		if let Err(err) = overrides::restore_stale(&path) {
+			warn!("Failed to restore {}: {}", path.display(), err);
+		}
+	}
+}
+
+/// Path of the file Studio reads FFlag overrides from.
+fn client_app_settings_path(roblox_studio: &RobloxStudio) -> Result<PathBuf> {
+	let settings = roblox_studio
+		.client_settings_path()
+		.map_err(|err| Error::Io("Failed to create the ClientSettings directory".into(), err))?;
+
+	Ok(settings.join("ClientAppSettings.json"))
+}
+
+fn print_profile(name: &str, profile: &Profile, roblox_studio: &RobloxStudio) -> Result<()> {
+	println!("Profile:           {}", name);
+
+	if !profile.fflags.is_empty() {
+		println!("FFlags file:       {}", client_app_settings_path(roblox_studio)?.display());
+	}
+
+	for (flag, value) in &profile.fflags {
+		println!("FFlag:             {}={}", flag, value);
+	}
+
+	Ok(())
+}
+
+/// Runs a hook from the project configuration with the platform's shell.
+fn run_hook(name: &'static str, hook: &str, directory: &Path) -> Result<()> {
+	debug!("Running the {} hook: {}", name, hook);
+
+	let mut command = if cfg!(target_os = "windows") {
+		let mut command = Command::new("cmd");
+		command.arg("/C").arg(hook);
+		command
+	} else {
+		let mut command = Command::new("sh");
+		command.arg("-c").arg(hook);
+		command
+	};
+
+	let status = command
+		.current_dir(directory)
+		.status()
+		.map_err(|err| Error::Io(format!("Failed to run the {} hook", name), err))?;
+
+	if status.success() {
+		Ok(())
+	} else {
+		Err(Error::Hook(name, status))
+	}
+}
+
+/// Locates Studio, honoring the version pinned by the project configuration
+/// unless --studio-version is given. A pinned version that isn't installed is
+/// installed next to the current installation first, or where Roblox installs
+/// Studio when there's none, see [`install::default_root`].
+pub fn locate(discovery: &DiscoveryArgs, project: Option<&Project>, dry_run: bool) -> Result<RobloxStudio> {
+	let pinned = match discovery.studio_version {
+		Some(_) => None,
+		None => project.and_then(|project| Some((project.config.studio_version.as_ref()?, &project.path))),
+	};
+
+	let Some((version, project_path)) = pinned else {
+		return Ok(RobloxStudio::locate_with(&discovery.options())?);
+	};
+
+	let options = discovery.options().version(version);
+
+	match RobloxStudio::locate_with(&options) {
+		Err(roblox_install::Error::VersionNotInstalled(_) | roblox_install::Error::NotInstalled) if !dry_run => {
+			eprintln!(
+				"Roblox Studio {} is pinned by {} but isn't installed",
+				version,
+				project_path.display()
+			);
+
+			let channel = discovery.deployment_channel();
+			let version_hash = deploy::resolve_version_hash(channel, version)
+				.map_err(Error::Install)?;
+			let root = install::default_root(discovery)?;
+
+			install::install(channel, &version_hash, &root)?;
+
+			Ok(RobloxStudio::locate_with(&options.version(version_hash))?)
+		}
+		result => Ok(result?),
+	}
+}
+
+pub fn print_command(command: &Command) -> Result<()> {
+	let working_directory = match command.get_current_dir() {
+		Some(directory) => directory.to_owned(),
+		None => env::current_dir()
+			.map_err(|err| Error::Io("Failed to read the working directory".to_owned(), err))?,
+	};
+
+	println!("Executable:        {}", command.get_program().to_string_lossy());
+	println!("Working directory: {}", working_directory.display());
+	println!("Arguments:         {:?}", command.get_args().collect::<Vec<_>>());
+
+	for (name, value) in command.get_envs() {
+		if let Some(value) = value {
+			println!("Environment:       {}={}", name.to_string_lossy(), value.to_string_lossy());
+		}
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn web_url_needs_a_scheme() {
+		let url = "https://www.roblox.com/games/1818/Classic-Crossroads";
+
+		assert_eq!(web_url(Path::new(url)), Some(url));
+		assert_eq!(web_url(Path::new("HTTP://roblox.com/games/1818")), Some("HTTP://roblox.com/games/1818"));
+		assert_eq!(web_url(Path::new("~/roblox.com/place.rbxl")), None);
+		assert_eq!(web_url(Path::new("www.roblox.com/games/1818")), None);
+		assert_eq!(web_url(Path::new("place.rbxl")), None);
+	}
+}
@@ -0,0 +1,39 @@
+use std::process::ExitCode;
+
+use roblox_studio::roblox_install::RobloxStudio;
+
+use crate::cli::{DiscoveryArgs, OpenFolderArgs, StudioPath};
+use crate::commands::locate;
+use crate::error::{Error, Result};
+
+pub fn run(args: &OpenFolderArgs, discovery: &DiscoveryArgs) -> Result<ExitCode> {
+	let roblox_studio = RobloxStudio::locate_with(&discovery.options())?;
+
+	if args.ensure_plugins_dir {
+		roblox_studio
+			.ensure_plugins_dir()
+			.map_err(|err| Error::Io("Failed to create the plugins directory".into(), err))?;
+	}
+
+	let mut directory = locate::studio_path(&roblox_studio, args.folder)?;
+
+	if matches!(args.folder, StudioPath::Application) && !directory.is_dir() {
+		directory.pop();
+	}
+
+	if !directory.is_dir() {
+		return Err(Error::Usage(format!("{} doesn't exist yet", directory.display())));
+	}
+
+	let mut command = roblox_studio.open_folder_command(&directory);
+
+	// The file manager is left running on its own, and Explorer's exit code
+	// doesn't tell whether it opened the directory.
+	command
+		.spawn()
+		.map_err(|err| Error::Io(format!("Failed to run {}", command.get_program().to_string_lossy()), err))?;
+
+	eprintln!("Opened {}", directory.display());
+
+	Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,84 @@
+use std::{
+	process::{ExitCode, ExitStatus},
+	thread,
+};
+
+use tracing::warn;
+
+use roblox_studio::{LaunchOptions, StudioTask};
+
+use crate::cli::{DiscoveryArgs, PlaytestArgs};
+use crate::commands::open;
+use crate::config::{GlobalConfig, Project};
+use crate::error::{Error, Result};
+use crate::supervisor;
+use crate::webhook::Webhook;
+
+pub fn run(args: &PlaytestArgs, discovery: &DiscoveryArgs, config: &GlobalConfig) -> Result<ExitCode> {
+	let project = Project::discover()?;
+	let roblox_studio = open::locate(discovery, project.as_ref(), args.dry_run)?;
+
+	let place = args
+		.place
+		.clone()
+		.or_else(|| project.as_ref()?.place())
+		.ok_or_else(|| Error::Usage("no place file was given, and the project doesn't set one".into()))?;
+
+	let place_name = place.file_name().unwrap_or(place.as_os_str()).to_string_lossy().into_owned();
+	let server = LaunchOptions::new()
+		.task(StudioTask::StartServer)
+		.place(place);
+	let client = LaunchOptions::new().task(StudioTask::StartClient);
+
+	if args.dry_run {
+		open::print_command(&server.to_command(&roblox_studio))?;
+
+		for _ in 0..args.clients {
+			println!();
+			open::print_command(&client.to_command(&roblox_studio))?;
+		}
+
+		return Ok(ExitCode::SUCCESS);
+	}
+
+	roblox_studio
+		.check_launchable()
+		.map_err(Error::Launch)?;
+
+	for issue in roblox_studio.validate() {
+		warn!("{}", issue);
+	}
+
+	let mut webhook = Webhook::new(config.webhook.as_deref());
+	let mut children = vec![roblox_studio.launch_with(&server).map_err(Error::Spawn)?];
+
+	webhook.send(&format!("Playtest of {} started with {} clients", place_name, args.clients));
+
+	if args.clients > 0 {
+		eprintln!("Waiting {} for the server to start", humantime::format_duration(args.client_delay));
+		thread::sleep(args.client_delay);
+	}
+
+	// When a client fails to start, the processes already started are left
+	// running, like when they're started from Studio.
+	for _ in 0..args.clients {
+		children.push(roblox_studio.launch_with(&client).map_err(Error::Spawn)?);
+	}
+
+	let statuses = supervisor::wait_all(&mut children)
+		.map_err(Error::Wait)?;
+
+	let outcome = |status: &ExitStatus| match status.code() {
+		Some(code) => format!("exited with code {}", code),
+		None => "was killed".to_owned(),
+	};
+	let mut message = format!("Playtest of {} finished: the server {}", place_name, outcome(&statuses[0]));
+
+	for (index, status) in statuses.iter().enumerate().skip(1) {
+		message.push_str(&format!(", client {} {}", index, outcome(status)));
+	}
+
+	webhook.send(&message);
+
+	Ok(supervisor::exit_code(statuses[0]))
+}
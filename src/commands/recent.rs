@@ -0,0 +1,51 @@
+use std::{path::Path, process::ExitCode};
+
+use serde::Serialize;
+
+use roblox_studio::recent::RecentPlace;
+use roblox_studio::roblox_install::RobloxStudio;
+
+use crate::cli::{DiscoveryArgs, RecentArgs, RecentCommand, RecentListArgs};
+use crate::error::Result;
+
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum PlaceOutput<'a> {
+	File { path: &'a Path },
+	Cloud { place_id: u64 },
+}
+
+pub fn run(args: &RecentArgs, discovery: &DiscoveryArgs) -> Result<ExitCode> {
+	match &args.command {
+		RecentCommand::List(args) => list(args, discovery),
+	}
+}
+
+fn list(args: &RecentListArgs, discovery: &DiscoveryArgs) -> Result<ExitCode> {
+	let roblox_studio = RobloxStudio::locate_with(&discovery.options())?;
+	let places = roblox_studio.recent_places();
+
+	if args.json {
+		let output: Vec<PlaceOutput> = places
+			.iter()
+			.filter_map(|place| match place {
+				RecentPlace::File(path) => Some(PlaceOutput::File { path }),
+				RecentPlace::Cloud { place_id } => Some(PlaceOutput::Cloud { place_id: *place_id }),
+				_ => None,
+			})
+			.collect();
+
+		println!("{}", serde_json::to_string_pretty(&output)?);
+		return Ok(ExitCode::SUCCESS);
+	}
+
+	for place in &places {
+		match place {
+			RecentPlace::File(path) => println!("{}", path.display()),
+			RecentPlace::Cloud { place_id } => println!("place {}", place_id),
+			_ => {}
+		}
+	}
+
+	Ok(ExitCode::SUCCESS)
+}
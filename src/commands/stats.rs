@@ -0,0 +1,302 @@
+use std::{
+	cmp::Reverse,
+	collections::BTreeMap,
+	process::ExitCode,
+	time::{Duration, SystemTime},
+};
+
+use serde::Serialize;
+
+use crate::cli::StatsArgs;
+use crate::error::Result;
+use crate::format;
+use crate::journal::{self, Entry};
+
+#[derive(Serialize)]
+struct StatsOutput {
+	sessions: usize,
+	/// The sessions the CLI waited for, whose exit is known.
+	waited: usize,
+	crashes: usize,
+	crash_rate: Option<f64>,
+	/// Total time spent in Studio, in seconds, over the sessions waited for.
+	time_in_studio: f64,
+	versions: Vec<VersionStats>,
+	places: Vec<PlaceStats>,
+}
+
+#[derive(Serialize)]
+struct VersionStats {
+	version: String,
+	sessions: usize,
+	/// Average load time in seconds, over the sessions whose load was timed.
+	average_load_time: Option<f64>,
+	crashes: usize,
+	crash_rate: Option<f64>,
+}
+
+#[derive(Serialize)]
+struct PlaceStats {
+	place: String,
+	sessions: usize,
+}
+
+pub fn run(args: &StatsArgs) -> Result<ExitCode> {
+	let now = SystemTime::now();
+
+	let entries: Vec<Entry> = journal::read()?
+		.into_iter()
+		.filter(|entry| {
+			let Some(since) = args.since else {
+				return true;
+			};
+
+			humantime::parse_rfc3339(&entry.started)
+				.is_ok_and(|started| now.duration_since(started).unwrap_or_default() <= since)
+		})
+		.collect();
+
+	let output = stats(&entries, args.places);
+
+	if args.json {
+		println!("{}", serde_json::to_string_pretty(&output)?);
+		return Ok(ExitCode::SUCCESS);
+	}
+
+	if entries.is_empty() {
+		eprintln!("No session was recorded");
+		return Ok(ExitCode::SUCCESS);
+	}
+
+	println!(
+		"Sessions:        {} ({} waited for), {} crashed{}",
+		output.sessions,
+		output.waited,
+		output.crashes,
+		rate(output.crash_rate)
+	);
+	println!("Time in Studio:  {}", format::age(Duration::from_secs_f64(output.time_in_studio)));
+
+	let version_width = output
+		.versions
+		.iter()
+		.map(|version| version.version.len())
+		.chain(["Version".len()])
+		.max()
+		.unwrap_or(0);
+
+	println!();
+	println!("{:<version_width$}  Sessions  Avg load  Crashes", "Version");
+
+	for version in &output.versions {
+		let load_time = version
+			.average_load_time
+			.map_or_else(|| "-".to_owned(), |load_time| format!("{:.1}s", load_time));
+
+		println!(
+			"{:<version_width$}  {:>8}  {:>8}  {}{}",
+			version.version,
+			version.sessions,
+			load_time,
+			version.crashes,
+			rate(version.crash_rate)
+		);
+	}
+
+	if !output.places.is_empty() {
+		println!();
+		println!("Most opened places:");
+
+		for place in &output.places {
+			println!("{:>5}  {}", place.sessions, place.place);
+		}
+	}
+
+	Ok(ExitCode::SUCCESS)
+}
+
+fn stats(entries: &[Entry], place_count: usize) -> StatsOutput {
+	let waited = entries.iter().filter(|entry| entry.duration.is_some()).count();
+	let crashes = entries.iter().filter(|entry| entry.crashed()).count();
+
+	let mut by_version: BTreeMap<String, Vec<&Entry>> = BTreeMap::new();
+	let mut by_place: BTreeMap<String, usize> = BTreeMap::new();
+
+	for entry in entries {
+		let version = entry
+			.version
+			.clone()
+			.or_else(|| entry.version_hash.clone())
+			.unwrap_or_else(|| "unknown".to_owned());
+
+		by_version.entry(version).or_default().push(entry);
+
+		let place = match (&entry.place, entry.place_id) {
+			(Some(place), _) => Some(place.display().to_string()),
+			(None, Some(place_id)) => Some(format!("place {}", place_id)),
+			(None, None) => None,
+		};
+
+		if let Some(place) = place {
+			*by_place.entry(place).or_default() += 1;
+		}
+	}
+
+	let versions = by_version
+		.into_iter()
+		.rev()
+		.map(|(version, entries)| {
+			let load_times: Vec<f64> = entries.iter().filter_map(|entry| entry.load_time).collect();
+			let waited = entries.iter().filter(|entry| entry.duration.is_some()).count();
+			let crashes = entries.iter().filter(|entry| entry.crashed()).count();
+
+			VersionStats {
+				version,
+				sessions: entries.len(),
+				average_load_time: (!load_times.is_empty())
+					.then(|| load_times.iter().sum::<f64>() / load_times.len() as f64),
+				crashes,
+				crash_rate: crash_rate(crashes, waited),
+			}
+		})
+		.collect();
+
+	let mut places: Vec<PlaceStats> = by_place
+		.into_iter()
+		.map(|(place, sessions)| PlaceStats { place, sessions })
+		.collect();
+
+	// Sorting is stable, so places opened as often stay in alphabetical order.
+	places.sort_by_key(|place| Reverse(place.sessions));
+	places.truncate(place_count);
+
+	StatsOutput {
+		sessions: entries.len(),
+		waited,
+		crashes,
+		crash_rate: crash_rate(crashes, waited),
+		time_in_studio: entries.iter().filter_map(|entry| entry.duration).sum(),
+		versions,
+		places,
+	}
+}
+
+/// The share of the sessions waited for that crashed. Detached sessions aren't
+/// counted, since how they ended isn't known.
+fn crash_rate(crashes: usize, waited: usize) -> Option<f64> {
+	(waited > 0).then(|| crashes as f64 / waited as f64)
+}
+
+fn rate(rate: Option<f64>) -> String {
+	match rate {
+		Some(rate) => format!(" ({:.0}%)", rate * 100.0),
+		None => String::new(),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::path::PathBuf;
+
+	use super::*;
+
+	fn entry(version: Option<&str>, place: Option<&str>, duration: Option<f64>, load_time: Option<f64>, exit_code: Option<i32>) -> Entry {
+		Entry {
+			started: "2024-05-01T10:00:00Z".to_owned(),
+			place: place.map(PathBuf::from),
+			place_id: None,
+			version: version.map(str::to_owned),
+			version_hash: None,
+			duration,
+			load_time,
+			exit_code,
+		}
+	}
+
+	#[test]
+	fn counts_crashes_over_the_sessions_waited_for() {
+		let output = stats(
+			&[
+				entry(Some("0.612.0.6120532"), None, Some(60.0), None, Some(0)),
+				entry(Some("0.612.0.6120532"), None, Some(30.0), None, Some(1)),
+				entry(Some("0.612.0.6120532"), None, Some(10.0), None, None),
+				entry(Some("0.612.0.6120532"), None, None, None, None),
+			],
+			10,
+		);
+
+		assert_eq!(output.sessions, 4);
+		assert_eq!(output.waited, 3);
+		assert_eq!(output.crashes, 1);
+		assert_eq!(output.crash_rate, Some(1.0 / 3.0));
+		assert_eq!(output.time_in_studio, 100.0);
+	}
+
+	#[test]
+	fn has_no_crash_rate_without_sessions_waited_for() {
+		let output = stats(&[entry(None, None, None, None, None)], 10);
+
+		assert_eq!(output.crash_rate, None);
+		assert_eq!(output.versions[0].crash_rate, None);
+	}
+
+	#[test]
+	fn averages_load_times_per_version() {
+		let mut unknown = entry(None, None, None, None, None);
+		unknown.version_hash = Some("version-abc".to_owned());
+
+		let output = stats(
+			&[
+				entry(Some("0.611.0.6110480"), None, Some(60.0), Some(10.0), Some(0)),
+				entry(Some("0.612.0.6120532"), None, Some(60.0), Some(4.0), Some(0)),
+				entry(Some("0.612.0.6120532"), None, Some(60.0), Some(8.0), Some(0)),
+				entry(Some("0.612.0.6120532"), None, Some(60.0), None, Some(0)),
+				unknown,
+				entry(None, None, None, None, None),
+			],
+			10,
+		);
+
+		let versions: Vec<(&str, usize, Option<f64>)> = output
+			.versions
+			.iter()
+			.map(|version| (version.version.as_str(), version.sessions, version.average_load_time))
+			.collect();
+
+		assert_eq!(
+			versions,
+			[
+				("version-abc", 1, None),
+				("unknown", 1, None),
+				("0.612.0.6120532", 3, Some(6.0)),
+				("0.611.0.6110480", 1, Some(10.0)),
+			]
+		);
+	}
+
+	#[test]
+	fn lists_the_most_opened_places() {
+		let mut published = entry(None, None, None, None, None);
+		published.place_id = Some(1818);
+
+		let output = stats(
+			&[
+				entry(None, Some("/home/alice/b.rbxl"), None, None, None),
+				entry(None, Some("/home/alice/a.rbxl"), None, None, None),
+				entry(None, Some("/home/alice/c.rbxl"), None, None, None),
+				entry(None, Some("/home/alice/c.rbxl"), None, None, None),
+				published,
+				entry(None, None, None, None, None),
+			],
+			3,
+		);
+
+		let places: Vec<(&str, usize)> = output
+			.places
+			.iter()
+			.map(|place| (place.place.as_str(), place.sessions))
+			.collect();
+
+		assert_eq!(places, [("/home/alice/c.rbxl", 2), ("/home/alice/a.rbxl", 1), ("/home/alice/b.rbxl", 1)]);
+	}
+}
@@ -0,0 +1,88 @@
+use std::process::ExitCode;
+
+use serde::Serialize;
+
+use roblox_studio::deploy::{self, ClientVersion};
+use roblox_studio::roblox_install::RobloxStudio;
+use roblox_studio::studio_version::StudioVersion;
+
+use crate::cli::{DiscoveryArgs, UpdateArgs};
+use crate::commands::install;
+use crate::error::{Error, Result};
+
+#[derive(Serialize)]
+struct CheckOutput<'a> {
+	channel: &'a str,
+	installed: VersionOutput,
+	latest: VersionOutput,
+	outdated: bool,
+}
+
+#[derive(Serialize)]
+struct VersionOutput {
+	version: Option<String>,
+	version_hash: Option<String>,
+}
+
+pub fn run(args: &UpdateArgs, discovery: &DiscoveryArgs) -> Result<ExitCode> {
+	let channel = discovery.deployment_channel();
+	let studio = RobloxStudio::locate_with(&discovery.options())?;
+	let latest = deploy::latest_version(channel).map_err(Error::Install)?;
+	let installed = studio.version();
+	let outdated = is_outdated(&studio, installed, &latest);
+
+	if args.json {
+		let output = CheckOutput {
+			channel,
+			installed: VersionOutput {
+				version: installed.map(|version| version.to_string()),
+				version_hash: studio.version_hash().map(str::to_owned),
+			},
+			latest: VersionOutput {
+				version: Some(latest.version.clone()),
+				version_hash: Some(latest.version_hash.clone()),
+			},
+			outdated,
+		};
+
+		println!("{}", serde_json::to_string_pretty(&output)?);
+		return Ok(ExitCode::SUCCESS);
+	}
+
+	let installed = match (installed, studio.version_hash()) {
+		(Some(version), Some(hash)) => format!("{} ({})", version, hash),
+		(Some(version), None) => version.to_string(),
+		(None, Some(hash)) => hash.to_owned(),
+		(None, None) => "an unknown version".to_owned(),
+	};
+
+	if !outdated {
+		println!("Roblox Studio {} is up to date", installed);
+		return Ok(ExitCode::SUCCESS);
+	}
+
+	println!(
+		"Roblox Studio {} ({}) is available on {}, {} is installed",
+		latest.version, latest.version_hash, channel, installed
+	);
+
+	if !args.check {
+		let root = match &args.dir {
+			Some(dir) => dir.clone(),
+			None => install::default_root(discovery)?,
+		};
+
+		install::install(channel, &latest.version_hash, &root)?;
+	}
+
+	Ok(ExitCode::SUCCESS)
+}
+
+/// Compares product versions when both are known, and version directories
+/// otherwise.
+fn is_outdated(studio: &RobloxStudio, installed: Option<StudioVersion>, latest: &ClientVersion) -> bool {
+	match (installed, latest.version.parse::<StudioVersion>()) {
+		(Some(installed), Ok(latest)) => installed < latest,
+		_ => studio.version_hash() != Some(latest.version_hash.as_str()),
+	}
+}
@@ -0,0 +1,29 @@
+use std::process::ExitCode;
+
+use roblox_studio::roblox_install::{self, RobloxStudio};
+
+use crate::cli::{DiscoveryArgs, VersionArgs};
+use crate::error::Result;
+
+pub fn run(args: &VersionArgs, discovery: &DiscoveryArgs) -> Result<ExitCode> {
+	if args.studio {
+		let studio = RobloxStudio::locate_with(&discovery.options())?;
+		let version = studio.version().ok_or(roblox_install::Error::VersionUnknown)?;
+
+		println!("{}", version);
+		return Ok(ExitCode::SUCCESS);
+	}
+
+	println!("roblox-studio {}", env!("CARGO_PKG_VERSION"));
+
+	match RobloxStudio::locate_with(&discovery.options()) {
+		Ok(studio) => match (studio.version(), studio.version_hash()) {
+			(Some(version), Some(hash)) => println!("Roblox Studio {} ({})", version, hash),
+			(Some(version), None) => println!("Roblox Studio {}", version),
+			(None, _) => println!("Roblox Studio (unknown version)"),
+		},
+		Err(err) => println!("Roblox Studio not found: {}", err),
+	}
+
+	Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,264 @@
+use std::{
+	cmp::Reverse,
+	collections::{HashMap, HashSet},
+	fs, io,
+	path::{Path, PathBuf},
+	process::ExitCode,
+	time::SystemTime,
+};
+
+use tracing::debug;
+
+use roblox_studio::process;
+use roblox_studio::roblox_install::{self, RobloxStudio};
+
+use crate::cli::{DiscoveryArgs, GcArgs, VersionsArgs, VersionsCommand};
+use crate::config::Project;
+use crate::error::{Error, Result};
+use crate::format;
+
+pub fn run(args: &VersionsArgs, discovery: &DiscoveryArgs) -> Result<ExitCode> {
+	match &args.command {
+		VersionsCommand::List => list(discovery),
+		VersionsCommand::Gc(args) => gc(args, discovery),
+	}
+}
+
+fn list(discovery: &DiscoveryArgs) -> Result<ExitCode> {
+	let studios = RobloxStudio::locate_all_with(&discovery.options());
+
+	if studios.is_empty() {
+		let err = match (&discovery.studio_version, &discovery.channel) {
+			(Some(version), _) => roblox_install::Error::VersionNotInstalled(version.clone()),
+			(None, Some(channel)) => roblox_install::Error::ChannelNotInstalled(channel.clone()),
+			(None, None) => roblox_install::Error::NotInstalled,
+		};
+
+		return Err(err.into());
+	}
+
+	let rows: Vec<[String; 5]> = studios
+		.iter()
+		.map(|studio| {
+			#[allow(deprecated)]
+			let root = studio.root_path();
+
+			let name = studio
+				.version_hash()
+				.map(str::to_owned)
+				.or_else(|| root.file_name().map(|name| name.to_string_lossy().into_owned()))
+				.unwrap_or_default();
+
+			let installed = fs::metadata(root)
+				.and_then(|metadata| metadata.created().or_else(|_| metadata.modified()))
+				.map(format::timestamp)
+				.unwrap_or_else(|_| "-".to_owned());
+
+			let size = directory_size(root)
+				.map(format::size)
+				.unwrap_or_else(|_| "-".to_owned());
+
+			let version = studio
+				.version()
+				.map(|version| version.to_string())
+				.unwrap_or_else(|| "-".to_owned());
+
+			[name, version, studio.channel(), installed, size]
+		})
+		.collect();
+
+	let header = ["VERSION", "PRODUCT VERSION", "CHANNEL", "INSTALLED", "SIZE"];
+	let mut widths = header.map(str::len);
+
+	for row in &rows {
+		for (width, cell) in widths.iter_mut().zip(row) {
+			*width = (*width).max(cell.len());
+		}
+	}
+
+	print_row(&header.map(str::to_owned), &widths);
+
+	for row in &rows {
+		print_row(row, &widths);
+	}
+
+	Ok(ExitCode::SUCCESS)
+}
+
+/// Removes old versions, keeping the newest ones of each channel, the current
+/// one and the one pinned by the project. With --channel, only versions of that
+/// channel are removed.
+fn gc(args: &GcArgs, discovery: &DiscoveryArgs) -> Result<ExitCode> {
+	let current = RobloxStudio::locate_with(&discovery.options())?;
+	let installed = RobloxStudio::locate_all_with(&discovery.installation_options());
+	let pinned = Project::discover()?.and_then(|project| project.config.studio_version);
+
+	#[allow(deprecated)]
+	let current_root = current.root_path();
+
+	let Some(versions) = current_root
+		.parent()
+		.filter(|versions| versions.ends_with("Versions"))
+	else {
+		println!("{} is not in a Versions directory, nothing to remove", current_root.display());
+		return Ok(ExitCode::SUCCESS);
+	};
+
+	let entries = fs::read_dir(versions)
+		.map_err(|err| Error::Io(format!("Failed to read {}", versions.display()), err))?;
+
+	let Some(in_use) = running_version_hashes() else {
+		println!("Roblox Studio is running from an unknown version, close it to remove old versions");
+		return Ok(ExitCode::SUCCESS);
+	};
+
+	// The Roblox Player keeps its versions in the same directory, so only the
+	// Studio installations found there are considered.
+	let mut candidates: Vec<(&RobloxStudio, PathBuf, SystemTime)> = entries
+		.filter_map(|entry| entry.ok())
+		.map(|entry| entry.path())
+		.filter(|path| path.is_dir() && path != current_root)
+		.filter_map(|path| {
+			#[allow(deprecated)]
+			let Some(studio) = installed.iter().find(|studio| studio.root_path() == path) else {
+				debug!("Keeping {}, which isn't a Roblox Studio installation", path.display());
+				return None;
+			};
+
+			if path.join("RobloxPlayerBeta.exe").exists() {
+				debug!("Keeping {}, which holds the Roblox Player", path.display());
+				return None;
+			}
+
+			let modified = fs::metadata(studio.application_path())
+				.and_then(|metadata| metadata.modified())
+				.ok()?;
+
+			Some((studio, path, modified))
+		})
+		.collect();
+
+	// Newest first.
+	candidates.sort_by_key(|(_, _, modified)| Reverse(*modified));
+
+	// How many versions of each channel are kept, counting the current one.
+	let mut kept: HashMap<String, u32> = HashMap::from([(current.channel().to_ascii_lowercase(), 1)]);
+	let mut removed = Vec::new();
+
+	for (studio, path, _) in candidates {
+		let channel = studio.channel();
+
+		if discovery
+			.channel
+			.as_ref()
+			.is_some_and(|only| !only.eq_ignore_ascii_case(&channel))
+		{
+			continue;
+		}
+
+		if pinned.as_ref().is_some_and(|pinned| studio.matches_version(pinned)) {
+			debug!("Keeping {}, which is pinned by the project", path.display());
+			continue;
+		}
+
+		if studio
+			.version_hash()
+			.is_some_and(|hash| in_use.contains(&hash.to_ascii_lowercase()))
+		{
+			println!("Keeping {}, which is running", path.display());
+			continue;
+		}
+
+		let kept = kept.entry(channel.to_ascii_lowercase()).or_default();
+
+		if *kept < args.keep {
+			*kept += 1;
+			continue;
+		}
+
+		removed.push(path);
+	}
+
+	let mut freed = 0;
+	let mut failure = None;
+
+	for path in removed {
+		let size = directory_size(&path).unwrap_or(0);
+
+		if args.dry_run {
+			println!("Would remove {} ({})", path.display(), format::size(size));
+			freed += size;
+			continue;
+		}
+
+		match fs::remove_dir_all(&path) {
+			Ok(()) => {
+				println!("Removed {} ({})", path.display(), format::size(size));
+				freed += size;
+			}
+			Err(err) => {
+				eprintln!("warning: Failed to remove {}: {}", path.display(), err);
+				failure.get_or_insert((path, err));
+			}
+		}
+	}
+
+	if args.dry_run {
+		println!("Would free {}", format::size(freed));
+	} else {
+		println!("Freed {}", format::size(freed));
+	}
+
+	match failure {
+		Some((path, err)) => Err(Error::Io(format!("Failed to remove {}", path.display()), err)),
+		None => Ok(ExitCode::SUCCESS),
+	}
+}
+
+/// The lowercase names of the version directories Studio is running from, or
+/// `None` when it's running but the platform doesn't say from where, like
+/// `tasklist` on Windows and under WSL.
+fn running_version_hashes() -> Option<HashSet<String>> {
+	process::running()
+		.into_iter()
+		.map(|process| {
+			// Processes running through Wine report a Windows path.
+			let path = process.path?;
+			let path = path.to_string_lossy();
+			let version = path.rsplit(['\\', '/']).nth(1)?;
+			Some(version.to_ascii_lowercase())
+		})
+		.collect()
+}
+
+fn print_row(row: &[String; 5], widths: &[usize; 5]) {
+	println!(
+		"{:<w0$}  {:<w1$}  {:<w2$}  {:<w3$}  {}",
+		row[0],
+		row[1],
+		row[2],
+		row[3],
+		row[4],
+		w0 = widths[0],
+		w1 = widths[1],
+		w2 = widths[2],
+		w3 = widths[3],
+	);
+}
+
+fn directory_size(path: &Path) -> io::Result<u64> {
+	let mut size = 0;
+
+	for entry in fs::read_dir(path)? {
+		let entry = entry?;
+		let file_type = entry.file_type()?;
+
+		if file_type.is_dir() {
+			size += directory_size(&entry.path())?;
+		} else if file_type.is_file() {
+			size += entry.metadata()?.len();
+		}
+	}
+
+	Ok(size)
+}
@@ -0,0 +1,428 @@
+use std::{
+	collections::BTreeMap,
+	env, fs, io,
+	path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, de::DeserializeOwned};
+use tracing::debug;
+
+use crate::error::{Error, Result};
+
+/// Name of the project configuration file, looked up from the working
+/// directory upwards.
+pub const PROJECT_CONFIG_FILE: &str = "roblox-studio.toml";
+
+/// Name of the global configuration file, in the `roblox-studio` directory of
+/// the platform's configuration directory.
+pub const GLOBAL_CONFIG_FILE: &str = "config.toml";
+
+/// The user's defaults, from the global `config.toml`. Command line flags and
+/// environment variables take precedence over them.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct GlobalConfig {
+	/// Installation to use when neither `--studio-path` nor `ROBLOX_STUDIO_PATH`
+	/// is given.
+	pub studio_path: Option<PathBuf>,
+
+	/// Deployment channel to use when `--channel` isn't given.
+	pub channel: Option<String>,
+
+	/// Arguments passed to Studio before the ones given after `--`.
+	#[serde(default)]
+	pub studio_args: Vec<String>,
+
+	/// Verbosity to use when `-v` isn't given, like the number of `-v` flags.
+	pub verbose: Option<u8>,
+
+	/// Environment variables set for Studio.
+	#[serde(default)]
+	pub env: BTreeMap<String, String>,
+
+	#[serde(default)]
+	pub profiles: BTreeMap<String, Profile>,
+
+	/// Whether sessions are recorded in the journal read by `history`, which
+	/// they are unless this is false.
+	pub journal: Option<bool>,
+
+	/// Slack or Discord compatible webhook URL `open`, `playtest` and
+	/// `bench open` post a message to when Studio starts and exits, and with
+	/// benchmark results.
+	pub webhook: Option<String>,
+}
+
+impl GlobalConfig {
+	/// Path of the global configuration file, like
+	/// `~/.config/roblox-studio/config.toml` on Linux.
+	pub fn path() -> Option<PathBuf> {
+		Some(dirs::config_dir()?.join("roblox-studio").join(GLOBAL_CONFIG_FILE))
+	}
+
+	/// Reads the global configuration file, or returns the defaults if it doesn't
+	/// exist.
+	pub fn load() -> Result<GlobalConfig> {
+		let Some(path) = GlobalConfig::path() else {
+			return Ok(GlobalConfig::default());
+		};
+
+		match fs::read_to_string(&path) {
+			Ok(contents) => parse(&path, &contents),
+			Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(GlobalConfig::default()),
+			Err(err) => Err(Error::Io(format!("Failed to read {}", path.display()), err)),
+		}
+	}
+}
+
+/// Settings shared by everyone working on a project, from its
+/// `roblox-studio.toml`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct ProjectConfig {
+	/// Roblox Studio version the project is pinned to, as a `version-<hash>`
+	/// directory or a product version. `open` installs it when it's missing.
+	pub studio_version: Option<String>,
+
+	/// Place file `open` opens when none is given, relative to the project
+	/// directory.
+	pub place: Option<PathBuf>,
+
+	/// Profile `open` uses when `--profile` isn't given.
+	pub profile: Option<String>,
+
+	#[serde(default)]
+	pub hooks: Hooks,
+
+	/// Environment variables set for Studio, which take precedence over the
+	/// global ones.
+	#[serde(default)]
+	pub env: BTreeMap<String, String>,
+
+	/// Profiles of the project, which take precedence over global profiles with
+	/// the same name.
+	#[serde(default)]
+	pub profiles: BTreeMap<String, Profile>,
+}
+
+/// A named set of changes applied to a Studio session with `open --profile`.
+/// Changes to the installation are reverted when Studio exits.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Profile {
+	/// FFlag overrides, merged into `ClientSettings/ClientAppSettings.json`.
+	#[serde(default)]
+	pub fflags: BTreeMap<String, serde_json::Value>,
+
+	/// Arguments passed to Studio after the place file.
+	#[serde(default)]
+	pub studio_args: Vec<String>,
+
+	/// File names of the only plugins to load. The other plugins are moved out of
+	/// the plugins directory for the session.
+	pub plugins: Option<Vec<String>>,
+
+	/// Environment variables set for Studio, which take precedence over the
+	/// ones outside of profiles.
+	#[serde(default)]
+	pub env: BTreeMap<String, String>,
+}
+
+impl Profile {
+	/// Whether applying the profile changes files, which have to be restored
+	/// once Studio exits.
+	pub fn changes_files(&self) -> bool {
+		!self.fflags.is_empty() || self.plugins.is_some()
+	}
+}
+
+/// Finds a profile by name, in the project first and in the global
+/// configuration otherwise.
+pub fn find_profile<'a>(name: &str, project: Option<&'a Project>, global: &'a GlobalConfig) -> Result<&'a Profile> {
+	project
+		.and_then(|project| project.config.profiles.get(name))
+		.or_else(|| global.profiles.get(name))
+		.ok_or_else(|| Error::Usage(format!("No profile named `{}` is configured", name)))
+}
+
+/// Shell commands run in the project directory around a Studio session.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Hooks {
+	/// Run before Studio is started, like a `rojo build` producing the place
+	/// file. Studio isn't started if it fails.
+	pub before_open: Option<String>,
+
+	/// Run after Studio exits. Only used with `--wait`, since Studio's exit
+	/// isn't observed otherwise.
+	pub after_exit: Option<String>,
+}
+
+/// A project configuration and the file it was read from.
+#[derive(Debug)]
+pub struct Project {
+	pub path: PathBuf,
+	pub config: ProjectConfig,
+}
+
+impl Project {
+	/// The directory containing the configuration file, which relative paths in it
+	/// are resolved against.
+	pub fn directory(&self) -> &Path {
+		self.path.parent().unwrap_or(Path::new("."))
+	}
+
+	/// The place file to open by default, if the project declares one.
+	pub fn place(&self) -> Option<PathBuf> {
+		Some(self.directory().join(self.config.place.as_ref()?))
+	}
+
+	/// Finds the closest `roblox-studio.toml` in the working directory or one
+	/// of its parents.
+	pub fn discover() -> Result<Option<Project>> {
+		let current_dir = env::current_dir()
+			.map_err(|err| Error::Io("Failed to read the working directory".to_owned(), err))?;
+
+		for directory in current_dir.ancestors() {
+			let path = directory.join(PROJECT_CONFIG_FILE);
+
+			match fs::read_to_string(&path) {
+				Ok(contents) => {
+					debug!("Using project configuration {}", path.display());
+					let config = parse(&path, &contents)?;
+					return Ok(Some(Project { path, config }));
+				}
+				Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+				Err(err) => return Err(Error::Io(format!("Failed to read {}", path.display()), err)),
+			}
+		}
+
+		Ok(None)
+	}
+}
+
+fn parse<T: DeserializeOwned>(path: &Path, contents: &str) -> Result<T> {
+	toml::from_str(contents).map_err(|err| Error::Config(path.to_owned(), err.message().to_owned()))
+}
+
+/// Sets the variables of a dotenv-style file in this process's environment, so
+/// they apply to configuration, discovery and Studio alike. Lines are
+/// `KEY=value`, optionally prefixed by `export`; values can be single-quoted
+/// (taken literally) or double-quoted (with `\n`, `\"` and `\\` escapes), and
+/// lines starting with `#` are comments.
+pub fn load_env_file(path: &Path) -> Result<()> {
+	let contents = fs::read_to_string(path)
+		.map_err(|err| Error::Io(format!("Failed to read {}", path.display()), err))?;
+
+	for (index, line) in contents.lines().enumerate() {
+		let line = line.trim();
+
+		if line.is_empty() || line.starts_with('#') {
+			continue;
+		}
+
+		let invalid = |message: &str| Error::Config(path.to_owned(), format!("line {}: {}", index + 1, message));
+
+		let line = line.strip_prefix("export ").unwrap_or(line);
+		let (name, value) = line.split_once('=').ok_or_else(|| invalid("expected KEY=value"))?;
+		let name = name.trim();
+
+		if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+			return Err(invalid("invalid variable name"));
+		}
+
+		let value = parse_env_value(value.trim()).ok_or_else(|| invalid("unterminated quoted value"))?;
+
+		// SAFETY: this runs at startup, before any other thread is started.
+		unsafe { env::set_var(name, value) };
+	}
+
+	Ok(())
+}
+
+fn parse_env_value(value: &str) -> Option<String> {
+	if let Some(rest) = value.strip_prefix('\'') {
+		return rest.strip_suffix('\'').map(str::to_owned);
+	}
+
+	let Some(rest) = value.strip_prefix('"') else {
+		// Unquoted values end at a comment.
+		let value = match value.find(" #") {
+			Some(index) => &value[..index],
+			None => value,
+		};
+
+		return Some(value.trim_end().to_owned());
+	};
+
+	let mut parsed = String::new();
+	let mut chars = rest.chars();
+
+	while let Some(c) = chars.next() {
+		match c {
+			'"' => return Some(parsed),
+			'\\' => match chars.next()? {
+				'n' => parsed.push('\n'),
+				c => parsed.push(c),
+			},
+			c => parsed.push(c),
+		}
+	}
+
+	None
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parses_the_global_configuration() {
+		let config: GlobalConfig = parse(
+			Path::new("config.toml"),
+			r#"
+studio-path = "/opt/roblox/Versions/version-1a2b3c4d5e6f4a7b"
+channel = "zcanary"
+studio-args = ["-debug"]
+verbose = 2
+journal = false
+"#,
+		)
+		.unwrap();
+
+		assert_eq!(config.studio_path, Some(PathBuf::from("/opt/roblox/Versions/version-1a2b3c4d5e6f4a7b")));
+		assert_eq!(config.channel.as_deref(), Some("zcanary"));
+		assert_eq!(config.studio_args, ["-debug"]);
+		assert_eq!(config.verbose, Some(2));
+		assert_eq!(config.journal, Some(false));
+		assert_eq!(config.webhook, None);
+	}
+
+	#[test]
+	fn rejects_unknown_settings() {
+		let result: Result<GlobalConfig> = parse(Path::new("config.toml"), "studio_path = \"/opt/roblox\"\n");
+
+		match result {
+			Err(Error::Config(path, message)) => {
+				assert_eq!(path, Path::new("config.toml"));
+				assert!(message.contains("studio_path"), "{}", message);
+			}
+			result => panic!("expected a configuration error, got {:?}", result.map(|_| ())),
+		}
+
+		assert!(parse::<GlobalConfig>(Path::new("config.toml"), "verbose = \"yes\"").is_err());
+	}
+
+	#[test]
+	fn parses_project_configurations() {
+		let config: ProjectConfig = parse(
+			Path::new("roblox-studio.toml"),
+			r#"
+studio-version = "0.612.0.6120532"
+place = "build/game.rbxl"
+profile = "testing"
+
+[hooks]
+before-open = "rojo build -o build/game.rbxl"
+"#,
+		)
+		.unwrap();
+
+		assert_eq!(config.studio_version.as_deref(), Some("0.612.0.6120532"));
+		assert_eq!(config.profile.as_deref(), Some("testing"));
+		assert_eq!(config.hooks.before_open.as_deref(), Some("rojo build -o build/game.rbxl"));
+		assert_eq!(config.hooks.after_exit, None);
+
+		let project = Project {
+			path: PathBuf::from("/home/me/game").join(PROJECT_CONFIG_FILE),
+			config,
+		};
+
+		assert_eq!(project.directory(), Path::new("/home/me/game"));
+		assert_eq!(project.place(), Some(PathBuf::from("/home/me/game/build/game.rbxl")));
+	}
+
+	#[test]
+	fn parses_profiles() {
+		let config: GlobalConfig = parse(
+			Path::new("config.toml"),
+			r#"
+[profiles.debug]
+studio-args = ["-debug"]
+fflags = { FFlagDebugMode = true, DFIntTaskSchedulerTargetFps = 144 }
+
+[profiles.clean]
+plugins = []
+"#,
+		)
+		.unwrap();
+
+		let debug = &config.profiles["debug"];
+		assert_eq!(debug.studio_args, ["-debug"]);
+		assert_eq!(debug.fflags["FFlagDebugMode"], serde_json::json!(true));
+		assert_eq!(debug.fflags["DFIntTaskSchedulerTargetFps"], serde_json::json!(144));
+		assert!(debug.changes_files());
+
+		let clean = &config.profiles["clean"];
+		assert_eq!(clean.plugins.as_deref(), Some(&[][..]));
+		assert!(clean.changes_files());
+
+		assert!(!Profile::default().changes_files());
+	}
+
+	#[test]
+	fn project_profiles_take_precedence() {
+		let global: GlobalConfig = parse(
+			Path::new("config.toml"),
+			"[profiles.shared]\nstudio-args = [\"global\"]\n\n[profiles.mine]\nstudio-args = [\"mine\"]\n",
+		)
+		.unwrap();
+		let project = Project {
+			path: PathBuf::from(PROJECT_CONFIG_FILE),
+			config: parse(Path::new(PROJECT_CONFIG_FILE), "[profiles.shared]\nstudio-args = [\"project\"]\n").unwrap(),
+		};
+
+		assert_eq!(find_profile("shared", Some(&project), &global).unwrap().studio_args, ["project"]);
+		assert_eq!(find_profile("shared", None, &global).unwrap().studio_args, ["global"]);
+		assert_eq!(find_profile("mine", Some(&project), &global).unwrap().studio_args, ["mine"]);
+		assert!(matches!(find_profile("missing", Some(&project), &global), Err(Error::Usage(_))));
+	}
+
+	#[test]
+	fn parses_environment_variables() {
+		let config: GlobalConfig = parse(
+			Path::new("config.toml"),
+			"[env]\nROBLOX_API_KEY = \"global\"\n\n[profiles.debug.env]\nDEBUG = \"1\"\n",
+		)
+		.unwrap();
+
+		assert_eq!(config.env["ROBLOX_API_KEY"], "global");
+		assert_eq!(config.profiles["debug"].env["DEBUG"], "1");
+
+		let config: ProjectConfig = parse(Path::new(PROJECT_CONFIG_FILE), "[env]\nPLACE = \"game\"\n").unwrap();
+		assert_eq!(config.env["PLACE"], "game");
+
+		// Values are strings, like the environment's.
+		assert!(parse::<GlobalConfig>(Path::new("config.toml"), "[env]\nDEBUG = 1\n").is_err());
+	}
+
+	#[test]
+	fn parses_env_file_values() {
+		assert_eq!(parse_env_value("plain").as_deref(), Some("plain"));
+		assert_eq!(parse_env_value("plain # comment").as_deref(), Some("plain"));
+		assert_eq!(parse_env_value("a#b").as_deref(), Some("a#b"));
+		assert_eq!(parse_env_value("").as_deref(), Some(""));
+		assert_eq!(parse_env_value(r"'single \n # literal'").as_deref(), Some(r"single \n # literal"));
+		assert_eq!(
+			parse_env_value(r#""double \"quoted\"\nand \\ escaped""#).as_deref(),
+			Some("double \"quoted\"\nand \\ escaped")
+		);
+	}
+
+	#[test]
+	fn rejects_unterminated_env_file_values() {
+		assert_eq!(parse_env_value("'single"), None);
+		assert_eq!(parse_env_value("\"double"), None);
+		assert_eq!(parse_env_value(r#""escaped\""#), None);
+	}
+}
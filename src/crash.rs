@@ -0,0 +1,294 @@
+//! Finds what Studio leaves behind when it crashes: minidumps and logs in its
+//! logs directory, and crash reports on MacOS, with a short summary of the
+//! crash when it can be read from them.
+
+use std::{
+    fs,
+    io::{self, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use tracing::debug;
+
+use crate::roblox_install::RobloxStudio;
+
+/// How much of the end of a log is searched for the error that ended it.
+const LOG_TAIL_SIZE: u64 = 64 * 1024;
+
+/// A file written by Studio around a crash.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct CrashArtifact {
+    pub path: PathBuf,
+    pub kind: ArtifactKind,
+
+    /// When the file was last written.
+    pub modified: SystemTime,
+
+    /// A one line summary of the crash, like
+    /// `EXCEPTION_ACCESS_VIOLATION (0xC0000005) at 0x7FF6A1B2C3D4`, when the
+    /// file has one.
+    pub signature: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum ArtifactKind {
+    /// A Windows minidump (`.dmp`), written by Studio's crash handler.
+    Dump,
+    /// A Studio log.
+    Log,
+    /// A crash report written by MacOS (`.ips` or `.crash`).
+    Report,
+}
+
+impl RobloxStudio {
+    /// The crash dumps, logs and crash reports written since `since`, like the
+    /// start of a session, oldest first. Dumps are in the `crashes` directory of
+    /// [`logs_path`](RobloxStudio::logs_path), and MacOS writes crash reports to
+    /// `~/Library/Logs/DiagnosticReports`.
+    pub fn crash_artifacts(&self, since: SystemTime) -> Vec<CrashArtifact> {
+        let mut artifacts = Vec::new();
+
+        if let Some(logs) = self.logs_path() {
+            collect(&logs, since, &mut artifacts);
+            collect(&logs.join("crashes"), since, &mut artifacts);
+        }
+
+        #[cfg(target_os = "macos")]
+        if let Some(home) = dirs::home_dir() {
+            collect(&home.join("Library/Logs/DiagnosticReports"), since, &mut artifacts);
+        }
+
+        artifacts.sort_by_key(|artifact| artifact.modified);
+        artifacts
+    }
+}
+
+fn collect(directory: &Path, since: SystemTime, artifacts: &mut Vec<CrashArtifact>) {
+    let Ok(entries) = fs::read_dir(directory) else {
+        debug!("Couldn't read {}", directory.display());
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_lowercase();
+
+        let kind = if name.ends_with(".dmp") {
+            ArtifactKind::Dump
+        } else if name.ends_with(".ips") || name.ends_with(".crash") {
+            // DiagnosticReports has reports of every application.
+            if !name.starts_with("robloxstudio") {
+                continue;
+            }
+
+            ArtifactKind::Report
+        } else if name.ends_with(".log") && name.contains("studio") {
+            ArtifactKind::Log
+        } else {
+            continue;
+        };
+
+        let Some(modified) = entry
+            .metadata()
+            .ok()
+            .filter(|metadata| metadata.is_file())
+            .and_then(|metadata| metadata.modified().ok())
+        else {
+            continue;
+        };
+
+        if modified < since {
+            continue;
+        }
+
+        let signature = match kind {
+            ArtifactKind::Dump => minidump_signature(&path),
+            ArtifactKind::Log => log_signature(&path),
+            ArtifactKind::Report => report_signature(&path),
+        }
+        .map_err(|err| debug!("Couldn't read {}: {}", path.display(), err))
+        .ok()
+        .flatten();
+
+        artifacts.push(CrashArtifact {
+            path,
+            kind,
+            modified,
+            signature,
+        });
+    }
+}
+
+/// Reads the exception from a minidump's exception stream. Minidumps start with
+/// a header pointing to a directory of streams, see `MINIDUMP_HEADER`,
+/// `MINIDUMP_DIRECTORY` and `MINIDUMP_EXCEPTION_STREAM`.
+fn minidump_signature(path: &Path) -> io::Result<Option<String>> {
+    Ok(dump_signature(&fs::read(path)?))
+}
+
+fn dump_signature(dump: &[u8]) -> Option<String> {
+    const EXCEPTION_STREAM: u32 = 6;
+
+    let u32_at = |offset: usize| -> Option<u32> { Some(u32::from_le_bytes(dump.get(offset..offset + 4)?.try_into().ok()?)) };
+    let u64_at = |offset: usize| -> Option<u64> { Some(u64::from_le_bytes(dump.get(offset..offset + 8)?.try_into().ok()?)) };
+
+    if dump.get(..4) != Some(b"MDMP") {
+        return None;
+    }
+
+    let streams = u32_at(8)? as usize;
+    let directory = u32_at(12)? as usize;
+
+    let exception = (0..streams)
+        .map(|index| directory + index * 12)
+        .find(|entry| u32_at(*entry) == Some(EXCEPTION_STREAM))?;
+    let exception = u32_at(exception + 8)? as usize;
+
+    // The exception record follows the thread ID and an alignment field.
+    let code = u32_at(exception + 8)?;
+    let address = u64_at(exception + 24)?;
+
+    Some(match exception_name(code) {
+        Some(name) => format!("{} (0x{:08X}) at 0x{:X}", name, code, address),
+        None => format!("exception 0x{:08X} at 0x{:X}", code, address),
+    })
+}
+
+fn exception_name(code: u32) -> Option<&'static str> {
+    Some(match code {
+        0x8000_0003 => "EXCEPTION_BREAKPOINT",
+        0xC000_0005 => "EXCEPTION_ACCESS_VIOLATION",
+        0xC000_001D => "EXCEPTION_ILLEGAL_INSTRUCTION",
+        0xC000_0094 => "EXCEPTION_INT_DIVIDE_BY_ZERO",
+        0xC000_00FD => "EXCEPTION_STACK_OVERFLOW",
+        0xC000_0374 => "STATUS_HEAP_CORRUPTION",
+        0xC000_0409 => "STATUS_STACK_BUFFER_OVERRUN",
+        0xE06D_7363 => "C++ exception",
+        _ => return None,
+    })
+}
+
+/// The last line of the log that reports an error, from its end.
+fn log_signature(path: &Path) -> io::Result<Option<String>> {
+    let mut file = fs::File::open(path)?;
+    let size = file.metadata()?.len();
+
+    let mut tail = Vec::new();
+    file.seek(SeekFrom::Start(size.saturating_sub(LOG_TAIL_SIZE)))?;
+    file.read_to_end(&mut tail)?;
+
+    Ok(error_line(&String::from_utf8_lossy(&tail)))
+}
+
+fn error_line(log: &str) -> Option<String> {
+    let line = log.lines().rev().find(|line| {
+        let line = line.to_lowercase();
+        ["crash", "exception", "fatal", "assert"]
+            .iter()
+            .any(|word| line.contains(word))
+    })?;
+
+    Some(line.trim().to_owned())
+}
+
+/// Reads the exception from a MacOS crash report. `.ips` reports are a JSON
+/// header line followed by a JSON body with an `exception` object, and older
+/// `.crash` reports have an `Exception Type:` line.
+fn report_signature(path: &Path) -> io::Result<Option<String>> {
+    Ok(report_exception(&fs::read_to_string(path)?))
+}
+
+fn report_exception(report: &str) -> Option<String> {
+    if let Some(line) = report.lines().find(|line| line.starts_with("Exception Type:")) {
+        return Some(line.trim_start_matches("Exception Type:").trim().to_owned());
+    }
+
+    let (_, body) = report.split_once('\n')?;
+    let body = serde_json::from_str::<serde_json::Value>(body).ok()?;
+
+    let exception = &body["exception"];
+    let parts: Vec<&str> = ["type", "signal", "subtype"]
+        .iter()
+        .filter_map(|field| exception[field].as_str())
+        .collect();
+
+    (!parts.is_empty()).then(|| parts.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn minidump(code: u32, address: u64) -> Vec<u8> {
+        let mut dump = vec![0; 64];
+        dump[..4].copy_from_slice(b"MDMP");
+        dump[8..12].copy_from_slice(&2u32.to_le_bytes());
+        dump[12..16].copy_from_slice(&16u32.to_le_bytes());
+
+        // A thread list stream followed by the exception stream at offset 40.
+        dump[16..20].copy_from_slice(&3u32.to_le_bytes());
+        dump[28..32].copy_from_slice(&6u32.to_le_bytes());
+        dump[36..40].copy_from_slice(&40u32.to_le_bytes());
+
+        dump[48..52].copy_from_slice(&code.to_le_bytes());
+        dump.extend_from_slice(&address.to_le_bytes());
+        dump
+    }
+
+    #[test]
+    fn reads_exceptions_from_minidumps() {
+        assert_eq!(
+            dump_signature(&minidump(0xC000_0005, 0x7FF6_1234)).as_deref(),
+            Some("EXCEPTION_ACCESS_VIOLATION (0xC0000005) at 0x7FF61234")
+        );
+        assert_eq!(
+            dump_signature(&minidump(0x1234_5678, 0)).as_deref(),
+            Some("exception 0x12345678 at 0x0")
+        );
+    }
+
+    #[test]
+    fn ignores_invalid_minidumps() {
+        assert_eq!(dump_signature(b"not a minidump"), None);
+        assert_eq!(dump_signature(&minidump(0xC000_0005, 0)[..40]), None);
+    }
+
+    #[test]
+    fn finds_the_last_error_in_logs() {
+        let log = "\
+2024-01-01T00:00:00.000Z,0.1,1a2b,6 [FLog::Output] Info: Loading place
+2024-01-01T00:00:01.000Z,0.2,1a2b,6 [FLog::Error] Exception caught in TaskScheduler
+2024-01-01T00:00:02.000Z,0.3,1a2b,6 [FLog::Error] Fatal error: out of memory  
+2024-01-01T00:00:03.000Z,0.4,1a2b,6 [FLog::Output] Info: Shutting down
+";
+
+        assert_eq!(
+            error_line(log).as_deref(),
+            Some("2024-01-01T00:00:02.000Z,0.3,1a2b,6 [FLog::Error] Fatal error: out of memory")
+        );
+        assert_eq!(error_line("[FLog::Output] Info: Loading place\n"), None);
+    }
+
+    #[test]
+    fn reads_exceptions_from_crash_reports() {
+        let report = "Process: RobloxStudio [1234]\nException Type:  EXC_BAD_ACCESS (SIGSEGV)\n";
+        assert_eq!(report_exception(report).as_deref(), Some("EXC_BAD_ACCESS (SIGSEGV)"));
+
+        let report = concat!(
+            r#"{"app_name":"RobloxStudio","bug_type":"309"}"#,
+            "\n",
+            r#"{"exception":{"type":"EXC_BAD_ACCESS","signal":"SIGSEGV","subtype":"KERN_INVALID_ADDRESS at 0x0"}}"#,
+        );
+        assert_eq!(
+            report_exception(report).as_deref(),
+            Some("EXC_BAD_ACCESS SIGSEGV KERN_INVALID_ADDRESS at 0x0")
+        );
+
+        assert_eq!(report_exception("{}\n{}"), None);
+        assert_eq!(report_exception("not a crash report"), None);
+    }
+}
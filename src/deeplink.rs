@@ -0,0 +1,191 @@
+//! Builds `roblox-studio:` URLs, which start Studio through the protocol handler
+//! the installer registers, like the website's "Edit in Studio" buttons do, and
+//! reads place IDs out of roblox.com URLs.
+
+use std::fmt;
+
+use crate::http;
+use crate::roblox_install::{Error, Result};
+
+const PLACE_UNIVERSE_URL: &str = "https://apis.roblox.com/universes/v1/places";
+
+/// The URL scheme Studio registers itself for.
+pub const SCHEME: &str = "roblox-studio";
+
+/// An action Studio performs when it's opened through a `roblox-studio:` URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum DeepLink {
+    /// Edits a place published to Roblox, like the "Edit in Studio" button.
+    EditPlace {
+        place_id: u64,
+        universe_id: Option<u64>,
+    },
+    /// Installs a plugin from the marketplace and opens Studio.
+    InstallPlugin { plugin_id: u64 },
+    /// Opens an asset from the marketplace, like a model, in a new place.
+    TryAsset { asset_id: u64 },
+}
+
+impl DeepLink {
+    /// The URL for this action, like
+    /// `roblox-studio:1+launchmode:edit+task:EditPlace+placeId:1818`.
+    pub fn to_url(&self) -> String {
+        self.to_string()
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, String)> {
+        match self {
+            DeepLink::EditPlace { place_id, universe_id } => {
+                let mut parameters = vec![
+                    ("launchmode", "edit".to_owned()),
+                    ("task", "EditPlace".to_owned()),
+                    ("placeId", place_id.to_string()),
+                ];
+
+                if let Some(universe_id) = universe_id {
+                    parameters.push(("universeId", universe_id.to_string()));
+                }
+
+                parameters
+            }
+            DeepLink::InstallPlugin { plugin_id } => vec![
+                ("launchmode", "plugin".to_owned()),
+                ("task", "InstallPlugin".to_owned()),
+                ("pluginId", plugin_id.to_string()),
+            ],
+            DeepLink::TryAsset { asset_id } => vec![
+                ("launchmode", "asset".to_owned()),
+                ("task", "TryAsset".to_owned()),
+                ("assetId", asset_id.to_string()),
+            ],
+        }
+    }
+}
+
+impl fmt::Display for DeepLink {
+    /// Parameters are `name:value` pairs joined with `+`, after the protocol
+    /// version `1`.
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{}:1", SCHEME)?;
+
+        for (name, value) in self.parameters() {
+            write!(formatter, "+{}:{}", name, value)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A place on roblox.com, read from the URL of its page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct WebPlace {
+    pub place_id: u64,
+
+    /// Only known from Creator Dashboard URLs, see [`universe_id`] otherwise.
+    pub universe_id: Option<u64>,
+}
+
+impl WebPlace {
+    /// Reads the place from the URL of an experience page, like
+    /// `https://www.roblox.com/games/1818/Classic-Crossroads`, or of a place in the
+    /// Creator Dashboard, like
+    /// `https://create.roblox.com/dashboard/creations/experiences/13058/places/1818/configure`.
+    /// Returns `None` for other URLs.
+    pub fn from_url(url: &str) -> Option<WebPlace> {
+        let url = url
+            .strip_prefix("https://")
+            .or_else(|| url.strip_prefix("http://"))
+            .unwrap_or(url);
+
+        let (host, path) = url.split_once('/')?;
+        let path = path.split(['?', '#']).next().unwrap_or_default();
+        let host = host.to_ascii_lowercase();
+
+        if host != "roblox.com" && !host.ends_with(".roblox.com") {
+            return None;
+        }
+
+        let segments: Vec<&str> = path.split('/').filter(|segment| !segment.is_empty()).collect();
+        let id_after = |name: &str| {
+            let position = segments.iter().position(|segment| *segment == name)?;
+            segments.get(position + 1)?.parse::<u64>().ok()
+        };
+
+        if let Some(place_id) = id_after("games") {
+            return Some(WebPlace {
+                place_id,
+                universe_id: None,
+            });
+        }
+
+        let place_id = id_after("places")?;
+
+        Some(WebPlace {
+            place_id,
+            universe_id: id_after("experiences"),
+        })
+    }
+}
+
+/// Asks Roblox's API which universe (experience) a place belongs to.
+pub fn universe_id(place_id: u64) -> Result<u64> {
+    let url = format!("{}/{}/universe", PLACE_UNIVERSE_URL, place_id);
+    let agent = http::agent()?;
+
+    let response: serde_json::Value = http::get(&agent, &url)?
+        .into_json()
+        .map_err(|err| http::download_error(&url, err))?;
+
+    // The universe ID is null when the place doesn't exist.
+    response
+        .get("universeId")
+        .and_then(serde_json::Value::as_u64)
+        .ok_or_else(|| Error::DownloadError(url, format!("place {} doesn't exist", place_id)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn place(place_id: u64, universe_id: Option<u64>) -> Option<WebPlace> {
+        Some(WebPlace { place_id, universe_id })
+    }
+
+    #[test]
+    fn reads_places_from_experience_pages() {
+        assert_eq!(
+            WebPlace::from_url("https://www.roblox.com/games/1818/Classic-Crossroads"),
+            place(1818, None)
+        );
+        assert_eq!(WebPlace::from_url("http://roblox.com/games/1818"), place(1818, None));
+        assert_eq!(WebPlace::from_url("https://WWW.Roblox.com/games/1818?privateServerLinkCode=1"), place(1818, None));
+        assert_eq!(WebPlace::from_url("https://web.roblox.com/de/games/1818#!/about"), place(1818, None));
+    }
+
+    #[test]
+    fn reads_places_from_the_creator_dashboard() {
+        assert_eq!(
+            WebPlace::from_url("https://create.roblox.com/dashboard/creations/experiences/13058/places/1818/configure"),
+            place(1818, Some(13058))
+        );
+    }
+
+    #[test]
+    fn ignores_other_urls() {
+        let urls = [
+            "https://www.roblox.com.example.com/games/1818",
+            "https://example.com/games/1818",
+            "https://www.roblox.com/games/Classic-Crossroads",
+            "https://www.roblox.com/catalog/1818",
+            "https://www.roblox.com",
+            "https://create.roblox.com/dashboard/creations/experiences/13058/overview",
+        ];
+
+        for url in urls {
+            assert_eq!(WebPlace::from_url(url), None, "{}", url);
+        }
+    }
+}
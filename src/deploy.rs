@@ -0,0 +1,480 @@
+//! Downloads Roblox Studio builds from Roblox's deployment CDN, the same way the
+//! bootstrapper does.
+
+use std::{
+    fs::{self, File},
+    io::{self, BufReader, Read, Write},
+    path::{Component, Path, PathBuf},
+};
+
+use tracing::{debug, warn};
+
+use crate::http;
+use crate::roblox_install::{CHANNEL_FILE, Error, Result};
+use crate::studio_version::StudioVersion;
+
+/// The channel regular Roblox Studio releases are deployed to.
+pub const LIVE_CHANNEL: &str = "LIVE";
+
+const CLIENT_SETTINGS_URL: &str = "https://clientsettingscdn.roblox.com/v2/client-version";
+const SETUP_URL: &str = "https://setup.rbxcdn.com";
+
+/// The name `DeployHistory.txt` uses for Windows Roblox Studio builds.
+const HISTORY_BINARY_TYPE: &str = "Studio64";
+
+#[cfg(target_os = "macos")]
+const BINARY_TYPE: &str = "MacStudio";
+#[cfg(not(target_os = "macos"))]
+const BINARY_TYPE: &str = "WindowsStudio64";
+
+/// Where the contents of each package are extracted, relative to the version
+/// directory. Packages that aren't listed here are extracted at its root.
+const PACKAGE_DIRECTORIES: &[(&str, &str)] = &[
+    ("ApplicationConfig.zip", "ApplicationConfig"),
+    ("BuiltInPlugins.zip", "BuiltInPlugins"),
+    ("BuiltInStandalonePlugins.zip", "BuiltInStandalonePlugins"),
+    ("Plugins.zip", "Plugins"),
+    ("Qml.zip", "Qml"),
+    ("RibbonConfig.zip", "RibbonConfig"),
+    ("StudioFonts.zip", "StudioFonts"),
+    ("WebView2RuntimeInstaller.zip", "WebView2RuntimeInstaller"),
+    ("content-api-docs.zip", "content/api_docs"),
+    ("content-avatar.zip", "content/avatar"),
+    ("content-configs.zip", "content/configs"),
+    ("content-fonts.zip", "content/fonts"),
+    ("content-models.zip", "content/models"),
+    ("content-platform-dictionaries.zip", "PlatformContent/pc/shared_compression_dictionaries"),
+    ("content-platform-fonts.zip", "PlatformContent/pc/fonts"),
+    ("content-qt_translations.zip", "content/qt_translations"),
+    ("content-sky.zip", "content/sky"),
+    ("content-sounds.zip", "content/sounds"),
+    ("content-studio_svg_textures.zip", "content/studio_svg_textures"),
+    ("content-terrain.zip", "PlatformContent/pc/terrain"),
+    ("content-textures2.zip", "content/textures"),
+    ("content-textures3.zip", "PlatformContent/pc/textures"),
+    ("extracontent-luapackages.zip", "ExtraContent/LuaPackages"),
+    ("extracontent-models.zip", "ExtraContent/models"),
+    ("extracontent-scripts.zip", "ExtraContent/scripts"),
+    ("extracontent-textures.zip", "ExtraContent/textures"),
+    ("extracontent-translations.zip", "ExtraContent/translations"),
+    ("shaders.zip", "shaders"),
+    ("ssl.zip", "ssl"),
+];
+
+const APP_SETTINGS: &str = "\
+<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<Settings>
+\t<ContentFolder>content</ContentFolder>
+\t<BaseUrl>http://www.roblox.com</BaseUrl>
+</Settings>
+";
+
+/// A Roblox Studio build deployed to a channel.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct ClientVersion {
+    /// The product version, like `0.612.0.6120532`.
+    pub version: String,
+
+    /// The name of the version directory, like `version-1a2b3c4d5e6f4a7b`.
+    #[cfg_attr(feature = "serde", serde(rename = "clientVersionUpload"))]
+    pub version_hash: String,
+}
+
+/// A package listed in a build's `rbxPkgManifest.txt`.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Package {
+    pub name: String,
+    /// MD5 checksum of the package, as lowercase hex.
+    pub checksum: String,
+    pub size: u64,
+    pub extracted_size: u64,
+}
+
+/// Fetches the Roblox Studio build currently deployed to a channel.
+pub fn latest_version(channel: &str) -> Result<ClientVersion> {
+    let url = if channel.eq_ignore_ascii_case(LIVE_CHANNEL) {
+        format!("{}/{}", CLIENT_SETTINGS_URL, BINARY_TYPE)
+    } else {
+        format!("{}/{}/channel/{}", CLIENT_SETTINGS_URL, BINARY_TYPE, channel)
+    };
+
+    let agent = http::agent()?;
+
+    let response: serde_json::Value = http::get(&agent, &url)?
+        .into_json()
+        .map_err(|err| http::download_error(&url, err))?;
+
+    let field = |name: &str| {
+        response
+            .get(name)
+            .and_then(serde_json::Value::as_str)
+            .map(str::to_owned)
+            .ok_or_else(|| http::download_error(&url, format!("the response has no {}", name)))
+    };
+
+    Ok(ClientVersion {
+        version: field("version")?,
+        version_hash: field("clientVersionUpload")?,
+    })
+}
+
+/// Finds the version hash of a build, given either its `version-<hash>`
+/// directory name or its product version (like `0.612.0.6120532`, or a prefix
+/// of it like `0.612`).
+///
+/// Product versions are matched against the latest build on the channel and,
+/// on LIVE, against `DeployHistory.txt`. The newest matching build wins.
+pub fn resolve_version_hash(channel: &str, version: &str) -> Result<String> {
+    if version.starts_with("version-") {
+        return Ok(version.to_owned());
+    }
+
+    if !version.contains('.') {
+        return Ok(format!("version-{}", version));
+    }
+
+    let latest = latest_version(channel)?;
+
+    if matches_product_version(&latest.version, version) {
+        return Ok(latest.version_hash);
+    }
+
+    if !channel.eq_ignore_ascii_case(LIVE_CHANNEL) {
+        return Err(Error::VersionNotDeployed(version.to_owned()));
+    }
+
+    let agent = http::agent()?;
+    let url = format!("{}/DeployHistory.txt", SETUP_URL);
+
+    let history = http::get(&agent, &url)?
+        .into_string()
+        .map_err(|err| http::download_error(&url, err))?;
+
+    history
+        .lines()
+        .filter_map(parse_history_line)
+        .filter(|(_, product_version)| {
+            matches_product_version(&product_version.to_string(), version)
+        })
+        .max_by_key(|(_, product_version)| *product_version)
+        .map(|(version_hash, _)| version_hash.to_owned())
+        .ok_or_else(|| Error::VersionNotDeployed(version.to_owned()))
+}
+
+fn matches_product_version(product_version: &str, version: &str) -> bool {
+    product_version == version
+        || product_version
+            .strip_prefix(version)
+            .is_some_and(|rest| rest.starts_with('.'))
+}
+
+/// Parses a line like `New Studio64 version-1a2b3c4d5e6f4a7b at 3/14/2024
+/// 5:51:11 PM, file version: 0, 617, 0, 6170548, git hash: ...`.
+fn parse_history_line(line: &str) -> Option<(&str, StudioVersion)> {
+    let mut words = line.split_whitespace();
+
+    if words.next() != Some("New") || words.next() != Some(HISTORY_BINARY_TYPE) {
+        return None;
+    }
+
+    let version_hash = words.next().filter(|hash| hash.starts_with("version-"))?;
+
+    let (_, file_version) = line.split_once("file version:")?;
+    let file_version = file_version
+        .split_once(", git hash")
+        .map_or(file_version, |(file_version, _)| file_version)
+        .trim()
+        .trim_end_matches(['.', ',']);
+
+    Some((version_hash, file_version.parse().ok()?))
+}
+
+/// Fetches the list of packages that make up a Windows Roblox Studio build.
+pub fn fetch_manifest(channel: &str, version_hash: &str) -> Result<Vec<Package>> {
+    let agent = http::agent()?;
+    let url = package_url(channel, version_hash, "rbxPkgManifest.txt");
+
+    let manifest = http::get(&agent, &url)?
+        .into_string()
+        .map_err(|err| http::download_error(&url, err))?;
+
+    parse_manifest(&manifest)
+}
+
+fn parse_manifest(manifest: &str) -> Result<Vec<Package>> {
+    let mut lines = manifest.lines().map(str::trim).filter(|line| !line.is_empty());
+
+    match lines.next() {
+        Some("v0") => {}
+        header => {
+            return Err(Error::MalformedManifest(format!(
+                "unsupported manifest version {:?}",
+                header.unwrap_or_default()
+            )));
+        }
+    }
+
+    let lines: Vec<_> = lines.collect();
+
+    lines
+        .chunks(4)
+        .map(|chunk| {
+            let [name, checksum, size, extracted_size] = chunk else {
+                return Err(Error::MalformedManifest(format!(
+                    "incomplete entry for {}",
+                    chunk[0]
+                )));
+            };
+
+            let parse_size = |size: &str| {
+                size.parse().map_err(|_| {
+                    Error::MalformedManifest(format!("invalid size {:?} for {}", size, name))
+                })
+            };
+
+            Ok(Package {
+                name: (*name).to_owned(),
+                checksum: checksum.to_ascii_lowercase(),
+                size: parse_size(size)?,
+                extracted_size: parse_size(extracted_size)?,
+            })
+        })
+        .collect()
+}
+
+fn package_url(channel: &str, version_hash: &str, file: &str) -> String {
+    if channel.eq_ignore_ascii_case(LIVE_CHANNEL) {
+        format!("{}/{}-{}", SETUP_URL, version_hash, file)
+    } else {
+        format!(
+            "{}/channel/{}/{}-{}",
+            SETUP_URL,
+            channel.to_ascii_lowercase(),
+            version_hash,
+            file
+        )
+    }
+}
+
+/// Downloads a Windows Roblox Studio build and installs it into
+/// `<root>/Versions/<version_hash>`, where [`RobloxStudio::locate`] finds it
+/// when `root` is the Roblox directory (or `ROBLOX_STUDIO_PATH` points to it).
+///
+/// Packages are staged in `<root>/Downloads` and the version directory only
+/// appears once every package was verified and extracted. The channel is
+/// recorded in the version directory for [`RobloxStudio::channel`]. If the version is
+/// already installed, nothing is downloaded. `on_package` is called before
+/// each package is downloaded, with its index and the number of packages.
+///
+/// Returns the path of the version directory.
+///
+/// Returns [`Error::PlatformNotSupported`] on MacOS, where Studio is published as
+/// a zipped app bundle rather than in these packages.
+///
+/// [`RobloxStudio::locate`]: crate::roblox_install::RobloxStudio::locate
+/// [`RobloxStudio::channel`]: crate::roblox_install::RobloxStudio::channel
+pub fn install(
+    channel: &str,
+    version_hash: &str,
+    root: &Path,
+    mut on_package: impl FnMut(usize, usize, &Package),
+) -> Result<PathBuf> {
+    if cfg!(target_os = "macos") {
+        return Err(Error::PlatformNotSupported);
+    }
+
+    let versions = root.join("Versions");
+    let destination = versions.join(version_hash);
+
+    if destination.join("RobloxStudioBeta.exe").is_file() {
+        debug!("{} is already installed", version_hash);
+        return Ok(destination);
+    }
+
+    let packages = fetch_manifest(channel, version_hash)?;
+    let downloads = root.join("Downloads");
+    let staging = downloads.join(version_hash);
+
+    if staging.exists() {
+        fs::remove_dir_all(&staging).map_err(|err| Error::InstallError(staging.clone(), err))?;
+    }
+
+    fs::create_dir_all(&staging).map_err(|err| Error::InstallError(staging.clone(), err))?;
+
+    let agent = http::agent()?;
+
+    for (index, package) in packages.iter().enumerate() {
+        on_package(index, packages.len(), package);
+
+        let url = package_url(channel, version_hash, &package.name);
+        let archive = downloads.join(format!("{}-{}", version_hash, package.name));
+
+        download(&agent, &url, &archive, package)?;
+
+        if package.name.ends_with(".zip") {
+            let directory = PACKAGE_DIRECTORIES
+                .iter()
+                .find(|(name, _)| *name == package.name)
+                .map(|(_, directory)| staging.join(directory))
+                .unwrap_or_else(|| staging.clone());
+
+            extract(&archive, &directory)?;
+            fs::remove_file(&archive).map_err(|err| Error::InstallError(archive.clone(), err))?;
+        } else {
+            let file = staging.join(&package.name);
+            fs::rename(&archive, &file).map_err(|err| Error::InstallError(file, err))?;
+        }
+    }
+
+    let app_settings = staging.join("AppSettings.xml");
+    fs::write(&app_settings, APP_SETTINGS).map_err(|err| Error::InstallError(app_settings, err))?;
+
+    let channel_file = staging.join(CHANNEL_FILE);
+    fs::write(&channel_file, channel).map_err(|err| Error::InstallError(channel_file, err))?;
+
+    fs::create_dir_all(&versions).map_err(|err| Error::InstallError(versions.clone(), err))?;
+    fs::rename(&staging, &destination)
+        .map_err(|err| Error::InstallError(destination.clone(), err))?;
+
+    Ok(destination)
+}
+
+fn download(agent: &ureq::Agent, url: &str, path: &Path, package: &Package) -> Result<()> {
+    let response = http::get(agent, url)?;
+    let mut reader = response.into_reader();
+    let mut file = File::create(path).map_err(|err| Error::InstallError(path.to_owned(), err))?;
+    let mut context = md5::Context::new();
+    let mut buffer = vec![0; 64 * 1024];
+
+    loop {
+        let read = reader
+            .read(&mut buffer)
+            .map_err(|err| http::download_error(url, err))?;
+
+        if read == 0 {
+            break;
+        }
+
+        context.consume(&buffer[..read]);
+        file.write_all(&buffer[..read])
+            .map_err(|err| Error::InstallError(path.to_owned(), err))?;
+    }
+
+    let checksum = format!("{:x}", context.compute());
+
+    if checksum != package.checksum {
+        return Err(Error::ChecksumMismatch(package.name.clone()));
+    }
+
+    Ok(())
+}
+
+fn extract(archive: &Path, directory: &Path) -> Result<()> {
+    let install_error = |err: io::Error| Error::InstallError(archive.to_owned(), err);
+
+    let file = File::open(archive).map_err(install_error)?;
+    let mut zip = zip::ZipArchive::new(BufReader::new(file))
+        .map_err(|err| install_error(err.into()))?;
+
+    for index in 0..zip.len() {
+        let mut entry = zip.by_index(index).map_err(|err| install_error(err.into()))?;
+
+        // Roblox's packages use Windows path separators.
+        let name = entry.name().replace('\\', "/");
+        let relative = Path::new(&name);
+
+        if relative
+            .components()
+            .any(|component| !matches!(component, Component::Normal(_)))
+        {
+            warn!("Skipping {} in {}: unsafe path", name, archive.display());
+            continue;
+        }
+
+        let path = directory.join(relative);
+
+        if entry.is_dir() || name.ends_with('/') {
+            fs::create_dir_all(&path).map_err(install_error)?;
+            continue;
+        }
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(install_error)?;
+        }
+
+        let mut output = File::create(&path).map_err(install_error)?;
+        io::copy(&mut entry, &mut output).map_err(install_error)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_history_lines() {
+        let line = "New Studio64 version-1a2b3c4d5e6f4a7b at 3/14/2024 5:51:11 PM, \
+            file version: 0, 617, 0, 6170548, git hash: 0123456789abcdef ...";
+
+        let (hash, version) = parse_history_line(line).unwrap();
+        assert_eq!(hash, "version-1a2b3c4d5e6f4a7b");
+        assert_eq!(version, "0.617.0.6170548".parse().unwrap());
+
+        let line = "New Studio64 version-1a2b3c4d5e6f4a7b at 3/14/2024 5:51:11 PM, file version: 0, 617, 0, 6170548...";
+        assert_eq!(parse_history_line(line).unwrap().1, "0.617.0.6170548".parse().unwrap());
+    }
+
+    #[test]
+    fn skips_other_history_lines() {
+        let lines = [
+            "New WindowsPlayer version-1a2b3c4d5e6f4a7b at 3/14/2024 5:51:11 PM, file version: 0, 617, 0, 6170548...",
+            "New Studio64 at 3/14/2024 5:51:11 PM, file version: 0, 617, 0, 6170548...",
+            "Done!",
+            "New Studio64 version-1a2b3c4d5e6f4a7b at 3/14/2024 5:51:11 PM...",
+            "",
+        ];
+
+        for line in lines {
+            assert!(parse_history_line(line).is_none(), "{:?}", line);
+        }
+    }
+
+    #[test]
+    fn parses_manifests() {
+        let manifest = "v0\r\nRobloxApp.zip\r\nD41D8CD98F00B204E9800998ECF8427E\r\n120\r\n300\r\n\
+            content-fonts.zip\r\n0123456789abcdef0123456789abcdef\r\n4\r\n8\r\n\r\n";
+
+        let packages = parse_manifest(manifest).unwrap();
+        assert_eq!(packages.len(), 2);
+        assert_eq!(packages[0].name, "RobloxApp.zip");
+        assert_eq!(packages[0].checksum, "d41d8cd98f00b204e9800998ecf8427e");
+        assert_eq!((packages[0].size, packages[0].extracted_size), (120, 300));
+        assert_eq!(packages[1].name, "content-fonts.zip");
+        assert_eq!((packages[1].size, packages[1].extracted_size), (4, 8));
+    }
+
+    #[test]
+    fn rejects_malformed_manifests() {
+        let manifests = [
+            "",
+            "v1\nRobloxApp.zip\nd41d8cd98f00b204e9800998ecf8427e\n120\n300\n",
+            "v0\nRobloxApp.zip\nd41d8cd98f00b204e9800998ecf8427e\n120\n",
+            "v0\nRobloxApp.zip\nd41d8cd98f00b204e9800998ecf8427e\nbig\n300\n",
+        ];
+
+        for manifest in manifests {
+            assert!(
+                matches!(parse_manifest(manifest), Err(Error::MalformedManifest(_))),
+                "{:?}",
+                manifest
+            );
+        }
+
+        assert!(parse_manifest("v0\n").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,106 @@
+use std::{
+	io,
+	path::PathBuf,
+	process::{ExitCode, ExitStatus},
+	time::Duration,
+};
+
+use thiserror::Error;
+
+use roblox_studio::logs::LoadFailure;
+use roblox_studio::{ErrorKind, roblox_install};
+
+use crate::format;
+
+/// A wrapper for [`Result`](https://doc.rust-lang.org/std/result/enum.Result.html) that
+/// contains [`Error`] in the `Err` type.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Exit code when Roblox Studio couldn't be found.
+pub const EXIT_NOT_INSTALLED: u8 = 2;
+/// Exit code for invalid command line arguments (`EX_USAGE`).
+pub const EXIT_USAGE: u8 = 64;
+/// Exit code when the platform isn't supported (`EX_UNAVAILABLE`).
+pub const EXIT_UNAVAILABLE: u8 = 69;
+/// Exit code when Roblox Studio couldn't be started or waited on, or couldn't
+/// open the place (`EX_SOFTWARE`).
+pub const EXIT_SOFTWARE: u8 = 70;
+/// Exit code for other I/O or network failures (`EX_IOERR`).
+pub const EXIT_IO: u8 = 74;
+/// Exit code when Roblox Studio was killed for running past `--timeout`, like
+/// `timeout(1)`, or for taking too long to load a place.
+pub const EXIT_TIMEOUT: u8 = 124;
+/// Exit code for a misconfigured environment (`EX_CONFIG`).
+pub const EXIT_CONFIG: u8 = 78;
+
+#[derive(Debug, Error)]
+/// Everything that can go wrong while running a command.
+pub enum Error {
+	#[error("Failed to locate Roblox Studio: {0}")]
+	Locate(#[from] roblox_install::Error),
+
+	#[error("Failed to start Roblox Studio: {0}")]
+	Spawn(#[source] io::Error),
+
+	#[error("Failed to start Roblox Studio: {0}")]
+	Launch(#[source] roblox_install::Error),
+
+	#[error("Failed to wait for Roblox Studio: {0}")]
+	Wait(#[source] io::Error),
+
+	#[error("Failed to install Roblox Studio: {0}")]
+	Install(#[source] roblox_install::Error),
+
+	#[error("{0}")]
+	Usage(String),
+
+	#[error("Failed to serialize output: {0}")]
+	Serialize(#[from] serde_json::Error),
+
+	#[error("{0}: {1}")]
+	Io(String, #[source] io::Error),
+
+	#[error("Invalid configuration in {}: {1}", .0.display())]
+	Config(PathBuf, String),
+
+	#[error("The {0} hook failed ({1})")]
+	Hook(&'static str, ExitStatus),
+
+	#[error("Roblox Studio didn't exit within {}, so it was killed", humantime::format_duration(*.0))]
+	Timeout(Duration),
+
+	#[error("Roblox Studio used more than {} of memory, so it was killed", format::size(*.0))]
+	MemoryLimit(u64),
+
+	#[error("Roblox Studio couldn't open the place: {0}, so it was killed")]
+	LoadFailed(LoadFailure),
+
+	#[error("Roblox Studio didn't load the place within {}, so it was killed", humantime::format_duration(*.0))]
+	LoadTimeout(Duration),
+}
+
+impl Error {
+	/// The exit code the CLI should exit with when this error occurs.
+	pub fn exit_code(&self) -> ExitCode {
+		let code = match self {
+			Error::Locate(err) => match err.kind() {
+				ErrorKind::PlatformUnsupported => EXIT_UNAVAILABLE,
+				ErrorKind::Misconfigured => EXIT_CONFIG,
+				_ => EXIT_NOT_INSTALLED,
+			},
+			Error::Launch(_) => EXIT_UNAVAILABLE,
+			Error::Spawn(_)
+			| Error::Wait(_)
+			| Error::Serialize(_)
+			| Error::Hook(..)
+			| Error::MemoryLimit(_)
+			| Error::LoadFailed(_) => EXIT_SOFTWARE,
+			Error::Install(_) | Error::Io(..) => EXIT_IO,
+			Error::Usage(_) => EXIT_USAGE,
+			Error::Config(..) => EXIT_CONFIG,
+			Error::Timeout(_) | Error::LoadTimeout(_) => EXIT_TIMEOUT,
+		};
+
+		ExitCode::from(code)
+	}
+}
@@ -0,0 +1,155 @@
+//! Lifecycle events of a session, written to stdout as newline-delimited JSON by
+//! `open --events` so wrappers and editors can follow Studio without parsing the
+//! human output, which goes to stderr.
+
+use std::{
+	io::{self, Write},
+	path::Path,
+	process::Child,
+	time::{Duration, Instant, SystemTime},
+};
+
+use serde::Serialize;
+use tracing::{debug, warn};
+
+use crate::format;
+use crate::window;
+
+/// How often Studio's window is checked for while waiting for it to be ready
+/// and to load the place.
+const WINDOW_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+pub enum Event<'a> {
+	/// The installation that's started was found.
+	Located { path: &'a Path, version: Option<&'a str> },
+
+	/// Studio was started.
+	Spawned { pid: u32 },
+
+	/// Studio showed its main window.
+	Ready { pid: u32 },
+
+	/// Studio opened the place, from the title of its window.
+	PlaceLoaded { pid: u32, title: String },
+
+	/// Studio exited abnormally, leaving these crash dumps and logs.
+	Crashed {
+		pid: u32,
+		code: Option<i32>,
+		artifacts: Vec<&'a Path>,
+	},
+
+	/// Studio exited, without a code when it was killed.
+	Exited { pid: u32, code: Option<i32> },
+}
+
+#[derive(Serialize)]
+struct Record<'a> {
+	time: String,
+
+	#[serde(flatten)]
+	event: &'a Event<'a>,
+}
+
+/// Writes events to stdout, or nothing when they weren't asked for.
+#[derive(Debug, Clone, Copy)]
+pub struct Events {
+	enabled: bool,
+}
+
+impl Events {
+	pub fn new(enabled: bool) -> Events {
+		Events { enabled }
+	}
+
+	pub fn is_enabled(&self) -> bool {
+		self.enabled
+	}
+
+	/// Writes the event as a line of JSON, flushed right away so readers get it
+	/// as it happens. Failing to write, like when the reader went away, doesn't
+	/// stop the session.
+	pub fn emit(&self, event: Event) {
+		if !self.enabled {
+			return;
+		}
+
+		let record = Record {
+			time: format::timestamp(SystemTime::now()),
+			event: &event,
+		};
+
+		let result = serde_json::to_string(&record).map_err(io::Error::from).and_then(|line| {
+			let mut stdout = io::stdout().lock();
+			writeln!(stdout, "{}", line)?;
+			stdout.flush()
+		});
+
+		if let Err(err) = result {
+			debug!("Couldn't write the {:?} event: {}", event, err);
+		}
+	}
+}
+
+/// Watches Studio's window while it starts, to emit the ready and place-loaded
+/// events.
+pub struct StartupWatch {
+	events: Events,
+	expects_place: bool,
+	last_poll: Option<Instant>,
+	ready: bool,
+	done: bool,
+}
+
+impl StartupWatch {
+	/// Creates a watch for a newly started Studio. The place-loaded event is only
+	/// waited for when Studio was given a place to open.
+	pub fn new(events: Events, expects_place: bool) -> StartupWatch {
+		StartupWatch {
+			events,
+			expects_place,
+			last_poll: None,
+			ready: false,
+			done: !events.is_enabled(),
+		}
+	}
+
+	/// Checks Studio's window when it's time to. Studio's start page is titled
+	/// `Roblox Studio`, and an open place `<name> - Roblox Studio`.
+	pub fn poll(&mut self, child: &Child) {
+		let now = Instant::now();
+
+		if self.done || self.last_poll.is_some_and(|at| now - at < WINDOW_POLL_INTERVAL) {
+			return;
+		}
+
+		self.last_poll = Some(now);
+
+		let title = match window::title(child) {
+			Ok(Some(title)) => title,
+			Ok(None) => return,
+			Err(err) => {
+				warn!("Couldn't watch Roblox Studio's window for events: {}", err);
+				self.done = true;
+				return;
+			}
+		};
+
+		if !self.ready {
+			self.ready = true;
+			self.events.emit(Event::Ready { pid: child.id() });
+		}
+
+		if !self.expects_place {
+			self.done = true;
+		} else if title.trim() != "Roblox Studio" {
+			self.done = true;
+			self.events.emit(Event::PlaceLoaded {
+				pid: child.id(),
+				title,
+			});
+		}
+	}
+}
@@ -0,0 +1,37 @@
+use std::time::{Duration, SystemTime};
+
+/// Formats a size in bytes with a binary unit, like `1.4 GiB`.
+pub fn size(bytes: u64) -> String {
+	const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+
+	let mut value = bytes as f64;
+	let mut unit = 0;
+
+	while value >= 1024.0 && unit < UNITS.len() - 1 {
+		value /= 1024.0;
+		unit += 1;
+	}
+
+	if unit == 0 {
+		format!("{} {}", bytes, UNITS[0])
+	} else {
+		format!("{:.1} {}", value, UNITS[unit])
+	}
+}
+
+/// Formats a point in time as an RFC 3339 UTC timestamp, like `2024-05-01T12:00:00Z`.
+pub fn timestamp(time: SystemTime) -> String {
+	humantime::format_rfc3339_seconds(time).to_string()
+}
+
+/// Formats a duration with its largest unit, like `3d` or `12h`.
+pub fn age(duration: Duration) -> String {
+	let seconds = duration.as_secs();
+
+	match seconds {
+		0..60 => format!("{}s", seconds),
+		60..3600 => format!("{}m", seconds / 60),
+		3600..86400 => format!("{}h", seconds / 3600),
+		_ => format!("{}d", seconds / 86400),
+	}
+}
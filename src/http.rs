@@ -0,0 +1,37 @@
+//! The HTTP client used to reach Roblox, shared with the CLI.
+
+use std::{fmt::Display, sync::Arc};
+
+use tracing::debug;
+
+use crate::roblox_install::{Error, Result};
+
+/// Creates an HTTP agent that uses the platform's native TLS implementation,
+/// identifying itself as `roblox-studio`.
+pub fn agent() -> Result<ureq::Agent> {
+    let connector = native_tls::TlsConnector::new().map_err(Error::TlsError)?;
+
+    Ok(ureq::AgentBuilder::new()
+        .tls_connector(Arc::new(connector))
+        .user_agent(concat!("roblox-studio/", env!("CARGO_PKG_VERSION")))
+        .build())
+}
+
+/// Sends a GET request, turning HTTP error statuses into errors.
+pub(crate) fn get(agent: &ureq::Agent, url: &str) -> Result<ureq::Response> {
+    debug!("GET {}", url);
+
+    agent.get(url).call().map_err(|err| match err {
+        ureq::Error::Status(code, response) => {
+            download_error(url, format!("server responded with {} {}", code, response.status_text()))
+        }
+        ureq::Error::Transport(transport) => match transport.message() {
+            Some(message) => download_error(url, format!("{}: {}", transport.kind(), message)),
+            None => download_error(url, transport.kind()),
+        },
+    })
+}
+
+pub(crate) fn download_error(url: &str, reason: impl Display) -> Error {
+    Error::DownloadError(url.to_owned(), reason.to_string())
+}
@@ -0,0 +1,110 @@
+//! Lists and clears Roblox's HTTP cache, where Studio keeps the assets it
+//! downloaded, see [`RobloxStudio::http_cache_path`].
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use tracing::debug;
+
+use crate::roblox_install::RobloxStudio;
+
+/// A file of the HTTP cache, holding one downloaded asset.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct CacheEntry {
+    pub path: PathBuf,
+
+    /// The name of the file, a hash of the asset's URL.
+    pub hash: String,
+
+    /// The size of the file in bytes.
+    pub size: u64,
+
+    /// When the asset was last downloaded.
+    pub modified: SystemTime,
+}
+
+impl CacheEntry {
+    /// How long ago the asset was last downloaded.
+    pub fn age(&self) -> Duration {
+        SystemTime::now()
+            .duration_since(self.modified)
+            .unwrap_or_default()
+    }
+}
+
+/// Lists the entries of an HTTP cache directory, oldest first. A cache that
+/// doesn't exist yet is empty.
+pub fn entries(directory: &Path) -> io::Result<Vec<CacheEntry>> {
+    let read_dir = match fs::read_dir(directory) {
+        Ok(read_dir) => read_dir,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+
+    let mut entries = Vec::new();
+
+    for entry in read_dir {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+
+        if !metadata.is_file() {
+            continue;
+        }
+
+        entries.push(CacheEntry {
+            path: entry.path(),
+            hash: entry.file_name().to_string_lossy().into_owned(),
+            size: metadata.len(),
+            modified: metadata.modified()?,
+        });
+    }
+
+    entries.sort_by_key(|entry| entry.modified);
+    Ok(entries)
+}
+
+/// Removes the entries of an HTTP cache directory, or only those older than
+/// `older_than`, and returns the removed entries. Entries Studio is using are
+/// skipped on Windows, where they can't be removed.
+pub fn clear(directory: &Path, older_than: Option<Duration>) -> io::Result<Vec<CacheEntry>> {
+    let mut removed = Vec::new();
+
+    for entry in entries(directory)? {
+        if older_than.is_some_and(|older_than| entry.age() < older_than) {
+            continue;
+        }
+
+        match fs::remove_file(&entry.path) {
+            Ok(()) => removed.push(entry),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) if matches!(err.kind(), io::ErrorKind::PermissionDenied | io::ErrorKind::ResourceBusy) => {
+                debug!("Skipping {}, it is in use: {}", entry.path.display(), err);
+            }
+            Err(err) => return Err(err),
+        }
+    }
+
+    Ok(removed)
+}
+
+impl RobloxStudio {
+    /// Lists the entries of this installation's HTTP cache, see [`entries`].
+    pub fn http_cache_entries(&self) -> io::Result<Vec<CacheEntry>> {
+        match self.http_cache_path() {
+            Some(directory) => entries(&directory),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Clears this installation's HTTP cache, see [`clear`].
+    pub fn clear_http_cache(&self, older_than: Option<Duration>) -> io::Result<Vec<CacheEntry>> {
+        match self.http_cache_path() {
+            Some(directory) => clear(&directory, older_than),
+            None => Ok(Vec::new()),
+        }
+    }
+}
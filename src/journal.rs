@@ -0,0 +1,196 @@
+//! A journal of the sessions started by `open`, read by `history` to tell which
+//! Studio build a place was opened with. It's a JSON object per line in
+//! `journal.jsonl`, in the `roblox-studio` directory of the platform's data
+//! directory (like `~/.local/share/roblox-studio` on Linux).
+
+use std::{
+	fs,
+	io::{self, Write},
+	path::PathBuf,
+	process::ExitStatus,
+	time::{Duration, SystemTime},
+};
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use roblox_studio::roblox_install::RobloxStudio;
+
+use crate::error::{Error, Result};
+use crate::format;
+
+const JOURNAL_FILE: &str = "journal.jsonl";
+
+/// A session of Studio.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entry {
+	/// When Studio was started, as an RFC 3339 timestamp.
+	pub started: String,
+
+	/// The place file opened.
+	pub place: Option<PathBuf>,
+
+	/// The ID of the published place opened.
+	pub place_id: Option<u64>,
+
+	/// The product version of Studio, like `0.612.0.6120532`.
+	pub version: Option<String>,
+
+	/// The `version-<hash>` directory of the installation.
+	pub version_hash: Option<String>,
+
+	/// How long Studio ran, in seconds, when the CLI waited for it to exit.
+	pub duration: Option<f64>,
+
+	/// How long the place took to load, in seconds from starting Studio to its
+	/// log reporting the place loaded, when the CLI waited for it.
+	pub load_time: Option<f64>,
+
+	/// Studio's exit code, when the CLI waited for it to exit by itself.
+	pub exit_code: Option<i32>,
+}
+
+impl Entry {
+	/// An entry for a session of this installation started at `started`, whose
+	/// exit isn't known yet.
+	pub fn new(roblox_studio: &RobloxStudio, started: SystemTime, place: Option<PathBuf>, place_id: Option<u64>) -> Entry {
+		Entry {
+			started: format::timestamp(started),
+			place,
+			place_id,
+			version: roblox_studio.version().map(|version| version.to_string()),
+			version_hash: roblox_studio.version_hash().map(str::to_owned),
+			duration: None,
+			load_time: None,
+			exit_code: None,
+		}
+	}
+
+	/// Fills in how the session went, once Studio exited or was killed.
+	pub fn finish(&mut self, duration: Duration, load_time: Option<Duration>, status: Option<ExitStatus>) {
+		self.duration = Some(duration.as_secs_f64());
+		self.load_time = load_time.map(|load_time| load_time.as_secs_f64());
+		self.exit_code = status.and_then(|status| status.code());
+	}
+
+	/// Whether Studio exited abnormally by itself. Sessions it was killed in,
+	/// like for --timeout, don't count.
+	pub fn crashed(&self) -> bool {
+		self.exit_code.is_some_and(|code| code != 0)
+	}
+}
+
+/// Path of the journal, like `~/.local/share/roblox-studio/journal.jsonl` on
+/// Linux.
+pub fn path() -> Option<PathBuf> {
+	Some(dirs::data_dir()?.join("roblox-studio").join(JOURNAL_FILE))
+}
+
+/// Appends the entry to the journal. The session goes on when it can't be
+/// recorded, so failures are only reported.
+pub fn record(entry: &Entry) {
+	let Some(path) = path() else {
+		debug!("There is no data directory to keep the journal in");
+		return;
+	};
+
+	let result = (|| {
+		if let Some(directory) = path.parent() {
+			fs::create_dir_all(directory)?;
+		}
+
+		let line = serde_json::to_string(entry)?;
+		let mut file = fs::OpenOptions::new().create(true).append(true).open(&path)?;
+
+		writeln!(file, "{}", line)
+	})();
+
+	if let Err(err) = result {
+		warn!("Couldn't record the session in {}: {}", path.display(), err);
+	}
+}
+
+/// Reads the journal, oldest entry first. Lines that can't be read, like one cut
+/// short by a crash, are skipped.
+pub fn read() -> Result<Vec<Entry>> {
+	let Some(path) = path() else {
+		return Ok(Vec::new());
+	};
+
+	let contents = match fs::read_to_string(&path) {
+		Ok(contents) => contents,
+		Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+		Err(err) => return Err(Error::Io(format!("Failed to read {}", path.display()), err)),
+	};
+
+	Ok(parse(&contents))
+}
+
+/// Reads the entries of a journal, skipping lines that can't be read.
+fn parse(contents: &str) -> Vec<Entry> {
+	contents
+		.lines()
+		.filter(|line| !line.trim().is_empty())
+		.filter_map(|line| {
+			serde_json::from_str(line)
+				.map_err(|err| debug!("Skipping a journal entry: {}", err))
+				.ok()
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use std::path::Path;
+
+	use super::*;
+
+	#[test]
+	fn reads_entries() {
+		let entries = parse(concat!(
+			r#"{"started":"2024-05-01T10:00:00Z","place":"/home/alice/game.rbxl","place_id":null,"version":"0.612.0.6120532","version_hash":"version-abc","duration":120.5,"load_time":8.25,"exit_code":0}"#,
+			"\n",
+			r#"{"started":"2024-05-02T10:00:00Z","place":null,"place_id":1818,"version":null,"version_hash":"version-def","duration":null,"load_time":null,"exit_code":null}"#,
+			"\n",
+		));
+
+		assert_eq!(entries.len(), 2);
+		assert_eq!(entries[0].place.as_deref(), Some(Path::new("/home/alice/game.rbxl")));
+		assert_eq!(entries[0].version.as_deref(), Some("0.612.0.6120532"));
+		assert_eq!(entries[0].load_time, Some(8.25));
+		assert_eq!(entries[1].place_id, Some(1818));
+		assert_eq!(entries[1].duration, None);
+	}
+
+	#[test]
+	fn skips_unreadable_lines() {
+		let entries = parse(concat!(
+			r#"{"started":"2024-05-01T10:00:00Z","place":null,"place_id":1818,"version":null,"version_hash":null,"duration":null,"load_time":null,"exit_code":null}"#,
+			"\n\n",
+			r#"{"started":"2024-05-02T10:00:00Z","place":nu"#,
+			"\n",
+		));
+
+		assert_eq!(entries.len(), 1);
+		assert_eq!(entries[0].place_id, Some(1818));
+	}
+
+	#[test]
+	fn only_counts_abnormal_exits_as_crashes() {
+		let entry = |exit_code| Entry {
+			started: "2024-05-01T10:00:00Z".to_owned(),
+			place: None,
+			place_id: None,
+			version: None,
+			version_hash: None,
+			duration: Some(60.0),
+			load_time: None,
+			exit_code,
+		};
+
+		assert!(entry(Some(1)).crashed());
+		assert!(entry(Some(-1073741819)).crashed());
+		assert!(!entry(Some(0)).crashed());
+		assert!(!entry(None).crashed());
+	}
+}
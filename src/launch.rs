@@ -0,0 +1,196 @@
+//! Builds the command lines Roblox Studio is started with.
+
+use std::{
+    env,
+    ffi::{OsStr, OsString},
+    io,
+    path::{Path, PathBuf},
+    process::{Child, Command},
+};
+
+use crate::roblox_install::RobloxStudio;
+
+/// What Studio does once it starts, passed to it with `-task`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum StudioTask {
+    /// No task is given: Studio opens the place file if there is one, like when
+    /// it's opened from the file explorer, and the start page otherwise.
+    #[default]
+    Default,
+    /// Edits the place file (`-task EditFile`).
+    EditFile,
+    /// Edits a place published to Roblox (`-task EditPlace`). Studio joins the
+    /// place's Team Create session when it has one, as long as the universe ID is
+    /// given.
+    EditPlace {
+        place_id: u64,
+        universe_id: Option<u64>,
+    },
+    /// Starts a local test server for the place file (`-task StartServer`).
+    StartServer,
+    /// Starts a local test client that joins the local test server
+    /// (`-task StartClient`).
+    StartClient,
+}
+
+/// Options for starting Roblox Studio, turned into a command by
+/// [`LaunchOptions::to_command`].
+#[derive(Debug, Default, Clone)]
+#[must_use]
+pub struct LaunchOptions {
+    place: Option<PathBuf>,
+    task: StudioTask,
+    extra_args: Vec<OsString>,
+    env: Vec<(OsString, OsString)>,
+    detached: bool,
+}
+
+impl LaunchOptions {
+    pub fn new() -> LaunchOptions {
+        LaunchOptions::default()
+    }
+
+    /// Opens this place file. The path is converted for Studio when it runs
+    /// through Wine or WSL, see [`RobloxStudio::studio_path`].
+    pub fn place(mut self, place: impl Into<PathBuf>) -> LaunchOptions {
+        self.place = Some(place.into());
+        self
+    }
+
+    /// Gives Studio a task to perform, [`StudioTask::Default`] by default.
+    pub fn task(mut self, task: StudioTask) -> LaunchOptions {
+        self.task = task;
+        self
+    }
+
+    /// Passes an extra argument to Studio, after the ones for the place and task.
+    pub fn arg(mut self, arg: impl Into<OsString>) -> LaunchOptions {
+        self.extra_args.push(arg.into());
+        self
+    }
+
+    /// Passes extra arguments to Studio, after the ones for the place and task.
+    pub fn args<I, S>(mut self, args: I) -> LaunchOptions
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<OsString>,
+    {
+        self.extra_args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// Sets an environment variable for Studio. Under WSL, the variable is also
+    /// listed in `WSLENV`, otherwise it wouldn't reach Studio as a Windows program.
+    pub fn env(mut self, name: impl Into<OsString>, value: impl Into<OsString>) -> LaunchOptions {
+        self.env.push((name.into(), value.into()));
+        self
+    }
+
+    /// Starts Studio so that it outlives this process, see
+    /// [`RobloxStudio::detached_command`]. Detached Studio processes can't be
+    /// waited on under WSL.
+    pub fn detached(mut self, detached: bool) -> LaunchOptions {
+        self.detached = detached;
+        self
+    }
+
+    /// The arguments Studio is started with, without the executable.
+    pub fn studio_args(&self, studio: &RobloxStudio) -> Vec<OsString> {
+        let mut args: Vec<OsString> = Vec::new();
+        let place = self.place.as_deref().map(|place| studio.studio_path(place));
+
+        let task = match &self.task {
+            StudioTask::Default => None,
+            StudioTask::EditFile => Some("EditFile"),
+            StudioTask::EditPlace { .. } => Some("EditPlace"),
+            StudioTask::StartServer => Some("StartServer"),
+            StudioTask::StartClient => Some("StartClient"),
+        };
+
+        if let Some(task) = task {
+            args.extend(["-task".into(), task.into()]);
+        }
+
+        if let StudioTask::EditPlace { place_id, universe_id } = &self.task {
+            args.extend(["-placeId".into(), place_id.to_string().into()]);
+
+            if let Some(universe_id) = universe_id {
+                args.extend(["-universeId".into(), universe_id.to_string().into()]);
+            }
+        }
+
+        if let Some(place) = place {
+            if let StudioTask::Default = self.task {
+                args.push(place);
+            } else {
+                args.extend(["-localPlaceFile".into(), place]);
+            }
+        }
+
+        args.extend(self.extra_args.iter().cloned());
+        args
+    }
+
+    /// Creates the command that starts Studio with these options.
+    pub fn to_command(&self, studio: &RobloxStudio) -> Command {
+        let args = self.studio_args(studio);
+
+        let mut command = if self.detached {
+            studio.detached_command(&args)
+        } else {
+            let mut command = studio.command();
+            command.args(&args);
+            command
+        };
+
+        if !self.env.is_empty() {
+            command.envs(self.env.iter().map(|(name, value)| (name, value)));
+
+            if studio.runs_through_interop() {
+                command.env("WSLENV", wslenv(self.env.iter().map(|(name, _)| name.as_os_str())));
+            }
+        }
+
+        command
+    }
+}
+
+impl RobloxStudio {
+    /// Starts Roblox Studio as a child process, opening the place file if one is
+    /// given. See [`launch_with`](RobloxStudio::launch_with) for more options.
+    pub fn launch(&self, place: Option<&Path>) -> io::Result<Child> {
+        let mut options = LaunchOptions::new();
+
+        if let Some(place) = place {
+            options = options.place(place);
+        }
+
+        self.launch_with(&options)
+    }
+
+    /// Starts Roblox Studio with the given options. Fails without starting
+    /// anything when this installation can't be started from here, see
+    /// [`check_launchable`](RobloxStudio::check_launchable).
+    pub fn launch_with(&self, options: &LaunchOptions) -> io::Result<Child> {
+        self.check_launchable().map_err(io::Error::other)?;
+
+        options.to_command(self).spawn()
+    }
+}
+
+/// `WSLENV` with the given variables added to it.
+fn wslenv<'a>(names: impl Iterator<Item = &'a OsStr>) -> OsString {
+    let mut wslenv = env::var_os("WSLENV").unwrap_or_default();
+
+    for name in names {
+        if !wslenv.is_empty() {
+            wslenv.push(":");
+        }
+
+        wslenv.push(name);
+    }
+
+    wslenv
+}
@@ -0,0 +1,32 @@
+//! Locates Roblox Studio installations and downloads Studio builds from Roblox's
+//! deployment CDN. This is the library behind the `roblox-studio` CLI, for tools
+//! that need to find or install Studio without shelling out to it.
+//!
+//! ```no_run
+//! use roblox_studio::RobloxStudio;
+//!
+//! let studio = RobloxStudio::locate()?;
+//! println!("Roblox Studio is at {}", studio.application_path().display());
+//! # Ok::<(), roblox_studio::Error>(())
+//! ```
+
+pub mod crash;
+pub mod deeplink;
+pub mod deploy;
+pub mod http;
+pub mod http_cache;
+pub mod launch;
+pub mod locator;
+pub mod logs;
+pub mod process;
+#[cfg(feature = "reflection-metadata")]
+pub mod reflection_metadata;
+pub mod recent;
+pub mod roblox_install;
+pub mod studio_version;
+
+pub use deeplink::DeepLink;
+pub use launch::{LaunchOptions, StudioTask};
+pub use locator::{MockLocator, StudioLocator, SystemLocator};
+pub use roblox_install::{Error, ErrorKind, LocateOptions, Result, RobloxStudio, StudioParts, ValidationIssue};
+pub use studio_version::{ParseStudioVersionError, StudioVersion};
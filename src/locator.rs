@@ -0,0 +1,255 @@
+//! Abstracts how Roblox Studio is found, so code that needs an installation can
+//! be run against a fake one.
+
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+#[cfg(feature = "tokio")]
+use crate::roblox_install::Error;
+use crate::roblox_install::{LocateOptions, Result, RobloxStudio};
+
+/// Something that finds a Roblox Studio installation.
+pub trait StudioLocator {
+    fn locate(&self) -> Result<RobloxStudio>;
+}
+
+/// Finds the installation on this machine, with [`RobloxStudio::locate_with`].
+#[derive(Debug, Default, Clone)]
+pub struct SystemLocator {
+    options: LocateOptions,
+}
+
+impl SystemLocator {
+    pub fn new() -> SystemLocator {
+        SystemLocator::default()
+    }
+
+    pub fn with_options(options: LocateOptions) -> SystemLocator {
+        SystemLocator { options }
+    }
+}
+
+impl StudioLocator for SystemLocator {
+    fn locate(&self) -> Result<RobloxStudio> {
+        RobloxStudio::locate_with(&self.options)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl RobloxStudio {
+    /// Like [`locate`](RobloxStudio::locate), but probes the registry and file
+    /// system on Tokio's blocking thread pool instead of the caller's thread.
+    pub async fn locate_async() -> Result<RobloxStudio> {
+        Self::locate_with_async(LocateOptions::new()).await
+    }
+
+    /// Like [`locate_with`](RobloxStudio::locate_with), but probes the registry
+    /// and file system on Tokio's blocking thread pool instead of the caller's
+    /// thread.
+    pub async fn locate_with_async(options: LocateOptions) -> Result<RobloxStudio> {
+        match tokio::task::spawn_blocking(move || RobloxStudio::locate_with(&options)).await {
+            Ok(result) => result,
+            Err(err) if err.is_panic() => std::panic::resume_unwind(err.into_panic()),
+            Err(_) => Err(Error::Cancelled),
+        }
+    }
+}
+
+/// Name of the version directory of the fake installation.
+#[cfg(not(target_os = "macos"))]
+const MOCK_VERSION: &str = "version-0000000000000000";
+
+/// Returns a fake installation laid out like a real one in a directory: the
+/// executable (an empty file), the content and built-in plugins directories, and
+/// a plugins directory. On Windows and Linux, it's a version directory in
+/// `Versions`, and on MacOS an app bundle.
+#[derive(Debug)]
+pub struct MockLocator {
+    directory: PathBuf,
+    temporary: bool,
+}
+
+impl MockLocator {
+    /// Creates the fake installation in a new temporary directory, which is
+    /// removed when the locator is dropped.
+    pub fn new() -> io::Result<MockLocator> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let directory = env::temp_dir().join(format!(
+            "roblox-studio-mock-{}-{}",
+            process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        let mut locator = MockLocator::in_directory(&directory)?;
+        locator.temporary = true;
+        Ok(locator)
+    }
+
+    /// Creates the fake installation in the given directory, which is kept.
+    pub fn in_directory(directory: impl Into<PathBuf>) -> io::Result<MockLocator> {
+        let locator = MockLocator {
+            directory: directory.into(),
+            temporary: false,
+        };
+
+        let studio = locator.studio();
+
+        for directory in [studio.content_path(), studio.built_in_plugins_path(), studio.plugins_path()] {
+            fs::create_dir_all(directory)?;
+        }
+
+        if let Some(parent) = studio.application_path().parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(studio.application_path(), [])?;
+
+        Ok(locator)
+    }
+
+    /// The directory the fake installation is in.
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    fn studio(&self) -> RobloxStudio {
+        let root = self.directory.join("Versions").join(MOCK_VERSION);
+
+        RobloxStudio::from_native_paths(
+            root.join("content"),
+            root.join("RobloxStudioBeta.exe"),
+            root.join("BuiltInPlugins"),
+            self.directory.join("Plugins"),
+            root,
+        )
+    }
+
+    #[cfg(target_os = "macos")]
+    fn studio(&self) -> RobloxStudio {
+        let root = self.directory.join("RobloxStudio.app");
+        let contents = root.join("Contents");
+
+        RobloxStudio::from_native_paths(
+            contents.join("Resources").join("content"),
+            contents.join("MacOS").join("RobloxStudio"),
+            contents.join("Resources").join("BuiltInPlugins"),
+            self.directory.join("Plugins"),
+            root,
+        )
+    }
+}
+
+impl StudioLocator for MockLocator {
+    fn locate(&self) -> Result<RobloxStudio> {
+        Ok(self.studio())
+    }
+}
+
+impl Drop for MockLocator {
+    fn drop(&mut self) {
+        if self.temporary {
+            let _ = fs::remove_dir_all(&self.directory);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::roblox_install::Error;
+
+    fn locate(locator: &dyn StudioLocator) -> RobloxStudio {
+        locator.locate().expect("the installation should be found")
+    }
+
+    /// Where to put an installation that discovery should find. On Linux, Studio
+    /// is only found in a Wine prefix.
+    fn installation_directory(temporary: &MockLocator) -> PathBuf {
+        if cfg!(any(target_os = "windows", target_os = "macos")) {
+            temporary.directory().join("Roblox")
+        } else {
+            temporary.directory().join("prefix").join("drive_c").join("Program Files").join("Roblox")
+        }
+    }
+
+    #[test]
+    fn mock_installation_is_laid_out_like_a_real_one() {
+        let mock = MockLocator::new().unwrap();
+        let studio = locate(&mock);
+
+        assert!(studio.application_path().starts_with(mock.directory()));
+        assert!(studio.application_path().is_file());
+        assert!(studio.content_path().is_dir());
+        assert!(studio.built_in_plugins_path().is_dir());
+        assert!(studio.plugins_path().is_dir());
+    }
+
+    #[test]
+    fn mock_installation_is_removed_when_dropped() {
+        let mock = MockLocator::new().unwrap();
+        let directory = mock.directory().to_owned();
+
+        assert!(directory.is_dir());
+        drop(mock);
+        assert!(!directory.exists());
+    }
+
+    #[test]
+    fn locates_an_installation_from_its_directory() {
+        let temporary = MockLocator::new().unwrap();
+        let mock = MockLocator::in_directory(installation_directory(&temporary)).unwrap();
+        let expected = locate(&mock);
+
+        let studio = RobloxStudio::locate_from_directory(expected.application_path()).unwrap();
+        assert_eq!(studio.application_path(), expected.application_path());
+        assert_eq!(studio.content_path(), expected.content_path());
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    #[test]
+    fn locates_a_version_of_an_installation() {
+        let temporary = MockLocator::new().unwrap();
+        let mock = MockLocator::in_directory(installation_directory(&temporary)).unwrap();
+        let expected = locate(&mock);
+
+        let options = LocateOptions::new().path(mock.directory()).ignore_env(true);
+        let studio = locate(&SystemLocator::with_options(options));
+
+        assert_eq!(studio.application_path(), expected.application_path());
+        assert_eq!(studio.version_hash(), Some(MOCK_VERSION));
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    #[test]
+    fn locates_only_the_requested_version() {
+        let temporary = MockLocator::new().unwrap();
+        let mock = MockLocator::in_directory(installation_directory(&temporary)).unwrap();
+
+        let options = LocateOptions::new()
+            .path(mock.directory())
+            .ignore_env(true)
+            .version("version-1111111111111111");
+
+        assert!(SystemLocator::with_options(options).locate().is_err());
+    }
+
+    #[test]
+    fn empty_directory_is_not_an_installation() {
+        let temporary = MockLocator::new().unwrap();
+        let empty = installation_directory(&temporary);
+        fs::create_dir_all(&empty).unwrap();
+
+        match RobloxStudio::locate_from_directory(&empty) {
+            Err(Error::NotAnInstallation(path)) => assert_eq!(path, empty),
+            Err(err) => panic!("expected NotAnInstallation, got {}", err),
+            Ok(studio) => panic!("found an installation at {}", studio.application_path().display()),
+        }
+    }
+}
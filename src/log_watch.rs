@@ -0,0 +1,119 @@
+//! Follows the log of the Studio session while the CLI waits for it, to relay
+//! what Studio shows in its Output window, like what scripts `print` and `warn`,
+//! for `--stream-output`, and to notice when the place fails to load.
+
+use std::time::{Duration, Instant};
+
+use tracing::warn;
+
+use roblox_studio::logs::{LoadFailure, LogRecord, SessionLog, Severity};
+use roblox_studio::roblox_install::RobloxStudio;
+
+/// How often the log is read.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// The channel of the messages shown in the Output window.
+const OUTPUT_CHANNEL: &str = "FLog::Output";
+
+pub struct LogWatch {
+	log: SessionLog,
+	relays_output: bool,
+	watches_load: bool,
+	started: Instant,
+	loaded_after: Option<Duration>,
+	last_poll: Option<Instant>,
+	failed: bool,
+}
+
+impl LogWatch {
+	/// Creates a watch for a Studio that's about to be started.
+	pub fn new(roblox_studio: &RobloxStudio, relays_output: bool, watches_load: bool) -> LogWatch {
+		LogWatch {
+			log: SessionLog::new(roblox_studio),
+			relays_output,
+			watches_load,
+			started: Instant::now(),
+			loaded_after: None,
+			last_poll: None,
+			failed: false,
+		}
+	}
+
+	/// Reads what was written to the log since the last poll when it's time to.
+	/// Returns why the place couldn't be loaded, once, when the log reports it.
+	pub fn poll(&mut self, roblox_studio: &RobloxStudio) -> Option<LoadFailure> {
+		let now = Instant::now();
+
+		if self.last_poll.is_some_and(|at| now - at < POLL_INTERVAL) {
+			return None;
+		}
+
+		self.last_poll = Some(now);
+		self.read(roblox_studio)
+	}
+
+	/// Relays the rest of the output once Studio exited. Reading twice returns
+	/// the record held back by the first read, see
+	/// [`LogReader::read`](roblox_studio::logs::LogReader::read).
+	pub fn finish(&mut self, roblox_studio: &RobloxStudio) {
+		self.watches_load = false;
+		self.read(roblox_studio);
+		self.read(roblox_studio);
+	}
+
+	/// How long after the watch was created the log reported the place loaded,
+	/// once it has.
+	pub fn loaded_after(&self) -> Option<Duration> {
+		self.loaded_after
+	}
+
+	fn read(&mut self, roblox_studio: &RobloxStudio) -> Option<LoadFailure> {
+		if self.failed {
+			return None;
+		}
+
+		let records = match self.log.read(roblox_studio) {
+			Ok(records) => records,
+			Err(err) => {
+				warn!("Couldn't read Roblox Studio's log: {}", err);
+				self.failed = true;
+				return None;
+			}
+		};
+
+		let mut failure = None;
+
+		for record in &records {
+			if self.relays_output && record.is_in_channel(OUTPUT_CHANNEL) {
+				print(record);
+			}
+
+			if self.watches_load && failure.is_none() {
+				failure = LoadFailure::from_record(record);
+			}
+
+			// Once the place loaded, the session goes on and nothing is
+			// mistaken for a failure to load it.
+			if self.loaded_after.is_none() && record.reports_place_loaded() {
+				self.loaded_after = Some(self.started.elapsed());
+				self.watches_load = false;
+			}
+		}
+
+		if failure.is_some() {
+			self.watches_load = false;
+		}
+
+		failure
+	}
+}
+
+/// Prints the message like a console program would, with warnings and errors
+/// on stderr.
+fn print(record: &LogRecord) {
+	if record.severity >= Severity::Warning {
+		eprintln!("{}", record.message);
+	} else {
+		println!("{}", record.message);
+	}
+}
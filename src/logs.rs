@@ -0,0 +1,604 @@
+//! Finds the logs Studio writes to its logs directory, one per session, named
+//! like `0.612.0.6120532_20240501T120000Z_Studio_ABC12_last.log`, and parses
+//! their lines into [`LogRecord`]s.
+//!
+//! Each record starts a line like
+//! `2024-05-01T12:00:00.123Z,0.123000,1a2b,6,Warning [FLog::Output] message`:
+//! a timestamp, the seconds since Studio started, the thread, a verbosity
+//! level, the severity (left out by older versions), then the channel in
+//! brackets and the message. Lines that don't start like this continue the
+//! previous record's message, like stack traces do.
+
+use std::{
+    collections::HashSet,
+    fmt, fs,
+    io::{self, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use tracing::debug;
+
+use crate::roblox_install::RobloxStudio;
+
+/// The channel of the messages shown in the Output window, like what scripts
+/// `print` and `warn`. Scripts can write anything there, so it isn't trusted to
+/// report how loading the place went.
+const OUTPUT_CHANNEL: &str = "FLog::Output";
+
+/// How the messages Studio writes once it opened a place start, in lowercase.
+const PLACE_LOADED_MESSAGES: &[&str] = &["loaded place", "finished loading place", "opened place"];
+
+/// How the messages Studio writes when it couldn't open a place start, in
+/// lowercase.
+const LOAD_FAILURE_MESSAGES: &[&str] = &[
+    "failed to load place",
+    "failed to open place",
+    "unable to open place",
+    "error loading place",
+    "error opening place",
+];
+
+/// A log written by a Studio session.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct LogFile {
+    pub path: PathBuf,
+
+    /// When the log was last written to.
+    pub modified: SystemTime,
+
+    /// The size of the log in bytes.
+    pub size: u64,
+}
+
+impl LogFile {
+    /// Reads and parses the records of the log. See [`parse_records`].
+    pub fn records(&self) -> io::Result<Vec<LogRecord>> {
+        let contents = fs::read(&self.path)?;
+
+        Ok(parse_records(&String::from_utf8_lossy(&contents)))
+    }
+}
+
+impl RobloxStudio {
+    /// The logs of Studio sessions in [`logs_path`](RobloxStudio::logs_path),
+    /// oldest first. The logs of other Roblox applications sharing the
+    /// directory, like the player, are left out.
+    pub fn studio_logs(&self) -> Vec<LogFile> {
+        let Some(directory) = self.logs_path() else {
+            return Vec::new();
+        };
+
+        let Ok(entries) = fs::read_dir(&directory) else {
+            debug!("Couldn't read {}", directory.display());
+            return Vec::new();
+        };
+
+        let mut logs: Vec<LogFile> = entries
+            .flatten()
+            .filter(|entry| {
+                let name = entry.file_name().to_string_lossy().to_lowercase();
+                name.ends_with(".log") && name.contains("studio")
+            })
+            .filter_map(|entry| {
+                let metadata = entry.metadata().ok().filter(|metadata| metadata.is_file())?;
+
+                Some(LogFile {
+                    path: entry.path(),
+                    modified: metadata.modified().ok()?,
+                    size: metadata.len(),
+                })
+            })
+            .collect();
+
+        logs.sort_by_key(|log| log.modified);
+        logs
+    }
+
+    /// The log of the latest Studio session, the one written to last.
+    pub fn latest_log(&self) -> Option<LogFile> {
+        self.studio_logs().pop()
+    }
+}
+
+/// How serious a log record is, from least to most.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    fn from_name(name: &str) -> Option<Severity> {
+        match name.to_ascii_lowercase().as_str() {
+            "info" | "output" => Some(Severity::Info),
+            "warning" | "warn" => Some(Severity::Warning),
+            "error" => Some(Severity::Error),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+/// A line of a Studio log, with the lines continuing its message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct LogRecord {
+    /// When the record was written, when its timestamp could be read.
+    pub timestamp: Option<SystemTime>,
+
+    /// How long Studio had been running when the record was written.
+    pub elapsed: Option<Duration>,
+
+    /// The ID of the thread that wrote the record, in hexadecimal.
+    pub thread: String,
+
+    /// The channel the record was written to, like `FLog::Output`.
+    pub channel: Option<String>,
+
+    pub severity: Severity,
+
+    pub message: String,
+}
+
+impl LogRecord {
+    /// Parses the line starting a record, or returns `None` for a line that
+    /// continues the previous one.
+    ///
+    /// The severity is read from its field, and for older logs without one, from
+    /// the channel (like `FLog::Error`) or a prefix of the message (like
+    /// `Warning: `).
+    pub fn parse(line: &str) -> Option<LogRecord> {
+        let mut fields = line.splitn(4, ',');
+        let timestamp = fields.next()?;
+        let elapsed = fields.next()?;
+        let thread = fields.next()?;
+        let rest = fields.next()?;
+
+        // Records start with an RFC 3339 timestamp, like 2024-05-01T12:00:00Z.
+        if timestamp.len() < 20 || !timestamp.starts_with(|character: char| character.is_ascii_digit()) {
+            return None;
+        }
+
+        let elapsed: f64 = elapsed.parse().ok()?;
+
+        if !thread.chars().all(|character| character.is_ascii_hexdigit()) {
+            return None;
+        }
+
+        let level_end = rest.find(|character: char| !character.is_ascii_digit())?;
+        let rest = &rest[level_end..];
+
+        let (severity, rest) = match rest.strip_prefix(',') {
+            Some(rest) => {
+                let (severity, rest) = rest.split_once(' ').unwrap_or((rest, ""));
+                (Severity::from_name(severity), rest)
+            }
+            None => (None, rest.trim_start()),
+        };
+
+        let (channel, message) = match rest.strip_prefix('[').and_then(|rest| rest.split_once(']')) {
+            Some((channel, message)) => (Some(channel.to_owned()), message.trim_start()),
+            None => (None, rest),
+        };
+
+        let severity = severity
+            .or_else(|| {
+                let channel = channel.as_deref()?;
+                Severity::from_name(channel.rsplit("::").next()?)
+            })
+            .filter(|severity| *severity != Severity::Info)
+            .or_else(|| {
+                let (prefix, _) = message.split_once(": ")?;
+                Severity::from_name(prefix)
+            })
+            .unwrap_or(Severity::Info);
+
+        Some(LogRecord {
+            timestamp: humantime::parse_rfc3339(timestamp).ok(),
+            elapsed: (elapsed.is_finite() && elapsed >= 0.0).then(|| Duration::from_secs_f64(elapsed)),
+            thread: thread.to_owned(),
+            channel,
+            severity,
+            message: message.to_owned(),
+        })
+    }
+
+    /// Whether the record reports that Studio finished opening a place, like
+    /// `Loaded place 'Baseplate'`. Messages in the Output window are left out,
+    /// since a script can print anything.
+    pub fn reports_place_loaded(&self) -> bool {
+        self.reports_studio_message(PLACE_LOADED_MESSAGES)
+    }
+
+    /// Whether Studio itself wrote the record, rather than a script in the
+    /// Output window, and its message starts with one of `starts`.
+    fn reports_studio_message(&self, starts: &[&str]) -> bool {
+        let message = self.message.trim_start().to_lowercase();
+
+        !self.is_in_channel(OUTPUT_CHANNEL) && starts.iter().any(|start| message.starts_with(start))
+    }
+
+    /// Whether the record was written to this channel. The `FLog::` prefix and
+    /// the case can be left out, so `output` matches `FLog::Output`.
+    pub fn is_in_channel(&self, channel: &str) -> bool {
+        let Some(own) = &self.channel else {
+            return false;
+        };
+
+        own.eq_ignore_ascii_case(channel) || own.rsplit("::").next().is_some_and(|name| name.eq_ignore_ascii_case(channel))
+    }
+}
+
+/// Parses the records of a log, joining the lines continuing a record to its
+/// message. Lines before the first record are left out.
+pub fn parse_records(log: &str) -> Vec<LogRecord> {
+    let mut records: Vec<LogRecord> = Vec::new();
+
+    for line in log.lines() {
+        match LogRecord::parse(line) {
+            Some(record) => records.push(record),
+            None => {
+                if let Some(record) = records.last_mut() {
+                    record.message.push('\n');
+                    record.message.push_str(line);
+                }
+            }
+        }
+    }
+
+    records
+}
+
+/// Which records to keep, like only errors or the records of one channel.
+#[derive(Debug, Default, Clone)]
+#[must_use]
+pub struct LogFilter {
+    min_severity: Option<Severity>,
+    channels: Vec<String>,
+}
+
+impl LogFilter {
+    /// A filter keeping every record.
+    pub fn new() -> LogFilter {
+        LogFilter::default()
+    }
+
+    /// Keeps the records at least this serious.
+    pub fn min_severity(mut self, severity: Severity) -> LogFilter {
+        self.min_severity = Some(severity);
+        self
+    }
+
+    /// Keeps the records of this channel, see [`LogRecord::is_in_channel`].
+    /// Given several times, records of any of the channels are kept.
+    pub fn channel(mut self, channel: impl Into<String>) -> LogFilter {
+        self.channels.push(channel.into());
+        self
+    }
+
+    /// Whether the filter keeps every record.
+    pub fn is_empty(&self) -> bool {
+        self.min_severity.is_none() && self.channels.is_empty()
+    }
+
+    pub fn matches(&self, record: &LogRecord) -> bool {
+        self.min_severity.is_none_or(|severity| record.severity >= severity)
+            && (self.channels.is_empty() || self.channels.iter().any(|channel| record.is_in_channel(channel)))
+    }
+
+    /// The records of the log kept by the filter.
+    pub fn apply<'a>(&'a self, records: &'a [LogRecord]) -> impl Iterator<Item = &'a LogRecord> + 'a {
+        records.iter().filter(|record| self.matches(record))
+    }
+}
+
+/// Reads the records of a log as Studio writes them, like `tail -f`.
+#[derive(Debug)]
+pub struct LogReader {
+    path: PathBuf,
+    offset: u64,
+    partial: Vec<u8>,
+    pending: Option<LogRecord>,
+    last: Option<LogRecord>,
+}
+
+impl LogReader {
+    /// Reads the log from its start.
+    pub fn new(path: impl Into<PathBuf>) -> LogReader {
+        LogReader {
+            path: path.into(),
+            offset: 0,
+            partial: Vec::new(),
+            pending: None,
+            last: None,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Reads what was written since the last call, and returns the records it
+    /// completed. A record is complete once the next one starts, or once nothing
+    /// was written since the last call, so records are returned at most one call
+    /// late. Lines continuing a record that was already returned are returned as
+    /// a record like it, with these lines as its message.
+    pub fn read(&mut self) -> io::Result<Vec<LogRecord>> {
+        let mut file = fs::File::open(&self.path)?;
+        let size = file.metadata()?.len();
+
+        // A log that shrank was truncated, so it's read again from the start.
+        if size < self.offset {
+            self.offset = 0;
+            self.partial.clear();
+        }
+
+        let mut bytes = Vec::new();
+        file.seek(SeekFrom::Start(self.offset))?;
+        file.read_to_end(&mut bytes)?;
+        self.offset += bytes.len() as u64;
+
+        let mut records = Vec::new();
+
+        if bytes.is_empty() {
+            records.extend(self.pending.take());
+        }
+
+        self.partial.extend_from_slice(&bytes);
+
+        while let Some(end) = self.partial.iter().position(|byte| *byte == b'\n') {
+            let line: Vec<u8> = self.partial.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\r', '\n']);
+
+            match (LogRecord::parse(line), &mut self.pending) {
+                (Some(record), pending) => records.extend(pending.replace(record)),
+                (None, Some(pending)) => {
+                    pending.message.push('\n');
+                    pending.message.push_str(line);
+                }
+                (None, None) => {
+                    self.pending = self.last.as_ref().map(|last| LogRecord {
+                        message: line.to_owned(),
+                        ..last.clone()
+                    });
+                }
+            }
+        }
+
+        if let Some(record) = records.last() {
+            self.last = Some(record.clone());
+        }
+
+        Ok(records)
+    }
+}
+
+/// The log of a Studio session that's about to start, read as it's written.
+/// Studio's log is the first one to appear in the logs directory once it's
+/// started.
+#[derive(Debug)]
+pub struct SessionLog {
+    previous_logs: HashSet<PathBuf>,
+    reader: Option<LogReader>,
+}
+
+impl SessionLog {
+    /// Notes the logs of previous sessions. Call it before starting Studio.
+    pub fn new(roblox_studio: &RobloxStudio) -> SessionLog {
+        SessionLog {
+            previous_logs: roblox_studio.studio_logs().into_iter().map(|log| log.path).collect(),
+            reader: None,
+        }
+    }
+
+    /// The path of the session's log, once it appeared.
+    pub fn path(&self) -> Option<&Path> {
+        Some(self.reader.as_ref()?.path())
+    }
+
+    /// Reads the records written since the last call, see [`LogReader::read`].
+    /// Nothing is read until the session's log appears.
+    pub fn read(&mut self, roblox_studio: &RobloxStudio) -> io::Result<Vec<LogRecord>> {
+        if self.reader.is_none() {
+            self.reader = roblox_studio
+                .studio_logs()
+                .into_iter()
+                .find(|log| !self.previous_logs.contains(&log.path))
+                .map(|log| {
+                    debug!("Found the session's log at {}", log.path.display());
+                    LogReader::new(log.path)
+                });
+        }
+
+        match &mut self.reader {
+            Some(reader) => reader.read(),
+            None => Ok(Vec::new()),
+        }
+    }
+}
+
+/// Why Studio couldn't open a place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum LoadFailureKind {
+    /// The place file is corrupt or isn't a place.
+    Corrupt,
+    /// The user isn't allowed to edit the place published to Roblox.
+    PermissionDenied,
+    /// The place file or the published place doesn't exist.
+    NotFound,
+    Other,
+}
+
+/// A place Studio failed to open, as reported in its log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct LoadFailure {
+    pub kind: LoadFailureKind,
+
+    /// The message of the log record reporting it.
+    pub message: String,
+}
+
+impl LoadFailure {
+    /// Reads a failure to open a place from a record, when it reports one, like
+    /// `Failed to load place: the file is corrupt`. Only warnings and errors
+    /// Studio itself writes about the place count, so a script warning about
+    /// one in the Output window or an asset failing to load aren't mistaken for
+    /// it. Why it failed is read from the rest of the message.
+    pub fn from_record(record: &LogRecord) -> Option<LoadFailure> {
+        if record.severity < Severity::Warning || !record.reports_studio_message(LOAD_FAILURE_MESSAGES) {
+            return None;
+        }
+
+        let message = record.message.lines().next().unwrap_or_default();
+        let lowercase = message.to_lowercase();
+        let mentions = |words: &[&str]| words.iter().any(|word| lowercase.contains(word));
+
+        let kind = if mentions(&["corrupt", "invalid", "malformed", "unexpected end", "not a valid"]) {
+            LoadFailureKind::Corrupt
+        } else if mentions(&["permission", "denied", "not authorized", "unauthorized", "forbidden", "403"]) {
+            LoadFailureKind::PermissionDenied
+        } else if mentions(&["not found", "does not exist", "doesn't exist", "no such file", "404"]) {
+            LoadFailureKind::NotFound
+        } else {
+            LoadFailureKind::Other
+        };
+
+        Some(LoadFailure {
+            kind,
+            message: message.trim().to_owned(),
+        })
+    }
+}
+
+impl fmt::Display for LoadFailure {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cause = match self.kind {
+            LoadFailureKind::Corrupt => "the place file is corrupt",
+            LoadFailureKind::PermissionDenied => "you don't have permission to edit the place",
+            LoadFailureKind::NotFound => "the place doesn't exist",
+            LoadFailureKind::Other => "it failed to load",
+        };
+
+        write!(formatter, "{} ({})", cause, self.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(line: &str) -> LogRecord {
+        LogRecord::parse(line).expect("the line should start a record")
+    }
+
+    #[test]
+    fn parses_records() {
+        let record = record("2024-05-01T12:00:00.123Z,0.123000,1a2b,6,Warning [FLog::Output] careful");
+
+        assert_eq!(record.timestamp, humantime::parse_rfc3339("2024-05-01T12:00:00.123Z").ok());
+        assert_eq!(record.elapsed, Some(Duration::from_millis(123)));
+        assert_eq!(record.thread, "1a2b");
+        assert_eq!(record.channel.as_deref(), Some("FLog::Output"));
+        assert_eq!(record.severity, Severity::Warning);
+        assert_eq!(record.message, "careful");
+    }
+
+    #[test]
+    fn parses_records_of_older_logs() {
+        let error = record("2024-05-01T12:00:00.123Z,0.1,1a2b,6 [FLog::Error] Something broke");
+        assert_eq!(error.severity, Severity::Error);
+        assert_eq!(error.message, "Something broke");
+
+        let warning = record("2024-05-01T12:00:00.123Z,0.1,1a2b,6 [FLog::Output] Warning: careful");
+        assert_eq!(warning.severity, Severity::Warning);
+
+        let info = record("2024-05-01T12:00:00.123Z,0.1,1a2b,6 [FLog::Output] Hello world");
+        assert_eq!(info.severity, Severity::Info);
+        assert!(info.is_in_channel("output"));
+    }
+
+    #[test]
+    fn continuation_lines_are_not_records() {
+        assert_eq!(LogRecord::parse("Stack Begin"), None);
+        assert_eq!(LogRecord::parse("Script 'Workspace.Script', Line 3"), None);
+        assert_eq!(LogRecord::parse(""), None);
+    }
+
+    #[test]
+    fn joins_continuation_lines() {
+        let records = parse_records(
+            "2024-05-01T12:00:02.123Z,2.1,1a2b,6,Error [FLog::Output] Workspace.Script:3: boom\n\
+             Stack Begin\n\
+             Stack End\n\
+             2024-05-01T12:00:03.123Z,3.1,1a2b,6 [FLog::Output] after\n",
+        );
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].message, "Workspace.Script:3: boom\nStack Begin\nStack End");
+        assert_eq!(records[1].message, "after");
+    }
+
+    #[test]
+    fn reads_load_failures() {
+        let corrupt = record("2024-05-01T12:00:01.7Z,1.7,1a2b,6,Error [FLog::Error] Failed to load place: the file is corrupt");
+        let failure = LoadFailure::from_record(&corrupt).expect("the place failed to load");
+        assert_eq!(failure.kind, LoadFailureKind::Corrupt);
+        assert_eq!(failure.message, "Failed to load place: the file is corrupt");
+
+        let denied = record("2024-05-01T12:00:01.7Z,1.7,1a2b,6,Error [FLog::Network] Failed to open place 1818: HTTP 403 (Forbidden)");
+        assert_eq!(
+            LoadFailure::from_record(&denied).map(|failure| failure.kind),
+            Some(LoadFailureKind::PermissionDenied)
+        );
+
+        let other = record("2024-05-01T12:00:01.7Z,1.7,1a2b,6,Error [FLog::Error] Error loading place");
+        assert_eq!(LoadFailure::from_record(&other).map(|failure| failure.kind), Some(LoadFailureKind::Other));
+    }
+
+    #[test]
+    fn script_output_is_not_a_load_failure() {
+        for line in [
+            "2024-05-01T12:00:01.7Z,1.7,1a2b,6,Warning [FLog::Output] Couldn't place part",
+            "2024-05-01T12:00:01.7Z,1.7,1a2b,6,Warning [FLog::Output] invalid placement, can't load it",
+            "2024-05-01T12:00:01.7Z,1.7,1a2b,6,Error [FLog::Output] Failed to load place data from DataStore",
+        ] {
+            assert_eq!(LoadFailure::from_record(&record(line)), None, "{}", line);
+        }
+    }
+
+    #[test]
+    fn other_failures_are_not_load_failures() {
+        for line in [
+            "2024-05-01T12:00:01.7Z,1.7,1a2b,6,Warning [FLog::Asset] Failed to load asset rbxassetid://1818 for place 42",
+            "2024-05-01T12:00:01.7Z,1.7,1a2b,6,Error [FLog::Plugins] Couldn't open plugin PlaceTools.rbxm",
+            "2024-05-01T12:00:01.7Z,1.7,1a2b,6 [FLog::StudioLoad] Failed to load place: info records don't count",
+        ] {
+            assert_eq!(LoadFailure::from_record(&record(line)), None, "{}", line);
+        }
+    }
+
+    #[test]
+    fn reads_place_loaded() {
+        assert!(record("2024-05-01T12:00:00.5Z,0.5,1a2b,6 [FLog::StudioLoad] Loaded place 'Baseplate'").reports_place_loaded());
+        assert!(!record("2024-05-01T12:00:00.5Z,0.5,1a2b,6 [FLog::Output] Loaded place 'Baseplate'").reports_place_loaded());
+        assert!(!record("2024-05-01T12:00:00.5Z,0.5,1a2b,6 [FLog::StudioLoad] Loading place").reports_place_loaded());
+    }
+}
@@ -1,30 +1,114 @@
-use std::env;
-use std::process::Command;
+use std::process::ExitCode;
 
-mod roblox_install;
-use roblox_install::RobloxStudio;
+use clap::Parser;
+use tracing_subscriber::EnvFilter;
 
-fn main() -> Result<(), String> {
-	let args: Vec<String> = env::args().collect();
+mod cli;
+mod commands;
+mod config;
+mod error;
+mod events;
+mod format;
+mod journal;
+mod log_watch;
+mod monitor;
+mod notify;
+mod overrides;
+mod singleton;
+mod supervisor;
+mod webhook;
+mod window;
 
-	if args.len() != 2 {
-		return Err(format!("Usage: {} <place.(rbxl|rbxlx)>", args[0]));
+use cli::{Cli, Command};
+use config::GlobalConfig;
+
+fn main() -> ExitCode {
+	let mut cli = match Cli::try_parse() {
+		Ok(cli) => cli,
+		Err(err) => {
+			let _ = err.print();
+
+			return if err.use_stderr() {
+				ExitCode::from(error::EXIT_USAGE)
+			} else {
+				ExitCode::SUCCESS
+			};
+		}
+	};
+
+	for path in &cli.env_file {
+		if let Err(err) = config::load_env_file(path) {
+			if !cli.quiet {
+				eprintln!("error: {}", err);
+			}
+
+			return err.exit_code();
+		}
 	}
 
-	let roblox_studio = match RobloxStudio::locate() {
-		Ok(object) => object,
+	let config = match GlobalConfig::load() {
+		Ok(config) => config,
 		Err(err) => {
-			return Err(format!("Failed to locate Roblox Studio: {}", err));
+			if !cli.quiet {
+				eprintln!("error: {}", err);
+			}
+
+			return err.exit_code();
 		}
 	};
-	
-	let place_file_path = &args[1];
-	
-	if let Err(err) = Command::new(roblox_studio.application_path())
-		.arg(place_file_path)
-		.spawn() {
-		return Err(format!("Failed to start Roblox Studio: {}", err));
+
+	cli.apply_config(&config);
+	init_logging(cli.verbose);
+
+	match run(&cli, &config) {
+		Ok(code) => code,
+		Err(err) => {
+			if !cli.quiet {
+				eprintln!("error: {}", err);
+			}
+
+			err.exit_code()
+		}
+	}
+}
+
+fn run(cli: &Cli, config: &GlobalConfig) -> error::Result<ExitCode> {
+	match &cli.command {
+		Some(Command::Open(args)) => commands::open::run(args, &cli.discovery, config),
+		Some(Command::Playtest(args)) => commands::playtest::run(args, &cli.discovery, config),
+		Some(Command::Recent(args)) => commands::recent::run(args, &cli.discovery),
+		Some(Command::History(args)) => commands::history::run(args),
+		Some(Command::Stats(args)) => commands::stats::run(args),
+		Some(Command::Kill(args)) => commands::kill::run(args),
+		Some(Command::Logs(args)) => commands::logs::run(args, &cli.discovery),
+		Some(Command::Bench(args)) => commands::bench::run(args, &cli.discovery, config),
+		Some(Command::Locate(args)) => commands::locate::run(args, &cli.discovery),
+		Some(Command::OpenFolder(args)) => commands::open_folder::run(args, &cli.discovery),
+		Some(Command::Version(args)) => commands::version::run(args, &cli.discovery),
+		Some(Command::Versions(args)) => commands::versions::run(args, &cli.discovery),
+		Some(Command::Install(args)) => commands::install::run(args, &cli.discovery),
+		Some(Command::Update(args)) => commands::update::run(args, &cli.discovery),
+		Some(Command::Doctor) => commands::doctor::run(&cli.discovery),
+		Some(Command::Cache(args)) => commands::cache::run(args, &cli.discovery),
+		Some(Command::Deeplink(args)) => commands::deeplink::run(args),
+		Some(Command::Completions(args)) => commands::completions::run(args),
+		None => commands::open::run(&cli.open, &cli.discovery, config),
 	}
+}
+
+fn init_logging(verbose: u8) {
+	let default_level = match verbose {
+		0 => "warn",
+		1 => "debug",
+		_ => "trace",
+	};
+
+	let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(default_level));
 
-	Ok(())
+	tracing_subscriber::fmt()
+		.with_env_filter(filter)
+		.with_writer(std::io::stderr)
+		.without_time()
+		.with_target(false)
+		.init();
 }
@@ -0,0 +1,268 @@
+//! Samples the memory and CPU use of Studio while it runs, for `--monitor-resources`.
+
+use std::{
+	io,
+	process::Child,
+	time::{Duration, Instant},
+};
+
+use tracing::{debug, warn};
+
+use crate::format;
+
+/// How often the process is sampled.
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The resources a process used at some point.
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+	/// Resident memory, in bytes.
+	memory: u64,
+
+	/// CPU time used since the process started, in user and kernel mode.
+	cpu_time: Duration,
+}
+
+/// Keeps track of the resources used by a process across samples.
+pub struct Monitor {
+	max_memory: Option<u64>,
+	started: Instant,
+	last: Option<(Instant, Sample)>,
+	samples: u32,
+	peak_memory: u64,
+	total_memory: u64,
+	peak_cpu: f64,
+	failed: bool,
+}
+
+impl Monitor {
+	/// Creates a monitor, which reports the process as over the limit once its
+	/// resident memory exceeds `max_memory` bytes.
+	pub fn new(max_memory: Option<u64>) -> Monitor {
+		Monitor {
+			max_memory,
+			started: Instant::now(),
+			last: None,
+			samples: 0,
+			peak_memory: 0,
+			total_memory: 0,
+			peak_cpu: 0.0,
+			failed: false,
+		}
+	}
+
+	/// Samples the process when it's time to, and returns whether it's using more
+	/// memory than allowed. A process that can't be sampled is never over the
+	/// limit; the failure is reported once.
+	pub fn poll(&mut self, child: &Child) -> bool {
+		let now = Instant::now();
+
+		if self.failed || self.last.is_some_and(|(at, _)| now - at < SAMPLE_INTERVAL) {
+			return false;
+		}
+
+		let sample = match sample(child) {
+			Ok(sample) => sample,
+			Err(err) => {
+				warn!("Couldn't sample Roblox Studio's resource use: {}", err);
+				self.failed = true;
+				return false;
+			}
+		};
+
+		if let Some((at, last)) = self.last {
+			let elapsed = (now - at).as_secs_f64();
+			let cpu = sample.cpu_time.saturating_sub(last.cpu_time).as_secs_f64() / elapsed * 100.0;
+			self.peak_cpu = self.peak_cpu.max(cpu);
+		}
+
+		debug!("Roblox Studio uses {} of memory and {:?} of CPU time", format::size(sample.memory), sample.cpu_time);
+
+		self.last = Some((now, sample));
+		self.samples += 1;
+		self.peak_memory = self.peak_memory.max(sample.memory);
+		self.total_memory += sample.memory;
+
+		self.max_memory.is_some_and(|max_memory| sample.memory > max_memory)
+	}
+
+	/// The resources used across the session, as a few lines for the terminal.
+	pub fn summary(&self) -> String {
+		let Some((_, last)) = self.last else {
+			return "No resource usage was sampled".to_owned();
+		};
+
+		let running = self.started.elapsed();
+		let average_cpu = last.cpu_time.as_secs_f64() / running.as_secs_f64().max(f64::EPSILON) * 100.0;
+
+		format!(
+			"Ran for {}, sampled {} times\nMemory: peak {}, average {}\nCPU: {} of CPU time, average {:.0}%, peak {:.0}%",
+			humantime::format_duration(Duration::from_secs(running.as_secs())),
+			self.samples,
+			format::size(self.peak_memory),
+			format::size(self.total_memory / u64::from(self.samples)),
+			humantime::format_duration(Duration::from_millis(last.cpu_time.as_millis() as u64)),
+			average_cpu,
+			self.peak_cpu,
+		)
+	}
+}
+
+/// Parses a size like `8G`, `512M` or `1073741824`, with binary units.
+pub fn parse_size(value: &str) -> Result<u64, String> {
+	let value = value.trim();
+	let split = value.find(|character: char| !character.is_ascii_digit() && character != '.').unwrap_or(value.len());
+	let (number, unit) = value.split_at(split);
+
+	let multiplier: u64 = match unit.trim().to_ascii_uppercase().trim_end_matches("IB").trim_end_matches('B') {
+		"" => 1,
+		"K" => 1 << 10,
+		"M" => 1 << 20,
+		"G" => 1 << 30,
+		"T" => 1 << 40,
+		_ => return Err(format!("unknown unit in `{}`, expected K, M, G or T", value)),
+	};
+
+	let number: f64 = number.parse().map_err(|_| format!("invalid size `{}`, expected a size like 8G", value))?;
+
+	Ok((number * multiplier as f64) as u64)
+}
+
+#[cfg(target_os = "windows")]
+fn sample(child: &Child) -> io::Result<Sample> {
+	use std::{mem, os::windows::io::AsRawHandle};
+
+	use windows_sys::Win32::Foundation::FILETIME;
+	use windows_sys::Win32::System::ProcessStatus::{GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS};
+	use windows_sys::Win32::System::Threading::GetProcessTimes;
+
+	let handle = child.as_raw_handle() as _;
+
+	// SAFETY: the handle belongs to a child we haven't reaped yet, and the
+	// structures are plain data passed with their sizes.
+	unsafe {
+		let mut counters: PROCESS_MEMORY_COUNTERS = mem::zeroed();
+		counters.cb = mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32;
+
+		if GetProcessMemoryInfo(handle, &mut counters, counters.cb) == 0 {
+			return Err(io::Error::last_os_error());
+		}
+
+		let [mut creation, mut exit, mut kernel, mut user]: [FILETIME; 4] = mem::zeroed();
+
+		if GetProcessTimes(handle, &mut creation, &mut exit, &mut kernel, &mut user) == 0 {
+			return Err(io::Error::last_os_error());
+		}
+
+		// FILETIMEs count 100 nanosecond intervals.
+		let ticks = |time: FILETIME| (u64::from(time.dwHighDateTime) << 32) | u64::from(time.dwLowDateTime);
+
+		Ok(Sample {
+			memory: counters.WorkingSetSize as u64,
+			cpu_time: Duration::from_nanos((ticks(kernel) + ticks(user)) * 100),
+		})
+	}
+}
+
+/// Reads the process's resident pages from `/proc/<pid>/statm`, and its CPU
+/// time from the `utime` and `stime` fields of `/proc/<pid>/stat`.
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn sample(child: &Child) -> io::Result<Sample> {
+	use std::fs;
+
+	if roblox_studio::roblox_install::wsl_version().is_some() {
+		return Err(io::Error::new(
+			io::ErrorKind::Unsupported,
+			"Roblox Studio runs outside of WSL, where it can't be sampled",
+		));
+	}
+
+	let invalid = |file: &str| io::Error::new(io::ErrorKind::InvalidData, format!("unexpected contents in {}", file));
+	let process = format!("/proc/{}", child.id());
+
+	let statm = fs::read_to_string(format!("{}/statm", process))?;
+	let resident_pages: u64 = statm
+		.split_whitespace()
+		.nth(1)
+		.and_then(|pages| pages.parse().ok())
+		.ok_or_else(|| invalid("statm"))?;
+
+	// The command name can contain spaces and parentheses, so fields are
+	// counted from the last `)`, which ends it, starting at the 3rd field.
+	let stat = fs::read_to_string(format!("{}/stat", process))?;
+	let fields: Vec<&str> = stat
+		.rsplit_once(')')
+		.map(|(_, fields)| fields.split_whitespace().collect())
+		.unwrap_or_default();
+	let field = |index: usize| -> io::Result<u64> {
+		fields.get(index - 3).and_then(|field| field.parse().ok()).ok_or_else(|| invalid("stat"))
+	};
+	let ticks = field(14)? + field(15)?;
+
+	// SAFETY: `sysconf` has no memory safety requirements.
+	let (page_size, ticks_per_second) = unsafe { (libc::sysconf(libc::_SC_PAGESIZE), libc::sysconf(libc::_SC_CLK_TCK)) };
+
+	Ok(Sample {
+		memory: resident_pages * page_size.max(1) as u64,
+		cpu_time: Duration::from_secs_f64(ticks as f64 / ticks_per_second.max(1) as f64),
+	})
+}
+
+/// Asks `ps` for the process's resident memory in KiB and its CPU time, like
+/// `1:02.50` or `1:01:02.50`.
+#[cfg(target_os = "macos")]
+fn sample(child: &Child) -> io::Result<Sample> {
+	use std::process::Command;
+
+	let output = Command::new("ps")
+		.args(["-o", "rss=,time=", "-p", &child.id().to_string()])
+		.output()?;
+	let output = String::from_utf8_lossy(&output.stdout);
+	let invalid = || io::Error::new(io::ErrorKind::InvalidData, format!("unexpected output from ps: {}", output.trim()));
+
+	let mut fields = output.split_whitespace();
+	let memory: u64 = fields.next().and_then(|rss| rss.parse().ok()).ok_or_else(invalid)?;
+	let cpu_time = fields
+		.next()
+		.and_then(|time| {
+			time.split(':')
+				.try_fold(0.0, |total, part| Some(total * 60.0 + part.parse::<f64>().ok()?))
+		})
+		.ok_or_else(invalid)?;
+
+	Ok(Sample {
+		memory: memory * 1024,
+		cpu_time: Duration::from_secs_f64(cpu_time),
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parses_sizes() {
+		assert_eq!(parse_size("1073741824"), Ok(1 << 30));
+		assert_eq!(parse_size("512K"), Ok(512 << 10));
+		assert_eq!(parse_size("512M"), Ok(512 << 20));
+		assert_eq!(parse_size("8G"), Ok(8 << 30));
+		assert_eq!(parse_size("2T"), Ok(2 << 40));
+		assert_eq!(parse_size("1.5G"), Ok(3 << 29));
+	}
+
+	#[test]
+	fn parses_unit_spellings() {
+		for value in ["8g", "8G", "8GB", "8GiB", "8gib", "8 G", " 8G "] {
+			assert_eq!(parse_size(value), Ok(8 << 30), "{:?}", value);
+		}
+
+		assert_eq!(parse_size("100B"), Ok(100));
+	}
+
+	#[test]
+	fn rejects_invalid_sizes() {
+		for value in ["", "G", "8X", "8GG", "1.2.3G", "-1G", "eight"] {
+			assert!(parse_size(value).is_err(), "{:?}", value);
+		}
+	}
+}
@@ -0,0 +1,80 @@
+//! Desktop notifications for `--notify`, so Studio can be left loading or
+//! running in the background: `notify-send` on Linux, Notification Center
+//! through `osascript` on MacOS, and a tray balloon through PowerShell on
+//! Windows and WSL.
+
+use std::process::{Command, Stdio};
+
+use tracing::{debug, warn};
+
+#[cfg(not(target_os = "macos"))]
+use roblox_studio::roblox_install;
+
+/// Shows a notification. It's shown by another process that isn't waited for,
+/// and failing to start it is only reported.
+pub fn send(title: &str, body: &str) {
+	debug!("Notifying: {}: {}", title, body);
+
+	let mut command = command(title, body);
+
+	let result = command
+		.stdin(Stdio::null())
+		.stdout(Stdio::null())
+		.stderr(Stdio::null())
+		.spawn();
+
+	if let Err(err) = result {
+		warn!(
+			"Couldn't show a notification with {}: {}",
+			command.get_program().to_string_lossy(),
+			err
+		);
+	}
+}
+
+#[cfg(target_os = "macos")]
+fn command(title: &str, body: &str) -> Command {
+	let quote = |text: &str| format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""));
+
+	let mut command = Command::new("osascript");
+	command
+		.arg("-e")
+		.arg(format!("display notification {} with title {}", quote(body), quote(title)));
+	command
+}
+
+#[cfg(not(target_os = "macos"))]
+fn command(title: &str, body: &str) -> Command {
+	if cfg!(target_os = "windows") || (roblox_install::wsl_version().is_some() && roblox_install::wsl_interop_enabled()) {
+		return powershell(title, body);
+	}
+
+	let mut command = Command::new("notify-send");
+	command.args(["--app-name", "Roblox Studio", title, body]);
+	command
+}
+
+/// Shows a balloon from a tray icon, which Windows 10 and later turn into a
+/// toast notification. The icon has to stay around for the balloon to show.
+#[cfg(not(target_os = "macos"))]
+fn powershell(title: &str, body: &str) -> Command {
+	let quote = |text: &str| format!("'{}'", text.replace('\'', "''"));
+
+	let script = format!(
+		"Add-Type -AssemblyName System.Windows.Forms; \
+		$icon = New-Object System.Windows.Forms.NotifyIcon; \
+		$icon.Icon = [System.Drawing.SystemIcons]::Information; \
+		$icon.Visible = $true; \
+		$icon.ShowBalloonTip(10000, {}, {}, 'None'); \
+		Start-Sleep -Seconds 10; \
+		$icon.Dispose()",
+		quote(title),
+		quote(body)
+	);
+
+	let program = if cfg!(target_os = "windows") { "powershell" } else { "powershell.exe" };
+
+	let mut command = Command::new(program);
+	command.args(["-NoProfile", "-NonInteractive", "-WindowStyle", "Hidden", "-Command", &script]);
+	command
+}
@@ -0,0 +1,487 @@
+use std::{
+	collections::BTreeMap,
+	fs, io,
+	path::{Path, PathBuf},
+	time::{SystemTime, UNIX_EPOCH},
+};
+
+use tracing::{debug, warn};
+
+/// Suffix of the directory plugins are moved to while they're disabled, next to
+/// the plugins directory.
+const STASH_SUFFIX: &str = "disabled-by-roblox-studio";
+
+/// Suffix of the files and directories shadowed for a session, see
+/// [`Overrides::shadow`].
+const SHADOW_SUFFIX: &str = "shadowed-by-roblox-studio";
+
+/// Suffix of the copy of a file's previous contents kept while it's changed for
+/// a session, see [`Overrides::set_fflags`].
+const BACKUP_SUFFIX: &str = "overridden-by-roblox-studio";
+
+/// Changes made to files for a single Studio session. They're undone in reverse
+/// order by [`Overrides::revert`], or when the overrides are dropped, so a
+/// failure halfway through applying them leaves nothing behind. What's moved
+/// aside or changed is kept next to where it was, so the changes of a session
+/// that was killed before reverting them are reverted by [`restore_stale`].
+#[derive(Debug, Default)]
+pub struct Overrides {
+	undo: Vec<Undo>,
+}
+
+#[derive(Debug)]
+enum Undo {
+	/// Restore a file's previous contents, or remove it if it didn't exist, then
+	/// remove the backup of its contents.
+	Restore {
+		path: PathBuf,
+		previous: Option<Vec<u8>>,
+		backup: PathBuf,
+	},
+	/// Move a file or directory back to where it was.
+	Move { from: PathBuf, to: PathBuf },
+	/// Remove a directory that was created, once it's empty again.
+	RemoveDir(PathBuf),
+	/// Remove or move aside what was created at a path that was shadowed, then
+	/// move what was there before back.
+	Unshadow { path: PathBuf, stash: Option<PathBuf> },
+}
+
+impl Overrides {
+	pub fn new() -> Overrides {
+		Overrides::default()
+	}
+
+	/// Merges FFlag overrides into a `ClientAppSettings.json` file, creating it if
+	/// needed. Its previous contents are backed up next to it until they're
+	/// restored, and an empty backup stands for a file that didn't exist.
+	pub fn set_fflags(&mut self, path: &Path, fflags: &BTreeMap<String, serde_json::Value>) -> io::Result<()> {
+		restore_stale(path)?;
+
+		let previous = match fs::read(path) {
+			Ok(contents) => Some(contents),
+			Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+			Err(err) => return Err(err),
+		};
+
+		let mut settings = match &previous {
+			Some(contents) => match serde_json::from_slice(contents)? {
+				serde_json::Value::Object(settings) => settings,
+				_ => {
+					return Err(io::Error::new(
+						io::ErrorKind::InvalidData,
+						format!("{} doesn't contain a JSON object", path.display()),
+					));
+				}
+			},
+			None => serde_json::Map::new(),
+		};
+
+		for (name, value) in fflags {
+			settings.insert(name.clone(), value.clone());
+		}
+
+		if let Some(directory) = path.parent() {
+			self.create_dir(directory)?;
+		}
+
+		let contents = serde_json::to_vec_pretty(&settings)?;
+		let backup = sibling_path(path, BACKUP_SUFFIX);
+
+		fs::write(&backup, previous.as_deref().unwrap_or_default())?;
+
+		debug!("Writing {} FFlag override(s) to {}", fflags.len(), path.display());
+		self.undo.push(Undo::Restore {
+			path: path.to_owned(),
+			previous,
+			backup,
+		});
+		fs::write(path, contents)
+	}
+
+	/// Moves every plugin that isn't in `allowed` out of the plugins directory.
+	pub fn allow_plugins(&mut self, plugins: &Path, allowed: &[String]) -> io::Result<()> {
+		let entries = match fs::read_dir(plugins) {
+			Ok(entries) => entries,
+			Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+			Err(err) => return Err(err),
+		};
+
+		restore_stale(plugins)?;
+
+		let stash = stash_path(plugins);
+
+		if stash.exists() {
+			return Err(io::Error::new(
+				io::ErrorKind::AlreadyExists,
+				format!(
+					"{} was left behind by a previous session with plugins that are installed again, \
+					 move the ones to keep back to {} first",
+					stash.display(),
+					plugins.display()
+				),
+			));
+		}
+
+		for entry in entries {
+			let entry = entry?;
+			let name = entry.file_name();
+
+			if allowed.iter().any(|allowed| name.to_str() == Some(allowed)) {
+				continue;
+			}
+
+			self.create_dir(&stash)?;
+
+			let disabled = stash.join(&name);
+
+			debug!("Disabling plugin {}", entry.path().display());
+			fs::rename(entry.path(), &disabled)?;
+			self.undo.push(Undo::Move {
+				from: disabled,
+				to: entry.path(),
+			});
+		}
+
+		for allowed in allowed {
+			if !plugins.join(allowed).exists() {
+				warn!("Plugin {} isn't installed in {}", allowed, plugins.display());
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Moves a file or directory out of the way, so Studio starts without it.
+	/// When reverting, a file or an empty directory Studio created in its place
+	/// is removed, but a directory it wrote to, like plugins installed during
+	/// the session, is kept next to it, see [`session_path`].
+	pub fn shadow(&mut self, path: &Path) -> io::Result<()> {
+		restore_stale(path)?;
+
+		let stash = if path.exists() {
+			let stash = sibling_path(path, SHADOW_SUFFIX);
+
+			debug!("Shadowing {}", path.display());
+			fs::rename(path, &stash)?;
+			Some(stash)
+		} else {
+			None
+		};
+
+		self.undo.push(Undo::Unshadow {
+			path: path.to_owned(),
+			stash,
+		});
+
+		Ok(())
+	}
+
+	/// Undoes every change, returning the first error after trying them all.
+	pub fn revert(mut self) -> io::Result<()> {
+		self.revert_all()
+	}
+
+	fn revert_all(&mut self) -> io::Result<()> {
+		let mut result = Ok(());
+
+		while let Some(undo) = self.undo.pop() {
+			if let Err(err) = undo.apply() {
+				warn!("Failed to revert {:?}: {}", undo, err);
+				result = result.and(Err(err));
+			}
+		}
+
+		result
+	}
+
+	fn create_dir(&mut self, directory: &Path) -> io::Result<()> {
+		if directory.is_dir() {
+			return Ok(());
+		}
+
+		if let Some(parent) = directory.parent() {
+			self.create_dir(parent)?;
+		}
+
+		fs::create_dir(directory)?;
+		self.undo.push(Undo::RemoveDir(directory.to_owned()));
+
+		Ok(())
+	}
+}
+
+impl Drop for Overrides {
+	fn drop(&mut self) {
+		let _ = self.revert_all();
+	}
+}
+
+impl Undo {
+	fn apply(&self) -> io::Result<()> {
+		match self {
+			Undo::Restore { path, previous, backup } => {
+				match previous {
+					Some(contents) => fs::write(path, contents)?,
+					None => match fs::remove_file(path) {
+						Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+						_ => {}
+					},
+				}
+
+				fs::remove_file(backup)
+			}
+			Undo::Move { from, to } => fs::rename(from, to),
+			Undo::RemoveDir(directory) => fs::remove_dir(directory),
+			Undo::Unshadow { path, stash } => {
+				match fs::symlink_metadata(path) {
+					Ok(metadata) if !metadata.is_dir() => fs::remove_file(path)?,
+					Ok(_) if fs::read_dir(path)?.next().is_none() => fs::remove_dir(path)?,
+					Ok(_) => {
+						let kept = session_path(path);
+
+						fs::rename(path, &kept)?;
+						warn!("Kept what Roblox Studio wrote to {} during the session in {}", path.display(), kept.display());
+					}
+					Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+					Err(err) => return Err(err),
+				}
+
+				match stash {
+					Some(stash) => fs::rename(stash, path),
+					None => Ok(()),
+				}
+			}
+		}
+	}
+}
+
+/// Reverts what a session that was killed before it could revert its changes
+/// left behind at a path: moves it back if it was shadowed, moves plugins back
+/// to it if they were disabled, and restores its contents if they were changed.
+pub fn restore_stale(path: &Path) -> io::Result<()> {
+	let shadowed = sibling_path(path, SHADOW_SUFFIX);
+
+	if shadowed.exists() {
+		warn!("Moving {} back, which a previous session left behind", shadowed.display());
+		Undo::Unshadow {
+			path: path.to_owned(),
+			stash: Some(shadowed),
+		}
+		.apply()?;
+	}
+
+	let stash = stash_path(path);
+
+	if stash.is_dir() {
+		warn!("Moving the plugins in {}, which a previous session left behind, back", stash.display());
+
+		for entry in fs::read_dir(&stash)? {
+			let entry = entry?;
+			let plugin = path.join(entry.file_name());
+
+			// A plugin installed again since is kept, and so is the stash with it.
+			if plugin.exists() {
+				warn!("Keeping {}, since {} is installed again", entry.path().display(), plugin.display());
+			} else {
+				fs::rename(entry.path(), plugin)?;
+			}
+		}
+
+		if fs::read_dir(&stash)?.next().is_none() {
+			fs::remove_dir(&stash)?;
+		}
+	}
+
+	let backup = sibling_path(path, BACKUP_SUFFIX);
+
+	if backup.is_file() {
+		warn!("Restoring {}, which a previous session left changed", path.display());
+
+		let previous = fs::read(&backup)?;
+
+		Undo::Restore {
+			path: path.to_owned(),
+			previous: (!previous.is_empty()).then_some(previous),
+			backup,
+		}
+		.apply()?;
+	}
+
+	Ok(())
+}
+
+fn stash_path(plugins: &Path) -> PathBuf {
+	sibling_path(plugins, STASH_SUFFIX)
+}
+
+/// Where what Studio wrote to a shadowed path during a session is kept, like
+/// `Plugins.session-1714564800-by-roblox-studio`, so nothing is deleted when the
+/// shadowed path is moved back.
+fn session_path(path: &Path) -> PathBuf {
+	let now = SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.unwrap_or_default()
+		.as_secs();
+
+	sibling_path(path, &format!("session-{}-by-roblox-studio", now))
+}
+
+fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+	let mut name = path.file_name().unwrap_or_default().to_owned();
+	name.push(".");
+	name.push(suffix);
+	path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{
+		env, mem, process,
+		sync::atomic::{AtomicUsize, Ordering},
+	};
+
+	use super::*;
+
+	/// A new empty directory, removed when dropped.
+	struct TempDir(PathBuf);
+
+	impl TempDir {
+		fn new() -> TempDir {
+			static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+			let path = env::temp_dir().join(format!(
+				"roblox-studio-overrides-{}-{}",
+				process::id(),
+				COUNTER.fetch_add(1, Ordering::Relaxed)
+			));
+
+			fs::create_dir_all(&path).unwrap();
+			TempDir(path)
+		}
+	}
+
+	impl Drop for TempDir {
+		fn drop(&mut self) {
+			let _ = fs::remove_dir_all(&self.0);
+		}
+	}
+
+	fn names(directory: &Path) -> Vec<String> {
+		let mut names: Vec<_> = fs::read_dir(directory)
+			.unwrap()
+			.map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+			.collect();
+
+		names.sort();
+		names
+	}
+
+	#[test]
+	fn shadowed_paths_are_moved_back() {
+		let temp = TempDir::new();
+		let plugins = temp.0.join("Plugins");
+		let settings = temp.0.join("GlobalSettings_13.xml");
+		fs::create_dir(&plugins).unwrap();
+		fs::write(plugins.join("Mine.rbxm"), "mine").unwrap();
+
+		let mut overrides = Overrides::new();
+		overrides.shadow(&plugins).unwrap();
+		overrides.shadow(&settings).unwrap();
+
+		assert!(!plugins.exists());
+		fs::create_dir(&plugins).unwrap();
+		fs::write(&settings, "written by Studio").unwrap();
+
+		overrides.revert().unwrap();
+
+		assert_eq!(names(&temp.0), ["Plugins"]);
+		assert_eq!(names(&plugins), ["Mine.rbxm"]);
+	}
+
+	#[test]
+	fn stale_shadows_are_moved_back() {
+		let temp = TempDir::new();
+		let plugins = temp.0.join("Plugins");
+		fs::create_dir(&plugins).unwrap();
+		fs::write(plugins.join("Mine.rbxm"), "mine").unwrap();
+
+		let mut overrides = Overrides::new();
+		overrides.shadow(&plugins).unwrap();
+		mem::forget(overrides);
+
+		fs::create_dir(&plugins).unwrap();
+		fs::write(plugins.join("New.rbxm"), "installed during the session").unwrap();
+
+		restore_stale(&plugins).unwrap();
+
+		assert_eq!(names(&plugins), ["Mine.rbxm"]);
+
+		let names = names(&temp.0);
+		assert_eq!(names.len(), 2);
+		assert!(names[1].starts_with("Plugins.session-"), "{:?}", names);
+	}
+
+	#[test]
+	fn stale_disabled_plugins_are_moved_back() {
+		let temp = TempDir::new();
+		let plugins = temp.0.join("Plugins");
+		fs::create_dir(&plugins).unwrap();
+
+		for name in ["Allowed.rbxm", "Other.rbxm", "Reinstalled.rbxm"] {
+			fs::write(plugins.join(name), name).unwrap();
+		}
+
+		let mut overrides = Overrides::new();
+		overrides.allow_plugins(&plugins, &["Allowed.rbxm".to_owned()]).unwrap();
+		mem::forget(overrides);
+
+		assert_eq!(names(&plugins), ["Allowed.rbxm"]);
+		fs::write(plugins.join("Reinstalled.rbxm"), "again").unwrap();
+
+		restore_stale(&plugins).unwrap();
+
+		assert_eq!(names(&plugins), ["Allowed.rbxm", "Other.rbxm", "Reinstalled.rbxm"]);
+		assert_eq!(fs::read_to_string(plugins.join("Reinstalled.rbxm")).unwrap(), "again");
+		assert_eq!(names(&stash_path(&plugins)), ["Reinstalled.rbxm"]);
+	}
+
+	#[test]
+	fn stale_fflags_are_reverted() {
+		let temp = TempDir::new();
+		let existing = temp.0.join("Existing.json");
+		let created = temp.0.join("Created.json");
+		fs::write(&existing, r#"{"FFlagKept": true}"#).unwrap();
+
+		let fflags = BTreeMap::from([("FFlagDebug".to_owned(), serde_json::Value::Bool(true))]);
+
+		let mut overrides = Overrides::new();
+		overrides.set_fflags(&existing, &fflags).unwrap();
+		overrides.set_fflags(&created, &fflags).unwrap();
+		mem::forget(overrides);
+
+		assert!(fs::read_to_string(&existing).unwrap().contains("FFlagDebug"));
+
+		restore_stale(&existing).unwrap();
+		restore_stale(&created).unwrap();
+
+		assert_eq!(fs::read_to_string(&existing).unwrap(), r#"{"FFlagKept": true}"#);
+		assert_eq!(names(&temp.0), ["Existing.json"]);
+	}
+
+	#[test]
+	fn fflags_are_reverted() {
+		let temp = TempDir::new();
+		let settings = temp.0.join("ClientAppSettings.json");
+		fs::write(&settings, "{}").unwrap();
+
+		let fflags = BTreeMap::from([("FFlagDebug".to_owned(), serde_json::Value::Bool(true))]);
+
+		let mut overrides = Overrides::new();
+		overrides.set_fflags(&settings, &fflags).unwrap();
+		overrides.revert().unwrap();
+
+		assert_eq!(fs::read_to_string(&settings).unwrap(), "{}");
+		assert_eq!(names(&temp.0), ["ClientAppSettings.json"]);
+	}
+}
@@ -0,0 +1,190 @@
+//! Finds the Roblox Studio processes running on this machine, whichever
+//! installation they were started from.
+
+use std::{io, path::PathBuf, process::Command};
+
+use tracing::debug;
+
+/// A running Roblox Studio process.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct StudioProcess {
+    /// The process ID. Under WSL, this is the ID of the Windows process.
+    pub pid: u32,
+
+    /// The executable the process runs, when the platform reports it. Processes
+    /// running through Wine report the Windows path of the executable.
+    pub path: Option<PathBuf>,
+}
+
+/// The Roblox Studio processes currently running. They're listed with
+/// `tasklist` on Windows and under WSL, from `/proc` on Linux (for Studio
+/// running through Wine), and with `ps` on MacOS. Errors are logged, and give
+/// an empty list.
+pub fn running() -> Vec<StudioProcess> {
+    let processes = list_processes();
+    debug!("Found {} running Roblox Studio processes", processes.len());
+    processes
+}
+
+#[cfg(target_os = "windows")]
+fn list_processes() -> Vec<StudioProcess> {
+    tasklist("tasklist")
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn list_processes() -> Vec<StudioProcess> {
+    use std::fs;
+
+    use crate::roblox_install::wsl_version;
+
+    if wsl_version().is_some() {
+        return tasklist("tasklist.exe");
+    }
+
+    let Ok(entries) = fs::read_dir("/proc") else {
+        debug!("Couldn't read /proc");
+        return Vec::new();
+    };
+
+    entries
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let pid = entry.file_name().to_str()?.parse().ok()?;
+
+            // Wine replaces the command line of the processes it runs with the
+            // Windows path of their executable, like `C:\...\RobloxStudioBeta.exe`.
+            let command_line = fs::read(entry.path().join("cmdline")).ok()?;
+            let program = command_line.split(|byte| *byte == 0).next()?;
+            let program = String::from_utf8_lossy(program);
+            let name = program.rsplit(['\\', '/']).next()?;
+
+            name.eq_ignore_ascii_case("RobloxStudioBeta.exe").then(|| StudioProcess {
+                pid,
+                path: Some(PathBuf::from(program.as_ref())),
+            })
+        })
+        .collect()
+}
+
+#[cfg(target_os = "macos")]
+fn list_processes() -> Vec<StudioProcess> {
+    let output = match Command::new("ps").args(["-axo", "pid=,comm="]).output() {
+        Ok(output) => output,
+        Err(err) => {
+            debug!("Couldn't run ps: {}", err);
+            return Vec::new();
+        }
+    };
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (pid, program) = line.trim_start().split_once(' ')?;
+            let program = program.trim();
+
+            if !program.ends_with(".app/Contents/MacOS/RobloxStudio") {
+                return None;
+            }
+
+            Some(StudioProcess {
+                pid: pid.parse().ok()?,
+                path: Some(PathBuf::from(program)),
+            })
+        })
+        .collect()
+}
+
+/// Lists Studio processes with `tasklist`, which prints CSV lines like
+/// `"RobloxStudioBeta.exe","4242","Console","1","812,344 K"`, or a message when
+/// none match the filter.
+#[cfg(not(target_os = "macos"))]
+fn tasklist(program: &str) -> Vec<StudioProcess> {
+    let output = Command::new(program)
+        .args(["/FO", "CSV", "/NH", "/FI", "IMAGENAME eq RobloxStudioBeta.exe"])
+        .output();
+
+    let output = match output {
+        Ok(output) => output,
+        Err(err) => {
+            debug!("Couldn't run {}: {}", program, err);
+            return Vec::new();
+        }
+    };
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.trim().split("\",\"");
+            let name = fields.next()?.strip_prefix('"')?;
+            let pid = fields.next()?.parse().ok()?;
+
+            name.eq_ignore_ascii_case("RobloxStudioBeta.exe")
+                .then_some(StudioProcess { pid, path: None })
+        })
+        .collect()
+}
+
+impl StudioProcess {
+    /// Whether the process is still running.
+    pub fn is_running(&self) -> bool {
+        running().iter().any(|process| process.pid == self.pid)
+    }
+
+    /// Asks the process to exit, like closing its window. Studio can still
+    /// prompt to save unsaved changes, so it may not exit right away.
+    pub fn terminate(&self) -> io::Result<()> {
+        self.signal(false)
+    }
+
+    /// Kills the process without giving it a chance to save anything.
+    pub fn kill(&self) -> io::Result<()> {
+        self.signal(true)
+    }
+
+    #[cfg(target_os = "windows")]
+    fn signal(&self, force: bool) -> io::Result<()> {
+        taskkill("taskkill", self.pid, force)
+    }
+
+    #[cfg(unix)]
+    fn signal(&self, force: bool) -> io::Result<()> {
+        #[cfg(not(target_os = "macos"))]
+        if crate::roblox_install::wsl_version().is_some() {
+            return taskkill("taskkill.exe", self.pid, force);
+        }
+
+        let signal = if force { libc::SIGKILL } else { libc::SIGTERM };
+
+        // SAFETY: `kill` has no memory safety requirements.
+        if unsafe { libc::kill(self.pid as libc::pid_t, signal) } == 0 {
+            Ok(())
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+}
+
+/// Ends a Windows process with `taskkill`, which closes its windows unless
+/// it's forced to terminate it.
+#[cfg(not(target_os = "macos"))]
+fn taskkill(program: &str, pid: u32, force: bool) -> io::Result<()> {
+    let mut command = Command::new(program);
+
+    if force {
+        command.arg("/F");
+    }
+
+    let output = command.args(["/PID", &pid.to_string()]).output()?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!(
+            "{} failed: {}",
+            program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
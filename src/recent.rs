@@ -0,0 +1,243 @@
+//! Reads the places recently opened in Studio, from the list Studio keeps in its
+//! settings for the start page and the `File > Open Recent` menu.
+
+use std::path::PathBuf;
+
+use tracing::debug;
+
+use crate::roblox_install::RobloxStudio;
+
+/// Name of the setting holding the recently opened places, most recent first.
+const RECENT_FILES_SETTING: &str = "rbxRecentFiles";
+
+/// A place recently opened in Studio.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum RecentPlace {
+    /// A place file, with a path on this machine.
+    File(PathBuf),
+    /// A place published to Roblox, which Studio records by its ID.
+    Cloud { place_id: u64 },
+}
+
+impl RecentPlace {
+    fn parse(entry: &str, studio: &RobloxStudio) -> Option<RecentPlace> {
+        let entry = entry.trim();
+
+        if entry.is_empty() {
+            return None;
+        }
+
+        match entry.parse() {
+            Ok(place_id) => Some(RecentPlace::Cloud { place_id }),
+            Err(_) => Some(RecentPlace::File(studio.local_path(entry))),
+        }
+    }
+}
+
+impl RobloxStudio {
+    /// The places recently opened in this installation of Studio, most recent
+    /// first. Studio keeps them in the registry on Windows (which is read from the
+    /// Wine prefix's `user.reg` on Linux) and in its preferences on MacOS. Under
+    /// WSL the registry can't be read, so the list is always empty.
+    pub fn recent_places(&self) -> Vec<RecentPlace> {
+        self.recent_files_setting()
+            .iter()
+            .filter_map(|entry| RecentPlace::parse(entry, self))
+            .collect()
+    }
+
+    #[cfg(target_os = "windows")]
+    fn recent_files_setting(&self) -> Vec<String> {
+        let hkcu = winreg::RegKey::predef(winreg::enums::HKEY_CURRENT_USER);
+
+        debug!(r"Reading HKCU\Software\Roblox\RobloxStudio\{}", RECENT_FILES_SETTING);
+
+        hkcu.open_subkey(r"Software\Roblox\RobloxStudio")
+            .and_then(|key| key.get_value::<String, _>(RECENT_FILES_SETTING))
+            .map_err(|err| debug!("Couldn't read the recent places: {}", err))
+            .map(|files| files.lines().map(str::to_owned).collect())
+            .unwrap_or_default()
+    }
+
+    #[cfg(target_os = "macos")]
+    fn recent_files_setting(&self) -> Vec<String> {
+        let Some(home) = dirs::home_dir() else {
+            return Vec::new();
+        };
+
+        let preferences = home
+            .join("Library")
+            .join("Preferences")
+            .join("com.Roblox.RobloxStudio.plist");
+
+        let plist = match plist::Value::from_file(&preferences) {
+            Ok(plist) => plist,
+            Err(err) => {
+                debug!("Couldn't read {}: {}", preferences.display(), err);
+                return Vec::new();
+            }
+        };
+
+        plist
+            .as_dictionary()
+            .and_then(|dictionary| dictionary.get(RECENT_FILES_SETTING))
+            .and_then(plist::Value::as_array)
+            .map(|files| files.iter().filter_map(|file| Some(file.as_string()?.to_owned())).collect())
+            .unwrap_or_default()
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    fn recent_files_setting(&self) -> Vec<String> {
+        let Some(prefix) = self.wine_prefix() else {
+            debug!("The recent places can only be read from a Wine prefix on Linux");
+            return Vec::new();
+        };
+
+        let registry = prefix.join("user.reg");
+
+        match std::fs::read_to_string(&registry) {
+            Ok(registry) => wine_registry_value(&registry, r"Software\\Roblox\\RobloxStudio", RECENT_FILES_SETTING)
+                .unwrap_or_default(),
+            Err(err) => {
+                debug!("Couldn't read {}: {}", registry.display(), err);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Converts a path recorded by Studio into a path on this machine.
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    fn local_path(&self, path: &str) -> PathBuf {
+        let Some(prefix) = self.wine_prefix() else {
+            return PathBuf::from(path);
+        };
+
+        let mut chars = path.chars();
+        let (Some(drive), Some(':')) = (chars.next(), chars.next()) else {
+            return PathBuf::from(path);
+        };
+
+        // Wine maps Z: to the root of the file system, and other drives to the
+        // `dosdevices` links of the prefix.
+        let root = match drive.to_ascii_lowercase() {
+            'z' => PathBuf::from("/"),
+            drive => prefix.join("dosdevices").join(format!("{}:", drive)),
+        };
+
+        chars
+            .as_str()
+            .split(['\\', '/'])
+            .filter(|component| !component.is_empty())
+            .fold(root, |path, component| path.join(component))
+    }
+
+    #[cfg(any(target_os = "windows", target_os = "macos"))]
+    fn local_path(&self, path: &str) -> PathBuf {
+        PathBuf::from(path)
+    }
+}
+
+/// Reads a string or list of strings from a Wine registry file, where keys are
+/// sections like `[Software\\Roblox\\RobloxStudio] 1700000000` and values lines
+/// like `"name"="value"` or `"name"=str(7):"first\0second\0"`.
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn wine_registry_value(registry: &str, key: &str, name: &str) -> Option<Vec<String>> {
+    let section = format!("[{}]", key);
+    let value_prefix = format!("\"{}\"=", name);
+
+    let value = registry
+        .lines()
+        .skip_while(|line| !line.starts_with(&section))
+        .skip(1)
+        .take_while(|line| !line.starts_with('['))
+        .find_map(|line| line.strip_prefix(&value_prefix))?;
+
+    let value = value.strip_prefix("str(7):").unwrap_or(value);
+    let value = value.strip_prefix('"')?.strip_suffix('"')?;
+
+    let mut entries = vec![String::new()];
+    let mut chars = value.chars();
+
+    while let Some(char) = chars.next() {
+        match char {
+            '\\' => match chars.next() {
+                Some('0') => entries.push(String::new()),
+                Some('n') => entries.last_mut()?.push('\n'),
+                Some(escaped) => entries.last_mut()?.push(escaped),
+                None => {}
+            },
+            char => entries.last_mut()?.push(char),
+        }
+    }
+
+    entries.retain(|entry| !entry.is_empty());
+    Some(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::locator::{MockLocator, StudioLocator};
+
+    #[test]
+    fn parses_recent_places() {
+        let mock = MockLocator::new().unwrap();
+        let studio = mock.locate().unwrap();
+
+        assert_eq!(RecentPlace::parse("1818", &studio), Some(RecentPlace::Cloud { place_id: 1818 }));
+        assert_eq!(RecentPlace::parse(" 1818\r", &studio), Some(RecentPlace::Cloud { place_id: 1818 }));
+        assert_eq!(RecentPlace::parse("", &studio), None);
+        assert_eq!(RecentPlace::parse("  ", &studio), None);
+        assert!(matches!(RecentPlace::parse("Place1.rbxl", &studio), Some(RecentPlace::File(_))));
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    #[test]
+    fn reads_lists_from_wine_registry_files() {
+        let registry = r#"WINE REGISTRY Version 2
+
+[Software\\Roblox\\RobloxPlayer] 1700000000
+"rbxRecentFiles"="ignored"
+
+[Software\\Roblox\\RobloxStudio] 1700000000
+#time=1da0a1b2c3d4e5f
+"Other"="value"
+"rbxRecentFiles"=str(7):"C:\\Users\\me\\Place.rbxl\01818\0Z:\\home\\me\\Other Place.rbxl\0"
+
+[Software\\Wine] 1700000000
+"#;
+
+        assert_eq!(
+            wine_registry_value(registry, r"Software\\Roblox\\RobloxStudio", RECENT_FILES_SETTING).unwrap(),
+            [r"C:\Users\me\Place.rbxl", "1818", r"Z:\home\me\Other Place.rbxl"]
+        );
+        assert_eq!(
+            wine_registry_value(registry, r"Software\\Roblox\\RobloxStudio", "Other").unwrap(),
+            ["value"]
+        );
+        assert_eq!(wine_registry_value(registry, r"Software\\Roblox\\RobloxStudio", "Missing"), None);
+        assert_eq!(wine_registry_value(registry, r"Software\\Roblox\\Missing", RECENT_FILES_SETTING), None);
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    #[test]
+    fn converts_paths_in_wine_prefixes() {
+        let temporary = MockLocator::new().unwrap();
+        let prefix = temporary.directory().join("prefix");
+        let mock = MockLocator::in_directory(prefix.join("drive_c").join("Roblox")).unwrap();
+        let studio = RobloxStudio::locate_from_directory(mock.locate().unwrap().application_path()).unwrap();
+
+        assert_eq!(
+            studio.local_path(r"Z:\home\me\Place.rbxl"),
+            PathBuf::from("/home/me/Place.rbxl")
+        );
+        assert_eq!(
+            studio.local_path(r"C:\Users\me\Place.rbxl"),
+            prefix.join("dosdevices").join("c:").join("Users").join("me").join("Place.rbxl")
+        );
+        assert_eq!(studio.local_path("Place.rbxl"), PathBuf::from("Place.rbxl"));
+    }
+}
@@ -0,0 +1,139 @@
+//! Parses `ReflectionMetadata.xml`, the file Studio reads the explorer order,
+//! icons and descriptions of classes and their members from.
+
+use std::{fs, io, str::FromStr};
+
+use thiserror::Error;
+
+use crate::roblox_install::RobloxStudio;
+
+/// The contents of `ReflectionMetadata.xml`.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct ReflectionMetadata {
+    /// Metadata of every class, in the order of the file.
+    pub classes: Vec<ClassMetadata>,
+}
+
+/// Metadata of a class, like `Part`.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct ClassMetadata {
+    pub name: String,
+
+    /// Where the class is sorted in the explorer, lower first.
+    pub explorer_order: Option<i32>,
+
+    /// Index of the class icon in Studio's icon sheet.
+    pub explorer_image_index: Option<u32>,
+
+    /// Category the class is listed under in the insert object menu.
+    pub category: Option<String>,
+
+    /// Class of the instance new instances are inserted into, like `Workspace`.
+    pub preferred_parent: Option<String>,
+
+    /// Description shown in the object browser.
+    pub summary: Option<String>,
+
+    pub deprecated: bool,
+
+    /// Properties, functions, events and callbacks of the class that have metadata.
+    pub members: Vec<MemberMetadata>,
+}
+
+/// Metadata of a member of a class.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct MemberMetadata {
+    pub name: String,
+
+    /// Description shown in the object browser.
+    pub summary: Option<String>,
+
+    pub deprecated: bool,
+}
+
+#[derive(Debug, Error)]
+#[error("Invalid ReflectionMetadata.xml: {0}")]
+/// The error returned when parsing a [`ReflectionMetadata`] fails.
+pub struct ParseReflectionMetadataError(String);
+
+impl ReflectionMetadata {
+    /// Finds the metadata of a class by its name.
+    pub fn class(&self, name: &str) -> Option<&ClassMetadata> {
+        self.classes.iter().find(|class| class.name == name)
+    }
+}
+
+impl FromStr for ReflectionMetadata {
+    type Err = ParseReflectionMetadataError;
+
+    fn from_str(xml: &str) -> Result<ReflectionMetadata, ParseReflectionMetadataError> {
+        let document =
+            roxmltree::Document::parse(xml).map_err(|err| ParseReflectionMetadataError(err.to_string()))?;
+
+        let classes = document
+            .descendants()
+            .filter(|node| is_item(node, "ReflectionMetadataClass"))
+            .map(|class| {
+                let members = class
+                    .children()
+                    .filter(|group| group.has_tag_name("Item"))
+                    .flat_map(|group| group.children())
+                    .filter(|member| is_item(member, "ReflectionMetadataMember"))
+                    .map(|member| MemberMetadata {
+                        name: property(&member, "Name").unwrap_or_default(),
+                        summary: property(&member, "summary"),
+                        deprecated: property(&member, "Deprecated").as_deref() == Some("true"),
+                    })
+                    .collect();
+
+                ClassMetadata {
+                    name: property(&class, "Name").unwrap_or_default(),
+                    explorer_order: property(&class, "ExplorerOrder").and_then(|order| order.parse().ok()),
+                    explorer_image_index: property(&class, "ExplorerImageIndex").and_then(|index| index.parse().ok()),
+                    category: property(&class, "ClassCategory"),
+                    preferred_parent: property(&class, "PreferredParent"),
+                    summary: property(&class, "summary"),
+                    deprecated: property(&class, "Deprecated").as_deref() == Some("true"),
+                    members,
+                }
+            })
+            .filter(|class| !class.name.is_empty())
+            .collect();
+
+        Ok(ReflectionMetadata { classes })
+    }
+}
+
+impl RobloxStudio {
+    /// Reads and parses this installation's `ReflectionMetadata.xml`, see
+    /// [`reflection_metadata_path`](RobloxStudio::reflection_metadata_path).
+    pub fn reflection_metadata(&self) -> io::Result<ReflectionMetadata> {
+        let path = self.reflection_metadata_path().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, "ReflectionMetadata.xml is missing from the installation")
+        })?;
+
+        fs::read_to_string(path)?
+            .parse()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+}
+
+fn is_item(node: &roxmltree::Node, class: &str) -> bool {
+    node.has_tag_name("Item") && node.attribute("class") == Some(class)
+}
+
+/// Value of a property of an `Item`, from its `Properties` element.
+fn property(item: &roxmltree::Node, name: &str) -> Option<String> {
+    item.children()
+        .find(|child| child.has_tag_name("Properties"))?
+        .children()
+        .find(|property| property.attribute("name") == Some(name))
+        .map(|property| property.text().unwrap_or_default().trim().to_owned())
+        .filter(|value| !value.is_empty())
+}
@@ -1,10 +1,18 @@
 use std::{
-    env, fs, io,
-    path::{Path, PathBuf},
+    env,
+    ffi::{OsStr, OsString},
+    fs, io,
+    net::{IpAddr, Ipv4Addr},
+    path::{self, Path, PathBuf},
     process::Command,
+    sync::OnceLock,
 };
 
 use thiserror::Error;
+use tracing::{debug, trace};
+
+use crate::deploy::LIVE_CHANNEL;
+use crate::studio_version::StudioVersion;
 
 #[cfg(target_os = "windows")]
 use winreg::RegKey;
@@ -13,17 +21,51 @@ use winreg::RegKey;
 /// contains [`Error`] in the `Err` type.
 pub type Result<T> = std::result::Result<T, Error>;
 
-const ROBLOX_STUDIO_PATH_VARIABLE: &str = "ROBLOX_STUDIO_PATH";
+/// Environment variable pointing at the installation to use, see [`RobloxStudio::locate`].
+pub const ROBLOX_STUDIO_PATH_VARIABLE: &str = "ROBLOX_STUDIO_PATH";
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+const ROBLOX_STUDIO_WINDOWS_USER_VARIABLE: &str = "ROBLOX_STUDIO_WINDOWS_USER";
+const ROBLOX_STUDIO_APPLICATION_PATH_VARIABLE: &str = "ROBLOX_STUDIO_APPLICATION_PATH";
+const ROBLOX_STUDIO_CONTENT_PATH_VARIABLE: &str = "ROBLOX_STUDIO_CONTENT_PATH";
+const ROBLOX_STUDIO_PLUGINS_PATH_VARIABLE: &str = "ROBLOX_STUDIO_PLUGINS_PATH";
+
+/// Where WSL mounts Windows drives, unless `/etc/wsl.conf` says otherwise.
+const DEFAULT_WSL_MOUNT_ROOT: &str = "/mnt/";
+
+/// Registry keys holding Studio's `ContentFolder`, in the order they're probed:
+/// per-user installs, then per-machine installs, then legacy 32-bit installs.
+#[cfg(target_os = "windows")]
+const STUDIO_REGISTRY_KEYS: &[(winreg::HKEY, &str, &str)] = &[
+    (winreg::enums::HKEY_CURRENT_USER, "HKCU", r"Software\Roblox\RobloxStudio"),
+    (winreg::enums::HKEY_LOCAL_MACHINE, "HKLM", r"Software\Roblox\RobloxStudio"),
+    (winreg::enums::HKEY_LOCAL_MACHINE, "HKLM", r"Software\WOW6432Node\Roblox\RobloxStudio"),
+];
+
+/// Bundle names Studio is installed under on macOS, in the order they're probed.
+#[cfg(target_os = "macos")]
+const BUNDLE_NAMES: &[&str] = &["RobloxStudio.app", "RobloxStudioBeta.app"];
+
+#[cfg(target_os = "macos")]
+const BUNDLE_IDENTIFIER: &str = "com.roblox.RobloxStudio";
+
+/// Application ID of Vinegar's Flatpak, which keeps its data in
+/// `~/.var/app/<id>` instead of `~/.local/share`.
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+const VINEGAR_FLATPAK: &str = "org.vinegarhq.Vinegar";
+
+/// File in a version directory recording the channel it was installed from.
+pub(crate) const CHANNEL_FILE: &str = "channel.txt";
 
 #[derive(Debug, Error)]
 #[non_exhaustive]
+#[allow(clippy::enum_variant_names)]
 /// Everything that can go wrong while using roblox-install.
 pub enum Error {
     #[error("Couldn't find Documents directory")]
     DocumentsDirectoryNotFound,
 
-    #[error("The values of the registry keys used to find Roblox are malformed, maybe your Roblox installation is corrupt?")]
-    MalformedRegistry,
+    #[error("The value of the registry key {0} is malformed, maybe your Roblox installation is corrupt?")]
+    MalformedRegistry(String),
 
     #[error("Your platform is not currently supported")]
     PlatformNotSupported,
@@ -31,8 +73,8 @@ pub enum Error {
     #[error("Couldn't find Plugins directory")]
     PluginsDirectoryNotFound,
 
-    #[error("Couldn't find registry keys, Roblox might not be installed.")]
-    RegistryError(#[source] io::Error),
+    #[error("Couldn't read the registry key {0}, Roblox might not be installed: {1}")]
+    RegistryError(String, #[source] io::Error),
 
     #[error("Environment variable misconfigured: {0}")]
     EnvironmentVariableError(String),
@@ -40,20 +82,591 @@ pub enum Error {
     #[error("Couldn't find Roblox Studio")]
     NotInstalled,
 
+    #[error("{} is not a Roblox Studio installation", .0.display())]
+    NotAnInstallation(PathBuf),
+
+    #[error("The Roblox Studio {0} {} doesn't exist", .1.display())]
+    MissingPart(&'static str, PathBuf),
+
     #[error("Failed to detect WSL environment")]
     WSLDetectionError,
+
+    #[error("Roblox Studio is installed for several Windows users ({0}), select one with ROBLOX_STUDIO_WINDOWS_USER")]
+    AmbiguousWindowsUser(String),
+
+    #[error("Couldn't find Roblox Studio in {}, set ROBLOX_STUDIO_PATH to your Roblox directory (like C:\\Users\\<you>\\AppData\\Local\\Roblox)", .0.display())]
+    WslNotInstalled(PathBuf),
+
+    #[error("WSL interop is disabled, so Roblox Studio can't be started from WSL. Enable it in the [interop] section of /etc/wsl.conf and restart WSL")]
+    WslInteropDisabled,
+
+    #[error("Roblox Studio version {0} is not installed")]
+    VersionNotInstalled(String),
+
+    #[error("No Roblox Studio version from the {0} channel is installed")]
+    ChannelNotInstalled(String),
+
+    #[error("Roblox Studio version {0} couldn't be found on the deployment CDN")]
+    VersionNotDeployed(String),
+
+    #[error("Couldn't read the version of Roblox Studio")]
+    VersionUnknown,
+
+    #[error("Locating Roblox Studio was cancelled")]
+    Cancelled,
+
+    #[error("Couldn't initialize TLS: {0}")]
+    TlsError(#[source] native_tls::Error),
+
+    #[error("Failed to download {0}: {1}")]
+    DownloadError(String, String),
+
+    #[error("Malformed package manifest: {0}")]
+    MalformedManifest(String),
+
+    #[error("Package {0} is corrupt, its checksum doesn't match the manifest")]
+    ChecksumMismatch(String),
+
+    #[error("Failed to install {}: {1}", .0.display())]
+    InstallError(PathBuf, #[source] io::Error),
+}
+
+/// The class of an [`Error`], for callers that handle failures differently
+/// depending on their cause.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// Roblox Studio, or the requested version of it, isn't installed where it
+    /// was looked for.
+    NotInstalled,
+    /// An environment variable, the registry or the system is set up in a way
+    /// that prevents finding or starting Studio.
+    Misconfigured,
+    /// This platform isn't supported.
+    PlatformUnsupported,
+    /// The requested version isn't available from Roblox's deployment CDN.
+    NotDeployed,
+    /// A download failed.
+    Network,
+    /// Downloaded data or an installation is corrupt.
+    Corrupt,
+    /// A file system or registry operation failed.
+    Io,
+    /// The operation was cancelled.
+    Cancelled,
+}
+
+impl Error {
+    /// The class of this error.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Error::NotInstalled
+            | Error::NotAnInstallation(_)
+            | Error::MissingPart(..)
+            | Error::WslNotInstalled(_)
+            | Error::VersionNotInstalled(_)
+            | Error::ChannelNotInstalled(_)
+            | Error::RegistryError(..) => ErrorKind::NotInstalled,
+            Error::EnvironmentVariableError(_)
+            | Error::AmbiguousWindowsUser(_)
+            | Error::WslInteropDisabled
+            | Error::DocumentsDirectoryNotFound
+            | Error::PluginsDirectoryNotFound => ErrorKind::Misconfigured,
+            Error::PlatformNotSupported | Error::WSLDetectionError => ErrorKind::PlatformUnsupported,
+            Error::VersionNotDeployed(_) => ErrorKind::NotDeployed,
+            Error::TlsError(_) | Error::DownloadError(..) => ErrorKind::Network,
+            Error::MalformedRegistry(_)
+            | Error::MalformedManifest(_)
+            | Error::ChecksumMismatch(_)
+            | Error::VersionUnknown => ErrorKind::Corrupt,
+            Error::InstallError(..) => ErrorKind::Io,
+            Error::Cancelled => ErrorKind::Cancelled,
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn is_on_path(program: &str) -> bool {
+    env::var_os("PATH")
+        .is_some_and(|paths| env::split_paths(&paths).any(|path| path.join(program).is_file()))
+}
+
+/// The directory WSL mounts Windows drives in, from the `root` setting of the
+/// `[automount]` section of `/etc/wsl.conf`.
+pub fn wsl_mount_root() -> &'static Path {
+    static MOUNT_ROOT: OnceLock<PathBuf> = OnceLock::new();
+
+    MOUNT_ROOT.get_or_init(|| {
+        let root = fs::read_to_string("/etc/wsl.conf")
+            .ok()
+            .and_then(|config| automount_root(&config));
+
+        match root {
+            Some(root) => {
+                debug!("Windows drives are mounted in {} according to /etc/wsl.conf", root);
+                PathBuf::from(root)
+            }
+            None => PathBuf::from(DEFAULT_WSL_MOUNT_ROOT),
+        }
+    })
+}
+
+fn automount_root(config: &str) -> Option<String> {
+    let mut in_automount = false;
+
+    for line in config.lines() {
+        let line = line.split(['#', ';']).next().unwrap_or_default().trim();
+
+        if let Some(section) = line.strip_prefix('[').and_then(|line| line.strip_suffix(']')) {
+            in_automount = section.trim().eq_ignore_ascii_case("automount");
+        } else if in_automount
+            && let Some((key, value)) = line.split_once('=')
+            && key.trim().eq_ignore_ascii_case("root")
+        {
+            let value = value.trim().trim_matches('"');
+            return (!value.is_empty()).then(|| value.to_owned());
+        }
+    }
+
+    None
+}
+
+/// Text of the first `<name>` element of a small XML document like `AppSettings.xml`,
+/// which only holds a few elements with text in them.
+fn xml_element_text(xml: &str, name: &str) -> Option<String> {
+    let document = roxmltree::Document::parse(xml)
+        .map_err(|err| debug!("Couldn't parse XML: {}", err))
+        .ok()?;
+
+    let element = document.descendants().find(|node| node.has_tag_name(name))?;
+    let text: String = element
+        .descendants()
+        .filter(roxmltree::Node::is_text)
+        .filter_map(|node| node.text())
+        .collect();
+    let text = text.trim();
+
+    (!text.is_empty()).then(|| text.to_owned())
+}
+
+/// The directory a Windows drive is mounted in under WSL, like `/mnt/c`.
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn wsl_drive(letter: char) -> PathBuf {
+    wsl_mount_root().join(letter.to_ascii_lowercase().to_string())
+}
+
+/// Under WSL, translates a Windows path like `C:\Users\me` to where its drive
+/// is mounted, like `/mnt/c/Users/me`, so the same value works in PowerShell and
+/// WSL shells. Other paths are returned unchanged.
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn translate_windows_path(path: PathBuf) -> PathBuf {
+    let Some(path_str) = path.to_str() else {
+        return path;
+    };
+
+    let Some((drive, components)) = split_windows_path(path_str).filter(|_| is_wsl()) else {
+        return path;
+    };
+
+    let translated = components
+        .into_iter()
+        .fold(wsl_drive(drive), |translated, component| translated.join(component));
+
+    debug!("Translated {} to {}", path_str, translated.display());
+    translated
+}
+
+/// Splits an absolute Windows path like `C:\Users\me` into its drive letter and
+/// its components, which can be separated by either slash.
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn split_windows_path(path: &str) -> Option<(char, Vec<&str>)> {
+    let mut chars = path.chars();
+
+    let (Some(drive), Some(':')) = (chars.next(), chars.next()) else {
+        return None;
+    };
+
+    let rest = chars.as_str();
+
+    if !drive.is_ascii_alphabetic() || !(rest.is_empty() || rest.starts_with(['\\', '/'])) {
+        return None;
+    }
+
+    let components = rest
+        .split(['\\', '/'])
+        .filter(|component| !component.is_empty())
+        .collect();
+
+    Some((drive, components))
+}
+
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+fn translate_windows_path(path: PathBuf) -> PathBuf {
+    path
+}
+
+/// Converts a path under WSL into the Windows path it corresponds to. Paths on
+/// a mounted drive, like `/mnt/c/Users`, are converted directly and other paths
+/// go through `wslpath`, which maps them to the distribution's `\\wsl$` share.
+fn windows_path_from_wsl(path: &Path) -> Option<OsString> {
+    if let Ok(rest) = path.strip_prefix(wsl_mount_root())
+        && let Some(rest) = rest.to_str()
+    {
+        let (drive, rest) = rest.split_once('/').unwrap_or((rest, ""));
+
+        if drive.len() == 1 && drive.chars().all(|drive| drive.is_ascii_alphabetic()) {
+            return Some(format!("{}:\\{}", drive.to_ascii_uppercase(), rest.replace('/', "\\")).into());
+        }
+    }
+
+    let output = Command::new("wslpath")
+        .arg("-w")
+        .arg(path)
+        .output()
+        .map_err(|err| debug!("Couldn't run wslpath: {}", err))
+        .ok()?;
+
+    if !output.status.success() {
+        debug!("wslpath failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+        return None;
+    }
+
+    let windows_path = String::from_utf8(output.stdout).ok()?;
+    Some(windows_path.trim_end_matches(['\r', '\n']).into())
+}
+
+/// Splits a list of paths like `PATH`, skipping empty entries. On Windows, and
+/// elsewhere when the list contains a `;`, entries are separated by `;`.
+/// Otherwise they're separated by `:`, except after a drive letter, so that
+/// Windows paths like `C:\Users\me` can be listed under WSL.
+fn split_path_list(list: &str) -> Vec<String> {
+    if cfg!(target_os = "windows") || list.contains(';') {
+        return list
+            .split(';')
+            .filter(|entry| !entry.is_empty())
+            .map(str::to_owned)
+            .collect();
+    }
+
+    let mut entries: Vec<String> = Vec::new();
+    let mut after_drive_letter = false;
+
+    for part in list.split(':') {
+        let continues_drive = after_drive_letter && part.starts_with(['\\', '/']);
+
+        match entries.last_mut() {
+            Some(last) if continues_drive => {
+                last.push(':');
+                last.push_str(part);
+            }
+            _ => entries.push(part.to_owned()),
+        }
+
+        after_drive_letter = !continues_drive
+            && part.len() == 1
+            && part.starts_with(|c: char| c.is_ascii_alphabetic());
+    }
+
+    entries.retain(|entry| !entry.is_empty());
+    entries
+}
+
+/// Runs a command and returns its trimmed output, if it succeeded and printed
+/// something. Windows programs end their lines with `\r\n`.
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|err| debug!("Couldn't run {}: {}", program, err))
+        .ok()?;
+
+    if !output.status.success() {
+        debug!("{} exited with {}", program, output.status);
+        return None;
+    }
+
+    let output = String::from_utf8(output.stdout).ok()?;
+    let output = output.trim();
+
+    (!output.is_empty()).then(|| output.to_owned())
+}
+
+/// Whether WSL can start Windows programs. WSL registers a `binfmt_misc` handler
+/// for them, which is disabled when interop is turned off in `/etc/wsl.conf`.
+pub fn wsl_interop_enabled() -> bool {
+    ["/proc/sys/fs/binfmt_misc/WSLInterop", "/proc/sys/fs/binfmt_misc/WSLInterop-late"]
+        .iter()
+        .filter_map(|handler| fs::read_to_string(handler).ok())
+        .any(|status| status.lines().next() == Some("enabled"))
+}
+
+/// Escapes the characters cmd.exe would interpret in an argument. WSL quotes
+/// arguments containing whitespace when it builds the Windows command line, and
+/// cmd.exe takes those literally, so only the other ones need escaping.
+fn escape_cmd_argument(argument: &OsStr) -> OsString {
+    let argument = argument.to_string_lossy();
+
+    if argument.is_empty() || argument.contains([' ', '\t']) {
+        return argument.into_owned().into();
+    }
+
+    let mut escaped = String::with_capacity(argument.len());
+
+    for character in argument.chars() {
+        if matches!(character, '^' | '&' | '|' | '<' | '>' | '(' | ')' | '%' | '!') {
+            escaped.push('^');
+        }
+
+        escaped.push(character);
+    }
+
+    escaped.into()
+}
+
+/// The version of WSL this is running under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WslVersion {
+    /// WSL1 translates Linux system calls, the distribution shares its network
+    /// and processes with Windows.
+    Wsl1,
+    /// WSL2 runs a Linux kernel in a lightweight VM, with its own network and
+    /// filesystem that Windows sees through `\\wsl.localhost`.
+    Wsl2,
+}
+
+/// Detects whether this is running under WSL, and which version. It's only
+/// detected once, since `locate` can be called repeatedly.
+pub fn wsl_version() -> Option<WslVersion> {
+    static WSL_VERSION: OnceLock<Option<WslVersion>> = OnceLock::new();
+
+    *WSL_VERSION.get_or_init(detect_wsl)
 }
 
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
 fn is_wsl() -> bool {
-    if let Ok(output) = Command::new("uname").arg("-r").output() {
-        if let Ok(output_str) = String::from_utf8(output.stdout) {
-            return output_str.to_lowercase().contains("microsoft") || output_str.to_lowercase().contains("wsl");
+    wsl_version().is_some()
+}
+
+fn detect_wsl() -> Option<WslVersion> {
+    let release = fs::read_to_string("/proc/sys/kernel/osrelease").ok().or_else(|| {
+        let output = Command::new("uname").arg("-r").output().ok()?;
+        String::from_utf8(output.stdout).ok()
+    });
+
+    if let Some(release) = &release {
+        let lowercase = release.to_lowercase();
+
+        // WSL2 kernels are named like `5.15.0-microsoft-standard-WSL2`, and WSL1
+        // reports the Windows build, like `4.4.0-19041-Microsoft`.
+        let version = if lowercase.contains("wsl2") || lowercase.contains("microsoft-standard") {
+            Some(WslVersion::Wsl2)
+        } else if lowercase.contains("microsoft") || lowercase.contains("wsl") {
+            Some(WslVersion::Wsl1)
+        } else {
+            None
+        };
+
+        debug!("Kernel release is {:?}, WSL detected: {:?}", release.trim(), version);
+
+        if version.is_some() {
+            return version;
+        }
+    }
+
+    // The WSL launcher sets this for every process it starts. Only WSL2 can run
+    // a custom kernel that doesn't mention Microsoft.
+    if let Some(distribution) = env::var_os("WSL_DISTRO_NAME") {
+        debug!("Running in the WSL distribution {:?}", distribution);
+        return Some(WslVersion::Wsl2);
+    }
+
+    if release.is_none() {
+        debug!("Couldn't read the kernel release, assuming this is not WSL");
+    }
+
+    None
+}
+
+/// The address programs in the WSL distribution reach Windows at, like for
+/// servers started by Studio plugins. WSL1 shares its network with Windows, and
+/// WSL2 runs behind a virtual network where Windows is the default gateway.
+/// Returns `None` outside of WSL.
+pub fn wsl_windows_host() -> Option<IpAddr> {
+    match wsl_version()? {
+        WslVersion::Wsl1 => Some(Ipv4Addr::LOCALHOST.into()),
+        WslVersion::Wsl2 => {
+            let routes = fs::read_to_string("/proc/net/route").ok()?;
+
+            // Addresses are little-endian hex, like `0100A8C0` for 192.168.0.1.
+            routes.lines().skip(1).find_map(|line| {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+
+                if fields.get(1) != Some(&"00000000") {
+                    return None;
+                }
+
+                let gateway = u32::from_str_radix(fields.get(2)?, 16).ok()?;
+                Some(Ipv4Addr::from(gateway.to_le_bytes()).into())
+            })
+        }
+    }
+}
+
+/// The paths of an installation, for [`RobloxStudio::from_parts`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StudioParts {
+    /// The Roblox Studio executable.
+    pub application: PathBuf,
+    /// The content directory.
+    pub content: PathBuf,
+    /// The built-in plugins directory.
+    pub built_in_plugins: PathBuf,
+    /// The user's plugins directory.
+    pub plugins: PathBuf,
+    /// The installation directory: a version directory, or an app bundle on MacOS.
+    pub root: PathBuf,
+}
+
+/// A problem [`RobloxStudio::validate`] found with an installation.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum ValidationIssue {
+    #[error("The Roblox Studio executable {} is missing", .0.display())]
+    ApplicationMissing(PathBuf),
+
+    #[error("The Roblox Studio executable {} isn't executable", .0.display())]
+    ApplicationNotExecutable(PathBuf),
+
+    #[error("The content directory {} is missing", .0.display())]
+    ContentMissing(PathBuf),
+
+    #[error("The built-in plugins directory {} is missing", .0.display())]
+    BuiltInPluginsMissing(PathBuf),
+}
+
+/// Directories of an installation that have their own accessor, with a
+/// description for diagnostics.
+const SUBDIRECTORIES: [(&str, &str); 5] = [
+    ("extra content", "ExtraContent"),
+    ("platform content", "PlatformContent"),
+    ("Studio fonts", "StudioFonts"),
+    ("SSL certificates", "ssl"),
+    ("shaders", "shaders"),
+];
+
+#[cfg(unix)]
+fn is_executable(metadata: &fs::Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    metadata.permissions().mode() & 0o111 != 0
+}
+
+#[cfg(not(unix))]
+fn is_executable(_metadata: &fs::Metadata) -> bool {
+    true
+}
+
+/// Options for [`RobloxStudio::locate_with`].
+#[derive(Debug, Default, Clone)]
+#[must_use]
+pub struct LocateOptions {
+    path: Option<PathBuf>,
+    ignore_env: bool,
+    version: Option<String>,
+    channel: Option<String>,
+    windows_user: Option<String>,
+}
+
+impl LocateOptions {
+    pub fn new() -> LocateOptions {
+        LocateOptions::default()
+    }
+
+    /// Uses the installation at this path instead of looking into `ROBLOX_STUDIO_PATH`
+    /// and the usual install locations. Like the environment variable, the path can
+    /// be a version directory, the Roblox directory containing `Versions`, or the
+    /// executable itself.
+    pub fn path(mut self, path: impl Into<PathBuf>) -> LocateOptions {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Ignores `ROBLOX_STUDIO_PATH` and the variables overriding individual paths,
+    /// like `ROBLOX_STUDIO_PLUGINS_PATH`, for when they're set but stale.
+    pub fn ignore_env(mut self, ignore_env: bool) -> LocateOptions {
+        self.ignore_env = ignore_env;
+        self
+    }
+
+    /// Selects a specific installed version instead of the current one. The version
+    /// can either be the name of its directory (`version-<hash>`, with or without the
+    /// `version-` prefix) or its product version (like `0.612.0.6120532`, or a prefix
+    /// of it like `0.612`).
+    pub fn version(mut self, version: impl Into<String>) -> LocateOptions {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Selects an installation from a specific deployment channel, like `zcanary`.
+    /// See [`RobloxStudio::channel`] for how the channel of an installation is found.
+    pub fn channel(mut self, channel: impl Into<String>) -> LocateOptions {
+        self.channel = Some(channel.into());
+        self
+    }
+
+    /// Under WSL, uses the installation of this Windows user instead of the current
+    /// one. This takes precedence over `ROBLOX_STUDIO_WINDOWS_USER`.
+    pub fn windows_user(mut self, windows_user: impl Into<String>) -> LocateOptions {
+        self.windows_user = Some(windows_user.into());
+        self
+    }
+
+    fn matches(&self, studio: &RobloxStudio) -> bool {
+        let version_matches = self
+            .version
+            .as_ref()
+            .is_none_or(|version| studio.matches_version(version));
+
+        let channel_matches = self
+            .channel
+            .as_ref()
+            .is_none_or(|channel| studio.channel().eq_ignore_ascii_case(channel));
+
+        version_matches && channel_matches
+    }
+
+    fn not_installed_error(&self) -> Error {
+        match (&self.version, &self.channel) {
+            (Some(version), _) => Error::VersionNotInstalled(version.clone()),
+            (None, Some(channel)) => Error::ChannelNotInstalled(channel.clone()),
+            (None, None) => Error::NotInstalled,
         }
     }
-    false
+}
+
+/// How the Studio executable is started.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(target_os = "windows", target_os = "macos"), allow(dead_code))]
+enum Runner {
+    /// The executable is started directly.
+    #[default]
+    Native,
+    /// The Windows executable is started from WSL, through interop.
+    Interop,
+    /// The executable is started with `wine`, in the given prefix.
+    Wine(PathBuf),
+    /// A launcher like `vinegar run` is started instead. It finds the
+    /// executable by itself and forwards its arguments to Studio.
+    Launcher(Vec<String>),
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[must_use]
 pub struct RobloxStudio {
     content: PathBuf,
@@ -61,172 +674,947 @@ pub struct RobloxStudio {
     built_in_plugins: PathBuf,
     plugins: PathBuf,
     root: PathBuf,
+    runner: Runner,
 }
 
 impl RobloxStudio {
     /// Attempts to find a Roblox Studio installation. It will start by looking up
     /// into the environment variable `ROBLOX_STUDIO_PATH`. If the variable is not
-    /// defined, it will find the usual installation on Windows and MacOS.
+    /// defined, it will find the usual installation on Windows and MacOS, or the
+    /// Vinegar or Grapejuice Wine prefix on Linux.
     ///
     /// On Windows (or WSL), the environment variable can point to a specific version (where
     /// the `RobloxStudioBeta.exe` file and `content` directory are located) or it
     /// can also point to the Roblox directory in AppData (`$APPDATA\Local\Roblox`)
     /// and it will find the latest version by itself.
+    /// It can also point to the executable itself. Several paths can be listed,
+    /// separated like in `PATH`, and the first one holding an installation is used.
+    ///
+    /// Once Studio is found, `ROBLOX_STUDIO_APPLICATION_PATH`,
+    /// `ROBLOX_STUDIO_CONTENT_PATH` and `ROBLOX_STUDIO_PLUGINS_PATH` replace the
+    /// corresponding paths when they're set, for setups where one of them lives
+    /// somewhere unusual (like a plugins folder redirected to OneDrive).
     pub fn locate() -> Result<RobloxStudio> {
-        Self::locate_from_env().unwrap_or_else(Self::locate_target_specific)
+        Self::discover(&LocateOptions::new())
     }
 
-    #[cfg(target_os = "windows")]
-    fn locate_target_specific() -> Result<RobloxStudio> {
-        let hkcu = RegKey::predef(winreg::enums::HKEY_CURRENT_USER);
+    fn discover(options: &LocateOptions) -> Result<RobloxStudio> {
+        let studio = match &options.path {
+            Some(path) => Self::locate_from_directory(path)?,
+            None if options.ignore_env => Self::locate_target_specific(options)?,
+            None => Self::locate_from_env().unwrap_or_else(|| Self::locate_target_specific(options))?,
+        };
 
-        let roblox_studio_reg = hkcu
-            .open_subkey(r"Software\Roblox\RobloxStudio")
-            .map_err(Error::RegistryError)?;
+        let studio = if options.ignore_env {
+            studio
+        } else {
+            studio.with_path_overrides()?
+        };
 
-        let content_folder_value: String = roblox_studio_reg
-            .get_value("ContentFolder")
-            .map_err(Error::RegistryError)?;
+        for (name, directory) in SUBDIRECTORIES {
+            if studio.subdirectory(directory).is_none() {
+                debug!("The {} directory is missing from {}", name, studio.resources_directory().display());
+            }
+        }
 
-        let content_folder_path = PathBuf::from(content_folder_value);
+        Ok(studio)
+    }
 
-        let root = content_folder_path
-            .parent()
-            .ok_or(Error::MalformedRegistry)?
-            .to_path_buf();
+    /// Uses the installation at a path instead of finding it, for tools that manage
+    /// their own copies of Studio. The path can be:
+    ///
+    /// - a version directory, containing `RobloxStudioBeta.exe` and `content`;
+    /// - a Roblox directory containing `Versions`, in which case the current (or
+    ///   most recently installed) version is used;
+    /// - a `RobloxStudio.app` bundle on MacOS;
+    /// - the Studio executable itself.
+    ///
+    /// On Linux, the directory has to be in a Wine prefix, or on a Windows drive
+    /// under WSL. Fails with [`Error::NotAnInstallation`] when the path doesn't
+    /// contain an installation.
+    pub fn locate_from_directory(path: impl AsRef<Path>) -> Result<RobloxStudio> {
+        let path = path.as_ref();
 
-        let plugins = Self::locate_plugins_on_windows()?;
+        debug!("Using the installation at {}", path.display());
 
-        Ok(RobloxStudio {
-            content: content_folder_path,
-            application: root.join("RobloxStudioBeta.exe"),
-            built_in_plugins: root.join("BuiltInPlugins"),
-            plugins,
-            root,
-        })
-    }
+        let root = translate_windows_path(path.to_owned());
 
-    #[cfg(not(target_os = "macos"))]
-    fn locate_plugins_on_windows() -> Result<PathBuf> {
-        let mut plugin_dir = dirs::home_dir().ok_or(Error::PluginsDirectoryNotFound)?;
-        plugin_dir.push("AppData");
-        plugin_dir.push("Local");
-        plugin_dir.push("Roblox");
-        plugin_dir.push("Plugins");
-        Ok(plugin_dir)
-    }
+        if !root.exists() {
+            return Err(Error::NotAnInstallation(path.to_owned()));
+        }
 
-    #[cfg(target_os = "macos")]
-    fn locate_target_specific() -> Result<RobloxStudio> {
-        let mut root = PathBuf::from("/Applications");
-        root.push("RobloxStudio.app");
-        Self::locate_from_directory(root)
+        let studio = Self::locate_in_directory(Self::root_from_executable(root)).map_err(|err| match err {
+            Error::NotInstalled => Error::NotAnInstallation(path.to_owned()),
+            err => err,
+        })?;
+
+        // On MacOS, locating a bundle doesn't look inside it.
+        if !studio.application.is_file() {
+            return Err(Error::NotAnInstallation(path.to_owned()));
+        }
+
+        Ok(studio)
     }
 
-    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
-    #[inline]
-    fn locate_target_specific() -> Result<RobloxStudio> {
-        if is_wsl() {
-            // Default Windows Roblox installation path under WSL
-            let mut root = PathBuf::from("/mnt/c/Users");
-            
-            // Try to get the Windows username from the WSL environment
-            if let Ok(output) = Command::new("cmd.exe").args(&["/C", "echo %USERNAME%"]).output() {
-                if let Ok(username) = String::from_utf8(output.stdout) {
-                    let username = username.trim();
-                    root.push(username);
-                    root.push("AppData");
-                    root.push("Local");
-                    root.push("Roblox");
-                    
-                    return Self::locate_from_windows_directory(root);
+    fn with_path_overrides(mut self) -> Result<RobloxStudio> {
+        let overrides = [
+            (ROBLOX_STUDIO_APPLICATION_PATH_VARIABLE, &mut self.application),
+            (ROBLOX_STUDIO_CONTENT_PATH_VARIABLE, &mut self.content),
+            (ROBLOX_STUDIO_PLUGINS_PATH_VARIABLE, &mut self.plugins),
+        ];
+
+        for (variable, path) in overrides {
+            match env::var(variable) {
+                Ok(value) if !value.is_empty() => {
+                    debug!("Using {} = {}", variable, value);
+                    *path = translate_windows_path(PathBuf::from(value));
+                }
+                Ok(_) | Err(env::VarError::NotPresent) => {}
+                Err(err) => {
+                    return Err(Error::EnvironmentVariableError(format!(
+                        "could not convert environment variable `{}` to path ({})",
+                        variable, err,
+                    )));
                 }
             }
         }
-        Err(Error::PlatformNotSupported)
-    }
 
-    #[cfg(target_os = "windows")]
-    fn locate_from_directory(root: PathBuf) -> Result<RobloxStudio> {
-        Self::locate_from_windows_directory(root)
+        Ok(self)
     }
 
-    #[cfg(not(target_os = "macos"))]
-    fn locate_from_windows_directory(root: PathBuf) -> Result<RobloxStudio> {
-        let content_folder_path = root.join("content");
-        let plugins = Self::locate_plugins_on_windows()?;
-
-        if content_folder_path.is_dir() {
-            Ok(RobloxStudio {
-                content: content_folder_path,
-                application: root.join("RobloxStudioBeta.exe"),
-                built_in_plugins: root.join("BuiltInPlugins"),
-                plugins,
-                root,
-            })
-        } else {
-            let versions = root.join("Versions");
+    /// Creates an installation from its individual paths, like ones read from a
+    /// configuration file, instead of finding it. The executable and installation
+    /// directory have to exist. On Linux, installations in a Wine prefix are
+    /// started with Wine, and under WSL through interop, like located ones.
+    pub fn from_parts(parts: StudioParts) -> Result<RobloxStudio> {
+        if !parts.application.is_file() {
+            return Err(Error::MissingPart("executable", parts.application));
+        }
 
-            if versions.is_dir() {
-                fs::read_dir(&versions)
-                    .map_err(|_| Error::NotInstalled)?
-                    .filter_map(|entry| entry.ok())
-                    .find_map(|entry| {
-                        let version = entry.path();
-                        let application = version.join("RobloxStudioBeta.exe");
-
-                        if application.is_file() {
-                            Some(RobloxStudio {
-                                content: version.join("content"),
-                                application,
-                                built_in_plugins: version.join("BuiltInPlugins"),
-                                plugins: plugins.clone(),
-                                root: version.to_owned(),
-                            })
-                        } else {
-                            None
-                        }
-                    })
-                    .ok_or(Error::NotInstalled)
-            } else {
-                Err(Error::NotInstalled)
-            }
+        if !parts.root.is_dir() {
+            return Err(Error::MissingPart("installation directory", parts.root));
         }
-    }
 
-    #[cfg(target_os = "macos")]
-    fn locate_from_directory(root: PathBuf) -> Result<RobloxStudio> {
-        let contents = root.join("Contents");
-        let application = contents.join("MacOS").join("RobloxStudio");
-        let built_in_plugins = contents.join("Resources").join("BuiltInPlugins");
-        let documents = dirs::document_dir().ok_or(Error::DocumentsDirectoryNotFound)?;
-        let plugins = documents.join("Roblox").join("Plugins");
-        let content = contents.join("Resources").join("content");
+        let runner = Self::runner_for(&parts.root);
 
         Ok(RobloxStudio {
-            content,
-            application,
-            built_in_plugins,
-            plugins,
-            root,
+            content: parts.content,
+            application: parts.application,
+            built_in_plugins: parts.built_in_plugins,
+            plugins: parts.plugins,
+            root: parts.root,
+            runner,
         })
     }
 
     #[cfg(not(any(target_os = "windows", target_os = "macos")))]
-    #[inline]
-    fn locate_from_directory(root: PathBuf) -> Result<RobloxStudio> {
+    fn runner_for(root: &Path) -> Runner {
         if is_wsl() {
-            Self::locate_from_windows_directory(root)
-        } else {
-            Err(Error::PlatformNotSupported)
+            return Runner::Interop;
+        }
+
+        match Self::wine_prefix_of(root) {
+            Some(prefix) => Runner::Wine(prefix),
+            None => Runner::Native,
         }
     }
 
-    #[deprecated(
-        since = "0.2.0",
-        note = "The contents of the studio directory are inconsistent across platforms. \
-        Please use a dedicated method (like application_path) or file a feature request if one does not exist."
-    )]
+    #[cfg(any(target_os = "windows", target_os = "macos"))]
+    fn runner_for(_root: &Path) -> Runner {
+        Runner::Native
+    }
+
+    /// Creates an installation started directly from its executable, without
+    /// checking the paths.
+    pub(crate) fn from_native_paths(
+        content: PathBuf,
+        application: PathBuf,
+        built_in_plugins: PathBuf,
+        plugins: PathBuf,
+        root: PathBuf,
+    ) -> RobloxStudio {
+        RobloxStudio {
+            content,
+            application,
+            built_in_plugins,
+            plugins,
+            root,
+            runner: Runner::Native,
+        }
+    }
+
+    /// Attempts to find a Roblox Studio installation like [`locate`](RobloxStudio::locate),
+    /// then applies the given options to select a specific installation. The
+    /// installation `locate` finds is preferred if it matches, and the most recently
+    /// installed matching version otherwise.
+    pub fn locate_with(options: &LocateOptions) -> Result<RobloxStudio> {
+        let studio = Self::discover(options)?;
+
+        if options.matches(&studio) {
+            return Ok(studio);
+        }
+
+        Self::locate_siblings(studio)
+            .into_iter()
+            .find(|studio| options.matches(studio))
+            .ok_or_else(|| options.not_installed_error())
+    }
+
+    /// Whether this is the given version, either the name of its directory
+    /// (`version-<hash>`, with or without the `version-` prefix) or its product
+    /// version (like `0.612.0.6120532`, or a prefix of it like `0.612`), like
+    /// [`LocateOptions::version`] selects it.
+    pub fn matches_version(&self, version: &str) -> bool {
+        let hash = version.strip_prefix("version-").unwrap_or(version);
+
+        if let Some(own_hash) = self.version_hash()
+            && own_hash["version-".len()..].eq_ignore_ascii_case(hash)
+        {
+            return true;
+        }
+
+        self.version().is_some_and(|product_version| {
+            let product_version = product_version.to_string();
+
+            product_version == version
+                || product_version
+                    .strip_prefix(version)
+                    .is_some_and(|rest| rest.starts_with('.'))
+        })
+    }
+
+    #[cfg(target_os = "windows")]
+    fn locate_target_specific(_options: &LocateOptions) -> Result<RobloxStudio> {
+        let registry_error = match Self::locate_from_registry() {
+            Ok(studio) => return Ok(studio),
+            Err(err) => err,
+        };
+
+        if let Some(studio) = Self::locate_from_protocol_handler() {
+            return Ok(studio);
+        }
+
+        // Without usable registry entries, look for the files where the
+        // bootstrapper puts them.
+        if let Some(local) = dirs::data_local_dir()
+            && let Ok(studio) = Self::locate_from_windows_directory(local.join("Roblox"))
+        {
+            return Ok(studio);
+        }
+
+        for root in Self::per_machine_roots() {
+            if let Ok(studio) = Self::locate_from_windows_directory(root) {
+                return Ok(studio);
+            }
+        }
+
+        Err(registry_error)
+    }
+
+    /// Derives the installation from the command registered for `roblox-studio:`
+    /// links, which points to the Studio executable or the launcher next to it.
+    #[cfg(target_os = "windows")]
+    fn locate_from_protocol_handler() -> Option<RobloxStudio> {
+        let hkcr = RegKey::predef(winreg::enums::HKEY_CLASSES_ROOT);
+
+        debug!(r"Reading HKCR\roblox-studio\shell\open\command");
+
+        let command: String = hkcr
+            .open_subkey(r"roblox-studio\shell\open\command")
+            .and_then(|key| key.get_value(""))
+            .map_err(|err| debug!("Couldn't read the protocol handler: {}", err))
+            .ok()?;
+
+        debug!("Protocol handler command is {}", command);
+
+        let executable = match command.strip_prefix('"') {
+            Some(rest) => rest.split('"').next()?,
+            None => command.split_whitespace().next()?,
+        };
+
+        let directory = Path::new(executable).parent()?;
+
+        if let Ok(studio) = Self::locate_from_windows_directory(directory.to_owned()) {
+            return Some(studio);
+        }
+
+        // The handler can point to a version directory that doesn't contain
+        // Studio anymore, look for other versions next to it.
+        let versions = directory.parent().filter(|versions| versions.ends_with("Versions"))?;
+
+        Self::locate_from_windows_directory(versions.parent()?.to_owned()).ok()
+    }
+
+    /// Roblox directories of installs made for all users, like
+    /// `C:\Program Files (x86)\Roblox`. Windows on ARM has a separate directory
+    /// for native ARM64 programs.
+    #[cfg(target_os = "windows")]
+    fn per_machine_roots() -> Vec<PathBuf> {
+        let mut roots: Vec<PathBuf> = Vec::new();
+
+        for (variable, default) in [
+            ("ProgramFiles(x86)", r"C:\Program Files (x86)"),
+            ("ProgramFiles", r"C:\Program Files"),
+            ("ProgramW6432", r"C:\Program Files"),
+            ("ProgramFiles(Arm)", r"C:\Program Files (Arm)"),
+        ] {
+            let root = env::var_os(variable)
+                .map(PathBuf::from)
+                .unwrap_or_else(|| PathBuf::from(default))
+                .join("Roblox");
+
+            if !roots.contains(&root) {
+                roots.push(root);
+            }
+        }
+
+        roots
+    }
+
+    #[cfg(target_os = "windows")]
+    fn locate_from_registry() -> Result<RobloxStudio> {
+        let mut first_error = None;
+
+        for (hive, hive_name, path) in STUDIO_REGISTRY_KEYS {
+            let key_name = format!(r"{}\{}\ContentFolder", hive_name, path);
+            debug!("Reading {}", key_name);
+
+            let content_folder_value = RegKey::predef(*hive)
+                .open_subkey(path)
+                .and_then(|key| key.get_value::<String, _>("ContentFolder"));
+
+            match content_folder_value {
+                Ok(content_folder_value) => {
+                    debug!("ContentFolder is {}", content_folder_value);
+                    return Self::locate_from_content_folder(PathBuf::from(content_folder_value), key_name);
+                }
+                Err(err) => {
+                    debug!("Couldn't read it: {}", err);
+                    first_error.get_or_insert((key_name, err));
+                }
+            }
+        }
+
+        let (key_name, err) = first_error.expect("no registry keys were probed");
+        Err(Error::RegistryError(key_name, err))
+    }
+
+    #[cfg(target_os = "windows")]
+    fn locate_from_content_folder(content_folder_path: PathBuf, key_name: String) -> Result<RobloxStudio> {
+        let root = content_folder_path
+            .parent()
+            .ok_or(Error::MalformedRegistry(key_name))?
+            .to_path_buf();
+
+        let plugins = Self::locate_plugins_on_windows()?;
+
+        Ok(RobloxStudio {
+            content: content_folder_path,
+            application: root.join("RobloxStudioBeta.exe"),
+            built_in_plugins: root.join("BuiltInPlugins"),
+            plugins,
+            root,
+            runner: Runner::Native,
+        })
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    fn locate_plugins_on_windows() -> Result<PathBuf> {
+        let mut plugin_dir = dirs::home_dir().ok_or(Error::PluginsDirectoryNotFound)?;
+        plugin_dir.push("AppData");
+        plugin_dir.push("Local");
+        plugin_dir.push("Roblox");
+        plugin_dir.push("Plugins");
+        Ok(plugin_dir)
+    }
+
+    #[cfg(target_os = "macos")]
+    fn locate_target_specific(_options: &LocateOptions) -> Result<RobloxStudio> {
+        let mut applications = vec![PathBuf::from("/Applications")];
+
+        if let Some(home) = dirs::home_dir() {
+            applications.push(home.join("Applications"));
+        }
+
+        for directory in &applications {
+            for name in BUNDLE_NAMES {
+                let root = directory.join(name);
+                debug!("Probing {}", root.display());
+
+                if let Ok(studio) = Self::locate_in_directory(root)
+                    && studio.application.is_file()
+                {
+                    return Ok(studio);
+                }
+            }
+        }
+
+        applications
+            .iter()
+            .find_map(|directory| Self::locate_renamed_bundle(directory))
+            .or_else(Self::locate_with_spotlight)
+            .ok_or(Error::NotInstalled)
+    }
+
+    /// Asks Spotlight for Studio bundles, for installs moved somewhere else.
+    #[cfg(target_os = "macos")]
+    fn locate_with_spotlight() -> Option<RobloxStudio> {
+        let query = format!("kMDItemCFBundleIdentifier == '{}'", BUNDLE_IDENTIFIER);
+
+        debug!("Running mdfind {:?}", query);
+
+        let output = Command::new("mdfind")
+            .arg(&query)
+            .output()
+            .map_err(|err| debug!("Couldn't run mdfind: {}", err))
+            .ok()?;
+
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter(|line| !line.is_empty())
+            .filter_map(|bundle| {
+                trace!("Probing {}", bundle);
+                Self::locate_in_directory(PathBuf::from(bundle)).ok()
+            })
+            .find(|studio| studio.application.is_file())
+    }
+
+    /// Finds a Studio bundle the user renamed, by its bundle identifier.
+    #[cfg(target_os = "macos")]
+    fn locate_renamed_bundle(directory: &Path) -> Option<RobloxStudio> {
+        let entries = fs::read_dir(directory)
+            .map_err(|err| debug!("Couldn't read {}: {}", directory.display(), err))
+            .ok()?;
+
+        let mut bundles: Vec<_> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|bundle| bundle.extension().is_some_and(|extension| extension == "app"))
+            .collect();
+
+        bundles.sort();
+
+        bundles
+            .into_iter()
+            .filter(|bundle| {
+                trace!("Probing {}", bundle.display());
+                Self::bundle_identifier(bundle).as_deref() == Some(BUNDLE_IDENTIFIER)
+            })
+            .filter_map(|bundle| Self::locate_in_directory(bundle).ok())
+            .find(|studio| studio.application.is_file())
+    }
+
+    #[cfg(target_os = "macos")]
+    fn bundle_identifier(bundle: &Path) -> Option<String> {
+        let plist = plist::Value::from_file(bundle.join("Contents").join("Info.plist")).ok()?;
+
+        plist
+            .as_dictionary()?
+            .get("CFBundleIdentifier")?
+            .as_string()
+            .map(str::to_owned)
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    #[inline]
+    fn locate_target_specific(options: &LocateOptions) -> Result<RobloxStudio> {
+        if is_wsl() {
+            return Self::locate_on_wsl(options);
+        }
+
+        Self::locate_in_wine().ok_or(Error::NotInstalled)
+    }
+
+    /// Finds the installation of the Windows user selected in the options or with
+    /// `ROBLOX_STUDIO_WINDOWS_USER`, then of the user Windows reports through
+    /// interop, then of the only user with a valid installation.
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    fn locate_on_wsl(options: &LocateOptions) -> Result<RobloxStudio> {
+        let users = wsl_drive('c').join("Users");
+
+        // Default Windows Roblox installation path under WSL
+        let roblox_directory = |username: &str| {
+            users.join(username).join("AppData").join("Local").join("Roblox")
+        };
+
+        let selected = options
+            .windows_user
+            .clone()
+            .or_else(|| env::var(ROBLOX_STUDIO_WINDOWS_USER_VARIABLE).ok());
+
+        if let Some(username) = selected {
+            debug!("Using the installation of the Windows user {:?}", username);
+            return Self::locate_from_wsl_directory(roblox_directory(&username));
+        }
+
+        if let Some(username) = Self::wsl_windows_user() {
+            debug!("Windows user name is {:?}", username);
+
+            match Self::locate_from_wsl_directory(roblox_directory(username)) {
+                Ok(studio) => return Ok(studio),
+                Err(err) => debug!("No installation for {}: {}", username, err),
+            }
+        }
+
+        debug!("Looking for installations of other users in {}", users.display());
+
+        let entries = fs::read_dir(&users).map_err(|err| {
+            debug!("Couldn't read {}: {}", users.display(), err);
+            Error::WslNotInstalled(users.clone())
+        })?;
+
+        let mut studios: Vec<(String, RobloxStudio)> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter_map(|username| {
+                let studio = Self::locate_from_wsl_directory(roblox_directory(&username)).ok()?;
+                Some((username, studio))
+            })
+            .collect();
+
+        match studios.len() {
+            0 => Err(Error::WslNotInstalled(users)),
+            1 => Ok(studios.pop().expect("one installation was found").1),
+            _ => {
+                let mut usernames: Vec<_> = studios.into_iter().map(|(username, _)| username).collect();
+                usernames.sort();
+                Err(Error::AmbiguousWindowsUser(usernames.join(", ")))
+            }
+        }
+    }
+
+    /// Asks Windows for the name of the current user, through interop. This
+    /// spawns Windows processes, so the answer is only looked up once.
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    fn wsl_windows_user() -> Option<&'static str> {
+        static WINDOWS_USER: OnceLock<Option<String>> = OnceLock::new();
+
+        WINDOWS_USER.get_or_init(Self::query_wsl_windows_user).as_deref()
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    fn query_wsl_windows_user() -> Option<String> {
+        if !wsl_interop_enabled() {
+            debug!("WSL interop is disabled, can't ask Windows for the user name");
+            return None;
+        }
+
+        // wslvar comes with wslu, which most distributions ship for WSL.
+        if let Some(profile) = command_output("wslvar", &["USERPROFILE"]) {
+            debug!("USERPROFILE from wslvar is {:?}", profile);
+
+            if let Some(username) = profile.rsplit('\\').next().filter(|name| !name.is_empty()) {
+                return Some(username.to_owned());
+            }
+        }
+
+        // cmd.exe echoes the variable name back when it isn't set.
+        command_output("cmd.exe", &["/C", "echo %USERNAME%"])
+            .filter(|username| !username.contains('%'))
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    fn locate_from_wsl_directory(root: PathBuf) -> Result<RobloxStudio> {
+        let studio = Self::locate_from_windows_directory(root)?;
+        let local = Path::new("AppData").join("Local").join("Roblox");
+
+        // The plugins directory is the Windows user's, not the one in the Linux home.
+        let plugins = studio
+            .root
+            .ancestors()
+            .find(|ancestor| ancestor.ends_with(&local))
+            .map(|roblox| roblox.join("Plugins"))
+            .unwrap_or(studio.plugins);
+
+        Ok(RobloxStudio {
+            plugins,
+            runner: Runner::Interop,
+            ..studio
+        })
+    }
+
+    /// Finds Studio in the Wine prefix from `WINEPREFIX`, or in the prefixes of
+    /// Vinegar (installed natively or as a Flatpak) or Grapejuice, the usual ways
+    /// of running it on Linux.
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    fn locate_in_wine() -> Option<RobloxStudio> {
+        if let Some(prefix) = env::var_os("WINEPREFIX") {
+            debug!("Probing the Wine prefix {:?} from WINEPREFIX", prefix);
+
+            if let Some(studio) = Self::locate_in_prefix(Path::new(&prefix)) {
+                return Some(studio);
+            }
+        }
+
+        let data = dirs::data_dir()?;
+
+        Self::locate_in_vinegar(&data.join("vinegar"), &["vinegar", "run"])
+            .or_else(|| {
+                let flatpak = dirs::home_dir()?
+                    .join(".var")
+                    .join("app")
+                    .join(VINEGAR_FLATPAK)
+                    .join("data")
+                    .join("vinegar");
+
+                Self::locate_in_vinegar(&flatpak, &["flatpak", "run", VINEGAR_FLATPAK, "run"])
+            })
+            .or_else(|| Self::locate_in_grapejuice(&data.join("grapejuice")))
+    }
+
+    /// Vinegar keeps Studio in its own `versions` directory in recent versions,
+    /// and inside its prefix before that. Studio is started through Vinegar when
+    /// it's available, so its Wine build and settings are used.
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    fn locate_in_vinegar(vinegar: &Path, launcher: &[&str]) -> Option<RobloxStudio> {
+        let prefix = [vinegar.join("prefixes").join("studio"), vinegar.join("pfx")]
+            .into_iter()
+            .find(|prefix| {
+                debug!("Probing {}", prefix.display());
+                prefix.is_dir()
+            })?;
+
+        let versions = vinegar.join("versions");
+        debug!("Probing {}", versions.display());
+
+        let studio = match Self::locate_versions(&versions, &Self::wine_plugins_path(&prefix)?)
+            .into_iter()
+            .next()
+        {
+            Some(studio) => studio,
+            None => Self::locate_in_prefix(&prefix)?,
+        };
+
+        let runner = if is_on_path(launcher[0]) {
+            Runner::Launcher(launcher.iter().map(|argument| (*argument).to_owned()).collect())
+        } else {
+            debug!("{} isn't on the PATH, starting Studio with wine", launcher[0]);
+            Runner::Wine(prefix)
+        };
+
+        Some(RobloxStudio { runner, ..studio })
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    fn locate_in_grapejuice(grapejuice: &Path) -> Option<RobloxStudio> {
+        let mut prefixes: Vec<_> = fs::read_dir(grapejuice.join("prefixes"))
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .collect();
+
+        prefixes.sort();
+        prefixes.push(grapejuice.join("wineprefix"));
+
+        prefixes.iter().find_map(|prefix| Self::locate_in_prefix(prefix))
+    }
+
+    /// Finds Studio where the bootstrapper installs it inside a Wine prefix.
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    fn locate_in_prefix(prefix: &Path) -> Option<RobloxStudio> {
+        let drive_c = prefix.join("drive_c");
+        let plugins = Self::wine_plugins_path(prefix)?;
+
+        [
+            plugins.parent()?.to_owned(),
+            drive_c.join("Program Files (x86)").join("Roblox"),
+            drive_c.join("Program Files").join("Roblox"),
+        ]
+        .into_iter()
+        .find_map(|root| Self::locate_from_windows_directory(root).ok())
+        .map(|studio| RobloxStudio {
+            plugins,
+            runner: Runner::Wine(prefix.to_owned()),
+            ..studio
+        })
+    }
+
+    /// The user's plugins directory inside a Wine prefix, in the profile of the
+    /// current user or of the only other user.
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    fn wine_plugins_path(prefix: &Path) -> Option<PathBuf> {
+        let users = prefix.join("drive_c").join("users");
+
+        let user = env::var_os("USER")
+            .map(|user| users.join(user))
+            .filter(|user| user.is_dir())
+            .or_else(|| {
+                fs::read_dir(&users)
+                    .ok()?
+                    .filter_map(|entry| entry.ok())
+                    .map(|entry| entry.path())
+                    .find(|user| user.is_dir() && !user.ends_with("Public"))
+            })?;
+
+        Some(user.join("AppData").join("Local").join("Roblox").join("Plugins"))
+    }
+
+    #[cfg(target_os = "windows")]
+    fn locate_in_directory(root: PathBuf) -> Result<RobloxStudio> {
+        Self::locate_from_windows_directory(root)
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    fn locate_from_windows_directory(root: PathBuf) -> Result<RobloxStudio> {
+        let content_folder_path = root.join("content");
+        let plugins = Self::locate_plugins_on_windows()?;
+
+        debug!("Probing {}", content_folder_path.display());
+
+        // Newer installers don't always ship the content directory at the root
+        // of a version directory, so the executable identifies one too.
+        if content_folder_path.is_dir() || root.join("RobloxStudioBeta.exe").is_file() {
+            Ok(RobloxStudio {
+                content: content_folder_path,
+                application: root.join("RobloxStudioBeta.exe"),
+                built_in_plugins: root.join("BuiltInPlugins"),
+                plugins,
+                root,
+                runner: Runner::Native,
+            })
+        } else {
+            let versions = root.join("Versions");
+
+            debug!("Probing {}", versions.display());
+
+            if versions.is_dir() {
+                let mut studios = Self::locate_versions(&versions, &plugins);
+
+                if let Some(current) = Self::current_version_hash()
+                    && let Some(index) = studios
+                        .iter()
+                        .position(|studio| studio.version_hash() == Some(current.as_str()))
+                {
+                    debug!("Using the current version {} recorded by the bootstrapper", current);
+                    return Ok(studios.swap_remove(index));
+                }
+
+                studios.into_iter().next().ok_or(Error::NotInstalled)
+            } else {
+                Err(Error::NotInstalled)
+            }
+        }
+    }
+
+    /// Reads the channel the bootstrapper recorded for the current version.
+    #[cfg(target_os = "windows")]
+    fn registry_channel(&self) -> Option<String> {
+        let current = Self::current_version_hash()?;
+
+        if self.version_hash() != Some(current.as_str()) {
+            return None;
+        }
+
+        let hkcu = RegKey::predef(winreg::enums::HKEY_CURRENT_USER);
+
+        debug!(r"Reading HKCU\Software\ROBLOX Corporation\Environments\roblox-studio\Channel");
+
+        hkcu.open_subkey(r"Software\ROBLOX Corporation\Environments\roblox-studio\Channel")
+            .and_then(|key| key.get_value::<String, _>("www.roblox.com"))
+            .map_err(|err| debug!("Couldn't read the current channel: {}", err))
+            .ok()
+            .filter(|channel| !channel.is_empty())
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    fn registry_channel(&self) -> Option<String> {
+        None
+    }
+
+    /// Reads the version directory the bootstrapper recorded as the current one.
+    #[cfg(target_os = "windows")]
+    fn current_version_hash() -> Option<String> {
+        let hkcu = RegKey::predef(winreg::enums::HKEY_CURRENT_USER);
+
+        debug!(r"Reading HKCU\Software\ROBLOX Corporation\Environments\roblox-studio\version");
+
+        hkcu.open_subkey(r"Software\ROBLOX Corporation\Environments\roblox-studio")
+            .and_then(|key| key.get_value("version"))
+            .map_err(|err| debug!("Couldn't read the current version: {}", err))
+            .ok()
+    }
+
+    /// The registry isn't readable from WSL, so the most recently installed
+    /// version is used instead.
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    fn current_version_hash() -> Option<String> {
+        None
+    }
+
+    /// Finds every version directory containing a Studio executable, most
+    /// recently installed first.
+    #[cfg(not(target_os = "macos"))]
+    fn locate_versions(versions: &Path, plugins: &Path) -> Vec<RobloxStudio> {
+        let entries = match fs::read_dir(versions) {
+            Ok(entries) => entries,
+            Err(err) => {
+                debug!("Couldn't read {}: {}", versions.display(), err);
+                return Vec::new();
+            }
+        };
+
+        let mut studios: Vec<_> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let version = entry.path();
+                let application = version.join("RobloxStudioBeta.exe");
+
+                trace!("Probing {}", application.display());
+
+                if application.is_file() {
+                    Some(RobloxStudio {
+                        content: version.join("content"),
+                        application,
+                        built_in_plugins: version.join("BuiltInPlugins"),
+                        plugins: plugins.to_owned(),
+                        root: version,
+                        runner: Runner::Native,
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        studios.sort_by_cached_key(|studio| {
+            std::cmp::Reverse(fs::metadata(&studio.application).and_then(|metadata| metadata.modified()).ok())
+        });
+
+        studios
+    }
+
+    #[cfg(target_os = "macos")]
+    fn locate_in_directory(root: PathBuf) -> Result<RobloxStudio> {
+        let contents = root.join("Contents");
+        let application = contents.join("MacOS").join("RobloxStudio");
+        let built_in_plugins = contents.join("Resources").join("BuiltInPlugins");
+        let documents = dirs::document_dir().ok_or(Error::DocumentsDirectoryNotFound)?;
+        let plugins = documents.join("Roblox").join("Plugins");
+        let content = contents.join("Resources").join("content");
+
+        Ok(RobloxStudio {
+            content,
+            application,
+            built_in_plugins,
+            plugins,
+            root,
+            runner: Runner::Native,
+        })
+    }
+
+    /// Finds every installed Roblox Studio version, starting from the installation
+    /// [`locate`](RobloxStudio::locate) would return. On Windows (or WSL), this is
+    /// every version directory next to it in `Versions`, most recently installed
+    /// first. On MacOS, this is every `RobloxStudio*.app` bundle next to it, which
+    /// is how channels other than the live one are installed.
+    ///
+    /// Returns an empty list if no installation could be found.
+    pub fn locate_all() -> Vec<RobloxStudio> {
+        Self::locate_all_with(&LocateOptions::new())
+    }
+
+    /// Like [`locate_all`](RobloxStudio::locate_all), starting from the
+    /// installation found with these options like
+    /// [`locate_with`](RobloxStudio::locate_with) does. Only the versions
+    /// matching the version and channel of the options, when set, are returned.
+    pub fn locate_all_with(options: &LocateOptions) -> Vec<RobloxStudio> {
+        match Self::discover(options) {
+            Ok(studio) => Self::locate_siblings(studio)
+                .into_iter()
+                .filter(|studio| options.matches(studio))
+                .collect(),
+            Err(err) => {
+                debug!("Couldn't locate Roblox Studio: {}", err);
+                Vec::new()
+            }
+        }
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    fn locate_siblings(studio: RobloxStudio) -> Vec<RobloxStudio> {
+        match studio.root.parent() {
+            Some(versions) if versions.file_name().is_some_and(|name| name == "Versions") => {
+                Self::locate_versions(versions, &studio.plugins)
+                    .into_iter()
+                    .map(|sibling| RobloxStudio {
+                        runner: studio.runner.clone(),
+                        ..sibling
+                    })
+                    .collect()
+            }
+            _ => vec![studio],
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    fn locate_siblings(studio: RobloxStudio) -> Vec<RobloxStudio> {
+        let Some(applications) = studio.root.parent() else {
+            return vec![studio];
+        };
+
+        let entries = match fs::read_dir(applications) {
+            Ok(entries) => entries,
+            Err(err) => {
+                debug!("Couldn't read {}: {}", applications.display(), err);
+                return vec![studio];
+            }
+        };
+
+        let mut studios: Vec<_> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|bundle| {
+                bundle
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with("RobloxStudio") && name.ends_with(".app"))
+            })
+            .filter_map(|bundle| Self::locate_in_directory(bundle).ok())
+            .filter(|studio| {
+                trace!("Probing {}", studio.application.display());
+                studio.application.is_file()
+            })
+            .collect();
+
+        studios.sort_by(|a, b| a.root.cmp(&b.root));
+        studios
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    #[inline]
+    fn locate_in_directory(root: PathBuf) -> Result<RobloxStudio> {
+        if is_wsl() {
+            return Self::locate_from_wsl_directory(root);
+        }
+
+        let Some(prefix) = Self::wine_prefix_of(&root) else {
+            return Err(Error::PlatformNotSupported);
+        };
+
+        let studio = Self::locate_from_windows_directory(root)?;
+
+        Ok(RobloxStudio {
+            plugins: Self::wine_plugins_path(&prefix).unwrap_or(studio.plugins),
+            runner: Runner::Wine(prefix),
+            ..studio
+        })
+    }
+
+    /// The Wine prefix containing a path, like `<prefix>/drive_c/users/<user>/AppData/Local/Roblox`.
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    fn wine_prefix_of(path: &Path) -> Option<PathBuf> {
+        path.ancestors()
+            .find(|ancestor| ancestor.ends_with("drive_c"))
+            .and_then(Path::parent)
+            .map(Path::to_owned)
+    }
+
+    #[deprecated(
+        since = "0.2.0",
+        note = "The contents of the studio directory are inconsistent across platforms. \
+        Please use a dedicated method (like application_path) or file a feature request if one does not exist."
+    )]
     #[must_use]
     #[inline]
     pub fn root_path(&self) -> &Path {
@@ -240,6 +1628,247 @@ impl RobloxStudio {
         &self.application
     }
 
+    /// Checks that the parts of this installation Studio needs to start are there,
+    /// returning the problems found. An empty list means the installation looks
+    /// complete; it can still fail to start for other reasons.
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+
+        match fs::metadata(&self.application) {
+            Ok(metadata) if !metadata.is_file() => {
+                issues.push(ValidationIssue::ApplicationMissing(self.application.clone()));
+            }
+            Ok(metadata) => {
+                if matches!(self.runner, Runner::Native) && !is_executable(&metadata) {
+                    issues.push(ValidationIssue::ApplicationNotExecutable(self.application.clone()));
+                }
+            }
+            Err(_) => issues.push(ValidationIssue::ApplicationMissing(self.application.clone())),
+        }
+
+        if !self.content.is_dir() {
+            issues.push(ValidationIssue::ContentMissing(self.content.clone()));
+        }
+
+        if !self.built_in_plugins.is_dir() {
+            issues.push(ValidationIssue::BuiltInPluginsMissing(self.built_in_plugins.clone()));
+        }
+
+        issues
+    }
+
+    /// Checks that this installation of Roblox Studio can be started from here,
+    /// which isn't the case under WSL when interop is disabled.
+    pub fn check_launchable(&self) -> Result<()> {
+        match self.runner {
+            Runner::Interop if !wsl_interop_enabled() => Err(Error::WslInteropDisabled),
+            _ => Ok(()),
+        }
+    }
+
+    /// Creates a command that starts this installation of Roblox Studio, without
+    /// any arguments. Most of the time this runs [`application_path`], but Studio
+    /// can also be started through Wine or a launcher like Vinegar on Linux.
+    ///
+    /// [`application_path`]: RobloxStudio::application_path
+    pub fn command(&self) -> Command {
+        match &self.runner {
+            Runner::Native => Command::new(&self.application),
+            Runner::Interop => {
+                let mut command = Command::new(&self.application);
+
+                // Windows sees a working directory in a WSL2 distribution as a
+                // `\\wsl.localhost` share, which Studio and cmd.exe don't
+                // support. WSL1 translates it by itself.
+                if wsl_version() == Some(WslVersion::Wsl2)
+                    && env::current_dir().is_ok_and(|current| !current.starts_with(wsl_mount_root()))
+                {
+                    command.current_dir(&self.root);
+                }
+
+                command
+            }
+            Runner::Wine(prefix) => {
+                let mut command = Command::new("wine");
+                command.env("WINEPREFIX", prefix).arg(&self.application);
+                command
+            }
+            Runner::Launcher(launcher) => {
+                let mut command = Command::new(&launcher[0]);
+                command.args(&launcher[1..]);
+                command
+            }
+        }
+    }
+
+    /// Creates a command that starts Roblox Studio with the given arguments without
+    /// tying its lifetime to this process. Under WSL, Studio is started through
+    /// `cmd.exe /C start`, otherwise this is [`command`] with the arguments.
+    ///
+    /// [`command`]: RobloxStudio::command
+    pub fn detached_command<I, S>(&self, args: I) -> Command
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        let Runner::Interop = self.runner else {
+            let mut command = self.command();
+            command.args(args);
+            return command;
+        };
+
+        let application = windows_path_from_wsl(&self.application)
+            .unwrap_or_else(|| self.application.clone().into_os_string());
+
+        let mut command = Command::new("cmd.exe");
+
+        // cmd.exe refuses to start in a `\\wsl.localhost` working directory.
+        command.current_dir(&self.root);
+
+        // The first quoted argument of `start` is the window title.
+        command
+            .args(["/C", "start", "Roblox Studio"])
+            .arg(escape_cmd_argument(&application))
+            .args(args.into_iter().map(|arg| escape_cmd_argument(arg.as_ref())));
+
+        command
+    }
+
+    /// Creates a command that opens a place file through the file association
+    /// Studio registers, which hands it to a running instance of Studio when
+    /// there's one: with `start` on Windows (and through `cmd.exe` under WSL),
+    /// `wine start` in the Wine prefix, and `open` on MacOS. Returns `None` when
+    /// Studio runs through a launcher, which doesn't register one.
+    pub fn open_file_command(&self, place: &Path) -> Option<Command> {
+        match &self.runner {
+            #[cfg(target_os = "macos")]
+            Runner::Native => {
+                let mut command = Command::new("open");
+                command.arg("-a").arg(&self.root).arg(place);
+                Some(command)
+            }
+            #[cfg(not(target_os = "macos"))]
+            Runner::Native => {
+                // The first quoted argument of `start` is the window title.
+                let mut command = Command::new("cmd");
+                command.args(["/C", "start", "Roblox Studio"]).arg(place);
+                Some(command)
+            }
+            Runner::Interop => {
+                let place = windows_path_from_wsl(place).unwrap_or_else(|| place.as_os_str().to_owned());
+
+                let mut command = Command::new("cmd.exe");
+                command
+                    .current_dir(&self.root)
+                    .args(["/C", "start", "Roblox Studio"])
+                    .arg(escape_cmd_argument(&place));
+                Some(command)
+            }
+            Runner::Wine(prefix) => {
+                let mut command = Command::new("wine");
+                command.env("WINEPREFIX", prefix).args(["start", "/unix"]).arg(place);
+                Some(command)
+            }
+            Runner::Launcher(_) => None,
+        }
+    }
+
+    /// Creates a command that opens a URL, like a `roblox-studio:` deep link,
+    /// through the protocol handler Studio registers, which hands it to a running
+    /// instance of Studio when there's one, like [`open_file_command`]. Returns
+    /// `None` when Studio runs through a launcher.
+    ///
+    /// [`open_file_command`]: RobloxStudio::open_file_command
+    pub fn open_url_command(&self, url: &str) -> Option<Command> {
+        match &self.runner {
+            #[cfg(target_os = "macos")]
+            Runner::Native => {
+                let mut command = Command::new("open");
+                command.arg("-a").arg(&self.root).arg(url);
+                Some(command)
+            }
+            #[cfg(not(target_os = "macos"))]
+            Runner::Native => {
+                let mut command = Command::new("cmd");
+                command.args(["/C", "start", "Roblox Studio", url]);
+                Some(command)
+            }
+            Runner::Interop => {
+                let mut command = Command::new("cmd.exe");
+                command
+                    .current_dir(&self.root)
+                    .args(["/C", "start", "Roblox Studio"])
+                    .arg(escape_cmd_argument(OsStr::new(url)));
+                Some(command)
+            }
+            Runner::Wine(prefix) => {
+                let mut command = Command::new("wine");
+                command.env("WINEPREFIX", prefix).args(["start", url]);
+                Some(command)
+            }
+            Runner::Launcher(_) => None,
+        }
+    }
+
+    /// Creates a command that opens a directory, like one of the installation's,
+    /// in the file manager: Explorer on Windows (and under WSL, with the
+    /// directory's Windows path), Finder on MacOS, and the desktop's file
+    /// manager through `xdg-open` on Linux. Explorer exits with 1 even when it
+    /// opened the directory, so its exit code can't be relied on.
+    pub fn open_folder_command(&self, directory: &Path) -> Command {
+        if self.runs_through_interop() {
+            let directory = windows_path_from_wsl(directory).unwrap_or_else(|| directory.as_os_str().to_owned());
+
+            let mut command = Command::new("explorer.exe");
+            command.arg(directory);
+            return command;
+        }
+
+        let program = if cfg!(target_os = "windows") {
+            "explorer"
+        } else if cfg!(target_os = "macos") {
+            "open"
+        } else {
+            "xdg-open"
+        };
+
+        let mut command = Command::new(program);
+        command.arg(directory);
+        command
+    }
+
+    /// Whether Studio is a Windows program started from WSL.
+    pub(crate) fn runs_through_interop(&self) -> bool {
+        matches!(self.runner, Runner::Interop)
+    }
+
+    /// The Wine prefix Studio runs in, if it runs through Wine.
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    pub(crate) fn wine_prefix(&self) -> Option<&Path> {
+        match &self.runner {
+            Runner::Wine(prefix) => Some(prefix),
+            _ => None,
+        }
+    }
+
+    /// Converts a path on this machine into one Roblox Studio understands, like a
+    /// place file to open. When Studio runs through Wine, paths are converted to
+    /// Wine's `Z:` drive. Under WSL, they're converted to Windows paths, like
+    /// `wslpath -w` does.
+    pub fn studio_path(&self, path: &Path) -> OsString {
+        match self.runner {
+            Runner::Native => path.as_os_str().to_owned(),
+            Runner::Interop => {
+                let path = path::absolute(path).unwrap_or_else(|_| path.to_owned());
+                windows_path_from_wsl(&path).unwrap_or_else(|| path.into_os_string())
+            }
+            Runner::Wine(_) | Runner::Launcher(_) => {
+                let path = path::absolute(path).unwrap_or_else(|_| path.to_owned());
+                format!("Z:{}", path.to_string_lossy().replace('/', "\\")).into()
+            }
+        }
+    }
+
     #[must_use]
     #[inline]
     /// Path to the content directory
@@ -269,19 +1898,509 @@ impl RobloxStudio {
         &self.plugins
     }
 
-    fn locate_from_env() -> Option<Result<RobloxStudio>> {
-        let variable_value = env::var(ROBLOX_STUDIO_PATH_VARIABLE).ok()?;
+    /// Path to the `ClientSettings` directory of this version, where Studio reads FFlag
+    /// overrides from its `ClientAppSettings.json` file. Studio doesn't create it,
+    /// so it's created if it doesn't exist yet.
+    pub fn client_settings_path(&self) -> io::Result<PathBuf> {
+        let directory = self.client_settings_directory();
 
-        let result = variable_value
-            .parse()
-            .map_err(|error| {
-                Error::EnvironmentVariableError(format!(
-                    "could not convert environment variable `{}` to path ({})",
-                    ROBLOX_STUDIO_PATH_VARIABLE, error,
-                ))
+        if !directory.is_dir() {
+            debug!("Creating {}", directory.display());
+            fs::create_dir_all(&directory)?;
+        }
+
+        Ok(directory)
+    }
+
+    fn client_settings_directory(&self) -> PathBuf {
+        #[cfg(target_os = "macos")]
+        return self.root.join("Contents").join("MacOS").join("ClientSettings");
+
+        #[cfg(not(target_os = "macos"))]
+        return self.root.join("ClientSettings");
+    }
+
+    #[must_use]
+    /// Path to the `ExtraContent` directory (textures, models and translations loaded
+    /// on demand). Returns `None` when the installation doesn't have it.
+    pub fn extra_content_path(&self) -> Option<PathBuf> {
+        self.subdirectory("ExtraContent")
+    }
+
+    #[must_use]
+    /// Path to the `PlatformContent` directory (platform-specific textures and
+    /// terrain). Returns `None` when the installation doesn't have it.
+    pub fn platform_content_path(&self) -> Option<PathBuf> {
+        self.subdirectory("PlatformContent")
+    }
+
+    #[must_use]
+    /// Path to the `StudioFonts` directory (fonts used by Studio's own interface).
+    /// Returns `None` when the installation doesn't have it.
+    pub fn studio_fonts_path(&self) -> Option<PathBuf> {
+        self.subdirectory("StudioFonts")
+    }
+
+    #[must_use]
+    /// Path to the `ssl` directory (the CA certificates Studio trusts). Returns `None`
+    /// when the installation doesn't have it.
+    pub fn ssl_path(&self) -> Option<PathBuf> {
+        self.subdirectory("ssl")
+    }
+
+    #[must_use]
+    /// Path to the `shaders` directory (compiled shader packs). Returns `None` when
+    /// the installation doesn't have it.
+    pub fn shaders_path(&self) -> Option<PathBuf> {
+        self.subdirectory("shaders")
+    }
+
+    #[must_use]
+    /// Path to `ReflectionMetadata.xml`, which holds the explorer order, icons and
+    /// descriptions of classes. Returns `None` when the installation doesn't have it.
+    pub fn reflection_metadata_path(&self) -> Option<PathBuf> {
+        Some(self.resources_directory().join("ReflectionMetadata.xml")).filter(|path| path.is_file())
+    }
+
+    /// The directory holding the installation's resources, next to the executable
+    /// on Windows and in `Contents/Resources` on MacOS.
+    fn resources_directory(&self) -> PathBuf {
+        #[cfg(target_os = "macos")]
+        return self.root.join("Contents").join("Resources");
+
+        #[cfg(not(target_os = "macos"))]
+        return self.root.clone();
+    }
+
+    fn subdirectory(&self, name: &str) -> Option<PathBuf> {
+        Some(self.resources_directory().join(name)).filter(|directory| directory.is_dir())
+    }
+
+    #[must_use]
+    /// Path to the directory Roblox Studio writes its logs to: `%LOCALAPPDATA%\Roblox\logs`
+    /// on Windows (in the Wine prefix or on the Windows drive on Linux), and
+    /// `~/Library/Logs/Roblox` on MacOS. Returns `None` when it can't be told from
+    /// where the installation is.
+    pub fn logs_path(&self) -> Option<PathBuf> {
+        #[cfg(target_os = "macos")]
+        return Some(dirs::home_dir()?.join("Library").join("Logs").join("Roblox"));
+
+        #[cfg(not(target_os = "macos"))]
+        return Some(self.local_roblox_directory()?.join("logs"));
+    }
+
+    #[must_use]
+    /// Path to the per-user directory Roblox Studio keeps its state in, like its settings
+    /// (`GlobalSettings_13.xml`), `LocalStorage` and downloaded assets:
+    /// `%LOCALAPPDATA%\Roblox` on Windows (in the Wine prefix or on the Windows drive on
+    /// Linux), and `~/Library/Roblox` on MacOS. Returns `None` when it can't be told from
+    /// where the installation is.
+    pub fn studio_app_data_path(&self) -> Option<PathBuf> {
+        #[cfg(target_os = "macos")]
+        return Some(dirs::home_dir()?.join("Library").join("Roblox"));
+
+        #[cfg(not(target_os = "macos"))]
+        return self.local_roblox_directory();
+    }
+
+    /// Creates the user's plugins directory if it doesn't exist yet, which is the case
+    /// until Studio's `Plugins Folder` button is used, and returns its path. It's
+    /// created readable by everyone and writable by the user only, like Studio does.
+    pub fn ensure_plugins_dir(&self) -> io::Result<&Path> {
+        if !self.plugins.is_dir() {
+            debug!("Creating the plugins directory {}", self.plugins.display());
+
+            let mut builder = fs::DirBuilder::new();
+            builder.recursive(true);
+
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::DirBuilderExt;
+
+                builder.mode(0o755);
+            }
+
+            builder.create(&self.plugins)?;
+        }
+
+        Ok(&self.plugins)
+    }
+
+    #[must_use]
+    /// Path to the `InstalledPlugins` directory holding the plugins installed from the
+    /// marketplace through Studio, with one directory per plugin named after its asset
+    /// ID. Unlike local plugins in [`plugins_path`](RobloxStudio::plugins_path), they're
+    /// kept per Roblox account, in a directory named after the user ID next to the local
+    /// plugins directory. When several accounts have used Studio, the one whose plugins
+    /// changed last is picked. Returns `None` when no plugin was ever installed this way.
+    pub fn managed_plugins_path(&self) -> Option<PathBuf> {
+        #[cfg(target_os = "macos")]
+        let directory = self.documents_directory()?.join("Roblox");
+
+        #[cfg(not(target_os = "macos"))]
+        let directory = self.local_roblox_directory()?;
+
+        fs::read_dir(directory)
+            .ok()?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                let name = entry.file_name();
+                let name = name.to_string_lossy();
+                !name.is_empty() && name.bytes().all(|byte| byte.is_ascii_digit())
             })
-            .and_then(Self::locate_from_directory);
+            .map(|entry| entry.path().join("InstalledPlugins"))
+            .filter_map(|path| {
+                let modified = fs::metadata(&path).ok()?.modified().ok()?;
+                Some((modified, path))
+            })
+            .max_by_key(|(modified, _)| *modified)
+            .map(|(_, path)| path)
+    }
+
+    #[must_use]
+    /// Path to Roblox's HTTP cache, where Studio keeps the assets it downloaded:
+    /// `%TEMP%\Roblox\http` on Windows (in the Wine prefix or on the Windows drive on
+    /// Linux), and `$TMPDIR/Roblox/http` on MacOS. Returns `None` when it can't be told
+    /// from where the installation is. See [`http_cache`](crate::http_cache) to list
+    /// and clear it.
+    pub fn http_cache_path(&self) -> Option<PathBuf> {
+        Some(self.temp_directory()?.join("Roblox").join("http"))
+    }
+
+    #[cfg(any(target_os = "windows", target_os = "macos"))]
+    fn temp_directory(&self) -> Option<PathBuf> {
+        Some(env::temp_dir())
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    fn temp_directory(&self) -> Option<PathBuf> {
+        Some(self.local_roblox_directory()?.parent()?.join("Temp"))
+    }
+
+    #[must_use]
+    /// Path to the directory Roblox Studio saves crash-recovery copies of open places to:
+    /// `Documents\Roblox\AutoSaves` on Windows (in the Wine prefix or on the Windows drive
+    /// on Linux), and `~/Documents/Roblox/AutoSaves` on MacOS. Returns `None` when it
+    /// can't be told from where the installation is. The directory only exists once
+    /// Studio has auto-saved a place.
+    pub fn auto_saves_path(&self) -> Option<PathBuf> {
+        Some(self.documents_directory()?.join("Roblox").join("AutoSaves"))
+    }
+
+    /// The user's `Documents` directory, as seen by Studio.
+    #[cfg(any(target_os = "windows", target_os = "macos"))]
+    fn documents_directory(&self) -> Option<PathBuf> {
+        dirs::document_dir()
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    fn documents_directory(&self) -> Option<PathBuf> {
+        // `AppData\Local\Roblox` is three levels below the Windows user's directory.
+        let user = self.local_roblox_directory()?.ancestors().nth(3)?.to_owned();
+
+        Some(user.join("Documents"))
+    }
+
+    /// The user's `AppData\Local\Roblox` directory, where Studio keeps its logs,
+    /// plugins and caches.
+    #[cfg(target_os = "windows")]
+    fn local_roblox_directory(&self) -> Option<PathBuf> {
+        Some(dirs::data_local_dir()?.join("Roblox"))
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    fn local_roblox_directory(&self) -> Option<PathBuf> {
+        let local = Path::new("AppData").join("Local").join("Roblox");
+
+        match &self.runner {
+            Runner::Wine(prefix) => Some(Self::wine_plugins_path(prefix)?.parent()?.to_owned()),
+            Runner::Launcher(_) => self
+                .plugins
+                .parent()
+                .filter(|directory| directory.ends_with(&local))
+                .map(Path::to_owned),
+            Runner::Interop => self
+                .root
+                .ancestors()
+                .find(|ancestor| ancestor.ends_with(&local))
+                .map(Path::to_owned),
+            Runner::Native => None,
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    /// Name of the `version-<hash>` directory this installation lives in. Returns
+    /// `None` for installations that aren't versioned this way, like on MacOS.
+    pub fn version_hash(&self) -> Option<&str> {
+        self.root
+            .file_name()?
+            .to_str()
+            .filter(|name| name.starts_with("version-"))
+    }
+
+    #[must_use]
+    /// The deployment channel this installation comes from, like `LIVE` or `zcanary`.
+    /// It is read from the `channel.txt` file recorded by [`deploy::install`], or from
+    /// the channel the Windows bootstrapper recorded for the current version, and
+    /// defaults to `LIVE`.
+    ///
+    /// [`deploy::install`]: crate::deploy::install
+    pub fn channel(&self) -> String {
+        if let Ok(channel) = fs::read_to_string(self.root.join(CHANNEL_FILE)) {
+            let channel = channel.trim();
+
+            if !channel.is_empty() {
+                return channel.to_owned();
+            }
+        }
+
+        self.registry_channel()
+            .unwrap_or_else(|| LIVE_CHANNEL.to_owned())
+    }
+
+    #[must_use]
+    /// The Roblox site this installation talks to, like `http://www.roblox.com`, read
+    /// from the `BaseUrl` setting of its `AppSettings.xml`. Builds for test sites point
+    /// somewhere else, like `https://www.sitetest1.robloxlabs.com`. Returns `None` when
+    /// the file or the setting is missing.
+    pub fn base_url(&self) -> Option<String> {
+        let path = self.resources_directory().join("AppSettings.xml");
+        let settings = fs::read_to_string(&path)
+            .map_err(|err| debug!("Couldn't read {}: {}", path.display(), err))
+            .ok()?;
+
+        xml_element_text(&settings, "BaseUrl")
+    }
+
+    #[must_use]
+    /// The product version of this installation, like `0.612.0.6120532`. On Windows
+    /// (or WSL), it is read from the version resource of `RobloxStudioBeta.exe`, and
+    /// on MacOS from the bundle's `Info.plist`.
+    pub fn version(&self) -> Option<StudioVersion> {
+        let version = self.read_product_version()?;
+
+        version
+            .parse()
+            .map_err(|err| debug!("{}", err))
+            .ok()
+    }
 
-        Some(result)
+    #[cfg(not(target_os = "macos"))]
+    fn read_product_version(&self) -> Option<String> {
+        let map = pelite::FileMap::open(&self.application)
+            .map_err(|err| debug!("Couldn't read {}: {}", self.application.display(), err))
+            .ok()?;
+        let file = pelite::PeFile::from_bytes(&map).ok()?;
+        let version_info = file.resources().ok()?.version_info().ok()?;
+
+        version_info
+            .translation()
+            .iter()
+            .find_map(|&language| version_info.value(language, "ProductVersion"))
+            .or_else(|| version_info.fixed().map(|fixed| fixed.dwProductVersion.to_string()))
+    }
+
+    #[cfg(target_os = "macos")]
+    fn read_product_version(&self) -> Option<String> {
+        let info = self.root.join("Contents").join("Info.plist");
+        let plist = plist::Value::from_file(&info)
+            .map_err(|err| debug!("Couldn't read {}: {}", info.display(), err))
+            .ok()?;
+        let dictionary = plist.as_dictionary()?;
+
+        dictionary
+            .get("CFBundleShortVersionString")
+            .or_else(|| dictionary.get("CFBundleVersion"))?
+            .as_string()
+            .map(str::to_owned)
+    }
+
+    fn locate_from_env() -> Option<Result<RobloxStudio>> {
+        let variable_value = match env::var(ROBLOX_STUDIO_PATH_VARIABLE) {
+            Ok(value) => value,
+            Err(err) => {
+                debug!("Not using {}: {}", ROBLOX_STUDIO_PATH_VARIABLE, err);
+                return None;
+            }
+        };
+
+        debug!("Using {} = {}", ROBLOX_STUDIO_PATH_VARIABLE, variable_value);
+
+        let mut first_error = None;
+
+        for candidate in split_path_list(&variable_value) {
+            let result = candidate
+                .parse()
+                .map_err(|error| {
+                    Error::EnvironmentVariableError(format!(
+                        "could not convert environment variable `{}` to path ({})",
+                        ROBLOX_STUDIO_PATH_VARIABLE, error,
+                    ))
+                })
+                .map(translate_windows_path)
+                .map(Self::root_from_executable)
+                .and_then(Self::locate_in_directory);
+
+            match result {
+                Ok(studio) => return Some(Ok(studio)),
+                Err(err) => {
+                    debug!("Couldn't use {}: {}", candidate, err);
+                    first_error.get_or_insert(err);
+                }
+            }
+        }
+
+        Some(Err(first_error.unwrap_or_else(|| {
+            Error::EnvironmentVariableError(format!("`{}` is empty", ROBLOX_STUDIO_PATH_VARIABLE))
+        })))
+    }
+
+    /// Accepts the path of the executable in place of the directory it's
+    /// installed in: the version directory containing `RobloxStudioBeta.exe`, or
+    /// the bundle containing `Contents/MacOS/RobloxStudio`.
+    fn root_from_executable(path: PathBuf) -> PathBuf {
+        if !path.is_file() {
+            return path;
+        }
+
+        let levels = if cfg!(target_os = "macos") { 3 } else { 1 };
+
+        match path.ancestors().nth(levels) {
+            Some(root) => {
+                debug!("{} is a file, using {}", path.display(), root.display());
+                root.to_owned()
+            }
+            None => path,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn xml_element_text_reads_app_settings() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Settings>
+	<ContentFolder>content</ContentFolder>
+	<BaseUrl>http://www.roblox.com</BaseUrl>
+</Settings>"#;
+
+        assert_eq!(xml_element_text(xml, "BaseUrl").as_deref(), Some("http://www.roblox.com"));
+        assert_eq!(xml_element_text(xml, "ContentFolder").as_deref(), Some("content"));
+        assert_eq!(xml_element_text(xml, "Missing"), None);
+    }
+
+    #[test]
+    fn xml_element_text_handles_attributes_entities_and_cdata() {
+        let xml = r#"<Settings>
+	<BaseUrl kind="web"> https://www.roblox.com/?a=1&amp;b=2 </BaseUrl>
+	<Other><![CDATA[<raw>]]></Other>
+	<Empty/>
+</Settings>"#;
+
+        assert_eq!(
+            xml_element_text(xml, "BaseUrl").as_deref(),
+            Some("https://www.roblox.com/?a=1&b=2")
+        );
+        assert_eq!(xml_element_text(xml, "Other").as_deref(), Some("<raw>"));
+        assert_eq!(xml_element_text(xml, "Empty"), None);
+    }
+
+    #[test]
+    fn xml_element_text_rejects_malformed_xml() {
+        assert_eq!(xml_element_text("<BaseUrl>http://www.roblox.com", "BaseUrl"), None);
+    }
+
+    #[test]
+    fn automount_root_reads_the_automount_section() {
+        let config = "[boot]\nroot = /boot\n\n[automount]\nenabled = true\nroot = \"/windir/\" # drives\n";
+        assert_eq!(automount_root(config).as_deref(), Some("/windir/"));
+
+        assert_eq!(automount_root("[Automount]\nRoot=/mnt/host ; comment\n").as_deref(), Some("/mnt/host"));
+    }
+
+    #[test]
+    fn automount_root_ignores_other_sections_and_comments() {
+        assert_eq!(automount_root("[boot]\nroot = /boot\n"), None);
+        assert_eq!(automount_root("[automount]\n# root = /windir/\n"), None);
+        assert_eq!(automount_root("[automount]\nroot = \"\"\n"), None);
+        assert_eq!(automount_root(""), None);
+    }
+
+    #[test]
+    fn split_path_list_splits_on_semicolons() {
+        assert_eq!(
+            split_path_list(r"C:\Roblox;;D:\Other Roblox;"),
+            [r"C:\Roblox", r"D:\Other Roblox"]
+        );
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn split_path_list_keeps_drive_letters() {
+        assert_eq!(split_path_list("/opt/roblox::/home/me/roblox"), ["/opt/roblox", "/home/me/roblox"]);
+        assert_eq!(
+            split_path_list(r"C:\Roblox:/opt/roblox:D:/Roblox"),
+            [r"C:\Roblox", "/opt/roblox", "D:/Roblox"]
+        );
+        assert_eq!(split_path_list("a:b"), ["a", "b"]);
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    #[test]
+    fn splits_windows_paths() {
+        assert_eq!(
+            split_windows_path(r"C:\Users\me\AppData"),
+            Some(('C', vec!["Users", "me", "AppData"]))
+        );
+        assert_eq!(split_windows_path("d:/Roblox//Versions/"), Some(('d', vec!["Roblox", "Versions"])));
+        assert_eq!(split_windows_path("E:"), Some(('E', vec![])));
+
+        for path in ["/mnt/c/Users", "C:Users", "1:\\Users", "Roblox", "", r"\\server\share"] {
+            assert_eq!(split_windows_path(path), None, "{:?}", path);
+        }
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    #[test]
+    fn translate_windows_path_keeps_other_paths() {
+        for path in ["/opt/roblox/RobloxStudioBeta.exe", "Versions/version-1a2b3c4d5e6f4a7b"] {
+            assert_eq!(translate_windows_path(PathBuf::from(path)), PathBuf::from(path));
+        }
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    #[test]
+    fn windows_path_from_wsl_converts_mounted_drives() {
+        let root = wsl_mount_root();
+
+        assert_eq!(
+            windows_path_from_wsl(&root.join("c").join("Users").join("me").join("Place.rbxl")),
+            Some(OsString::from(r"C:\Users\me\Place.rbxl"))
+        );
+        assert_eq!(windows_path_from_wsl(&root.join("d")), Some(OsString::from(r"D:\")));
+    }
+
+    #[test]
+    fn escapes_cmd_arguments() {
+        let escape = |argument: &str| escape_cmd_argument(OsStr::new(argument));
+
+        assert_eq!(escape(r"C:\Roblox\Place.rbxl"), OsString::from(r"C:\Roblox\Place.rbxl"));
+        assert_eq!(
+            escape("https://www.roblox.com/games/1818?a=1&b=%20"),
+            OsString::from("https://www.roblox.com/games/1818?a=1^&b=^%20")
+        );
+        assert_eq!(escape("a|b<c>d(e)f^g!h"), OsString::from("a^|b^<c^>d^(e^)f^^g^!h"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn does_not_escape_quoted_cmd_arguments() {
+        let escape = |argument: &str| escape_cmd_argument(OsStr::new(argument));
+
+        assert_eq!(escape(r"C:\My Places\A & B.rbxl"), OsString::from(r"C:\My Places\A & B.rbxl"));
+        assert_eq!(escape(""), OsString::new());
+    }
+}
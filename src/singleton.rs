@@ -0,0 +1,83 @@
+//! Works around Roblox's single instance check, so a second Studio can be
+//! started next to a running one.
+
+use std::io;
+
+use tracing::warn;
+
+/// Holds Roblox's singleton mutex while a new instance starts. Roblox
+/// processes create it to find out whether another one is already running, and
+/// the ones that can't own it assume they're not the first. Holding it first
+/// makes every instance see the same thing, so none of them defers to another.
+/// It's released when dropped.
+///
+/// A running Studio that owns the mutex keeps it, so it can't be held then, and
+/// `hold` warns and returns `None`, like it does on other platforms.
+pub struct SingletonGuard {
+	#[cfg(target_os = "windows")]
+	handle: windows_sys::Win32::Foundation::HANDLE,
+}
+
+/// Name of the mutex Roblox processes check for a running instance.
+#[cfg(target_os = "windows")]
+const SINGLETON_MUTEX: &str = "ROBLOX_singletonEvent";
+
+#[cfg(target_os = "windows")]
+pub fn hold() -> io::Result<Option<SingletonGuard>> {
+	use std::ptr;
+
+	use windows_sys::Win32::Foundation::{ERROR_ALREADY_EXISTS, GetLastError, WAIT_ABANDONED, WAIT_OBJECT_0};
+	use windows_sys::Win32::System::Threading::{CreateMutexW, WaitForSingleObject};
+
+	let name: Vec<u16> = SINGLETON_MUTEX.encode_utf16().chain([0]).collect();
+
+	// SAFETY: `name` is a null-terminated UTF-16 string that outlives the call.
+	let handle = unsafe { CreateMutexW(ptr::null(), 1, name.as_ptr()) };
+
+	if handle.is_null() {
+		return Err(io::Error::last_os_error());
+	}
+
+	let guard = SingletonGuard { handle };
+
+	// SAFETY: GetLastError has no preconditions.
+	if unsafe { GetLastError() } != ERROR_ALREADY_EXISTS {
+		return Ok(Some(guard));
+	}
+
+	// The mutex already existed, so asking to own it when it was created was
+	// ignored. It can still be taken when nothing owns it, like when the
+	// process that created it exited.
+	// SAFETY: the handle was returned by CreateMutexW and is still open.
+	match unsafe { WaitForSingleObject(guard.handle, 0) } {
+		WAIT_OBJECT_0 | WAIT_ABANDONED => Ok(Some(guard)),
+		_ => {
+			warn!(
+				"A running Roblox Studio holds Roblox's single instance mutex, so the new instance may hand the \
+				 place to it instead of opening it"
+			);
+			Ok(None)
+		}
+	}
+}
+
+/// The mutex can only be created from Windows: Studio running through Wine or
+/// from WSL checks it in a namespace this process can't reach, and Studio on
+/// MacOS doesn't use one.
+#[cfg(not(target_os = "windows"))]
+pub fn hold() -> io::Result<Option<SingletonGuard>> {
+	warn!("--new-instance only holds Roblox's single instance mutex on Windows, so it has no effect here");
+	Ok(None)
+}
+
+#[cfg(target_os = "windows")]
+impl Drop for SingletonGuard {
+	fn drop(&mut self) {
+		use windows_sys::Win32::Foundation::CloseHandle;
+
+		// SAFETY: the handle was returned by CreateMutexW and is only closed here.
+		unsafe {
+			CloseHandle(self.handle);
+		}
+	}
+}
@@ -0,0 +1,97 @@
+use std::{fmt, str::FromStr};
+
+use thiserror::Error;
+
+/// A Roblox Studio product version, like `0.612.0.6120532`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StudioVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+    pub build: u32,
+}
+
+#[derive(Debug, Error)]
+#[error("Invalid Roblox Studio version `{0}`, expected something like 0.612.0.6120532")]
+/// The error returned when parsing a [`StudioVersion`] fails.
+pub struct ParseStudioVersionError(String);
+
+impl FromStr for StudioVersion {
+    type Err = ParseStudioVersionError;
+
+    /// Parses a version with four dot-separated components. The comma-separated
+    /// format used by some Windows version resources (`0, 612, 0, 6120532`) is
+    /// accepted too.
+    fn from_str(value: &str) -> Result<StudioVersion, ParseStudioVersionError> {
+        let error = || ParseStudioVersionError(value.to_owned());
+
+        let mut components = value
+            .trim()
+            .split(['.', ','])
+            .map(|component| component.trim().parse::<u32>().map_err(|_| error()));
+
+        let mut next = || components.next().ok_or_else(error)?;
+        let version = StudioVersion {
+            major: next()?,
+            minor: next()?,
+            patch: next()?,
+            build: next()?,
+        };
+
+        if components.next().is_some() {
+            return Err(error());
+        }
+
+        Ok(version)
+    }
+}
+
+impl fmt::Display for StudioVersion {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{}.{}.{}.{}", self.major, self.minor, self.patch, self.build)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(value: &str) -> StudioVersion {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_versions() {
+        let expected = StudioVersion {
+            major: 0,
+            minor: 612,
+            patch: 0,
+            build: 6120532,
+        };
+
+        assert_eq!(version("0.612.0.6120532"), expected);
+        assert_eq!(version("0, 612, 0, 6120532"), expected);
+        assert_eq!(version(" 0.612.0.6120532\n"), expected);
+        assert_eq!(expected.to_string(), "0.612.0.6120532");
+    }
+
+    #[test]
+    fn rejects_other_versions() {
+        for value in ["", "0.612.0", "0.612.0.6120532.1", "0.612.x.6120532", "0..612.0", "-1.612.0.6120532"] {
+            assert!(value.parse::<StudioVersion>().is_err(), "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn orders_versions_by_component() {
+        assert!(version("0.612.0.6120532") < version("0.613.0.6130100"));
+        assert!(version("0.612.0.6120532") < version("0.612.1.0"));
+        assert!(version("0.99.0.9999999") < version("0.100.0.0"));
+        assert!(version("1.0.0.0") > version("0.700.0.7000000"));
+
+        let mut versions = vec![version("0.613.0.1"), version("0.9.0.5"), version("0.613.0.0")];
+        versions.sort();
+        assert_eq!(versions, [version("0.9.0.5"), version("0.613.0.0"), version("0.613.0.1")]);
+    }
+}
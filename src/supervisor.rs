@@ -0,0 +1,340 @@
+use std::{
+	io,
+	process::{Child, Command, ExitCode, ExitStatus},
+	slice,
+	sync::atomic::{AtomicBool, Ordering},
+	thread,
+	time::{Duration, Instant},
+};
+
+use tracing::debug;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Delays between attempts to start a locked executable, doubling up to the
+/// maximum.
+const FIRST_RETRY_DELAY: Duration = Duration::from_millis(500);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(10);
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+static HANDLER_SET: AtomicBool = AtomicBool::new(false);
+
+/// Whether Ctrl-C was forwarded to a child since the CLI started.
+static FORWARDED: AtomicBool = AtomicBool::new(false);
+
+/// Whether Ctrl-C tears attached children down instead of being forwarded.
+static ATTACHED: AtomicBool = AtomicBool::new(false);
+
+/// Keeps an attached child tied to this process, see [`attach`].
+pub struct Attachment {
+	#[cfg(target_os = "windows")]
+	job: windows_sys::Win32::Foundation::HANDLE,
+}
+
+/// Spawns the command, retrying for up to `window` while its executable is
+/// locked. The bootstrapper holds Studio's executable while it writes an
+/// update, which fails the spawn with a sharing violation on Windows and
+/// `ETXTBSY` on Unix.
+pub fn spawn_retrying(command: &mut Command, window: Duration) -> io::Result<Child> {
+	let started = Instant::now();
+	let mut delay = FIRST_RETRY_DELAY;
+
+	loop {
+		match command.spawn() {
+			Err(err) if is_locked(&err) && started.elapsed() + delay <= window => {
+				if delay == FIRST_RETRY_DELAY {
+					eprintln!(
+						"Roblox Studio's executable is locked, it's probably being updated. Retrying for up to {}",
+						humantime::format_duration(window)
+					);
+				}
+
+				debug!("Spawning failed with {}, retrying in {:?}", err, delay);
+
+				if !sleep_unless_interrupted(delay) {
+					return Err(io::Error::new(
+						io::ErrorKind::Interrupted,
+						"interrupted while waiting for the executable to be unlocked",
+					));
+				}
+
+				delay = (delay * 2).min(MAX_RETRY_DELAY);
+			}
+			Err(err) if is_locked(&err) => {
+				return Err(io::Error::new(
+					err.kind(),
+					format!("the executable is still locked, probably by an update in progress ({})", err),
+				));
+			}
+			result => return result,
+		}
+	}
+}
+
+/// Sleeps for the duration, returning false early when Ctrl-C is pressed.
+fn sleep_unless_interrupted(duration: Duration) -> bool {
+	let started = Instant::now();
+
+	while started.elapsed() < duration {
+		if interrupt_pending() {
+			return false;
+		}
+
+		thread::sleep(POLL_INTERVAL.min(duration - started.elapsed()));
+	}
+
+	!interrupt_pending()
+}
+
+fn is_locked(err: &io::Error) -> bool {
+	#[cfg(target_os = "windows")]
+	{
+		use windows_sys::Win32::Foundation::{ERROR_LOCK_VIOLATION, ERROR_SHARING_VIOLATION};
+
+		matches!(err.raw_os_error(), Some(code) if code == ERROR_SHARING_VIOLATION as i32 || code == ERROR_LOCK_VIOLATION as i32)
+	}
+
+	#[cfg(unix)]
+	{
+		err.raw_os_error() == Some(libc::ETXTBSY)
+	}
+}
+
+/// Prepares a command so its process can be torn down along with this one. On
+/// Unix, it's started in its own process group, so Ctrl-C reaches this process
+/// only and everything Studio started can be killed at once. On Linux, the
+/// kernel also kills it when this process dies, even from SIGKILL.
+pub fn prepare_attached(command: &mut Command) {
+	#[cfg(unix)]
+	{
+		use std::os::unix::process::CommandExt;
+
+		command.process_group(0);
+
+		#[cfg(target_os = "linux")]
+		// SAFETY: `prctl` is async-signal-safe, so it can run between fork and exec.
+		unsafe {
+			command.pre_exec(|| {
+				if libc::prctl(libc::PR_SET_PDEATHSIG, libc::SIGKILL) == 0 {
+					Ok(())
+				} else {
+					Err(io::Error::last_os_error())
+				}
+			});
+		}
+	}
+
+	#[cfg(not(unix))]
+	let _ = command;
+}
+
+/// Ties a child started with [`prepare_attached`] to this process: Ctrl-C
+/// kills it instead of being forwarded. On Windows, it's assigned to a job
+/// object that kills it and the processes it started once the job's last
+/// handle is closed, which happens when this process exits however it exits.
+pub fn attach(child: &Child) -> io::Result<Attachment> {
+	ATTACHED.store(true, Ordering::SeqCst);
+
+	#[cfg(target_os = "windows")]
+	{
+		use std::{mem, os::windows::io::AsRawHandle, ptr};
+
+		use windows_sys::Win32::Foundation::CloseHandle;
+		use windows_sys::Win32::System::JobObjects::{
+			AssignProcessToJobObject, CreateJobObjectW, JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE,
+			JOBOBJECT_EXTENDED_LIMIT_INFORMATION, JobObjectExtendedLimitInformation, SetInformationJobObject,
+		};
+
+		// SAFETY: the job handle is checked before use and closed on failure, the
+		// limit information is plain data passed with its size, and the process
+		// handle belongs to a child we haven't reaped yet.
+		unsafe {
+			let job = CreateJobObjectW(ptr::null(), ptr::null());
+
+			if job.is_null() {
+				return Err(io::Error::last_os_error());
+			}
+
+			let mut limits: JOBOBJECT_EXTENDED_LIMIT_INFORMATION = mem::zeroed();
+			limits.BasicLimitInformation.LimitFlags = JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE;
+
+			let configured = SetInformationJobObject(
+				job,
+				JobObjectExtendedLimitInformation,
+				&limits as *const JOBOBJECT_EXTENDED_LIMIT_INFORMATION as *const _,
+				mem::size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>() as u32,
+			);
+
+			if configured == 0 || AssignProcessToJobObject(job, child.as_raw_handle() as _) == 0 {
+				let err = io::Error::last_os_error();
+				CloseHandle(job);
+				return Err(err);
+			}
+
+			Ok(Attachment { job })
+		}
+	}
+
+	#[cfg(not(target_os = "windows"))]
+	{
+		let _ = child;
+		Ok(Attachment {})
+	}
+}
+
+#[cfg(target_os = "windows")]
+impl Drop for Attachment {
+	fn drop(&mut self) {
+		// SAFETY: the handle was returned by CreateJobObjectW and is only closed
+		// here. Closing it kills the processes still in the job.
+		unsafe {
+			windows_sys::Win32::Foundation::CloseHandle(self.job);
+		}
+	}
+}
+
+/// Waits for the child to exit, forwarding Ctrl-C to it instead of letting it
+/// terminate the CLI, so the child's exit status can still be reported.
+/// `stop` is called while it runs, and when it returns true, the child is
+/// killed and `None` is returned.
+pub fn wait_watching(child: &mut Child, mut stop: impl FnMut(&Child) -> io::Result<bool>) -> io::Result<Option<ExitStatus>> {
+	let statuses = wait_until(slice::from_mut(child), |children| stop(&children[0]))?;
+	Ok(statuses.map(|statuses| statuses[0]))
+}
+
+/// Waits for all the children to exit, like [`wait_watching`], and returns
+/// their exit statuses in the same order. Ctrl-C is forwarded to the ones still
+/// running.
+pub fn wait_all(children: &mut [Child]) -> io::Result<Vec<ExitStatus>> {
+	let statuses = wait_until(children, |_| Ok(false))?;
+	Ok(statuses.unwrap_or_default())
+}
+
+/// Waits for the children to exit until `stop` returns true, when the ones
+/// still running are killed and `None` is returned.
+fn wait_until(
+	children: &mut [Child],
+	mut stop: impl FnMut(&[Child]) -> io::Result<bool>,
+) -> io::Result<Option<Vec<ExitStatus>>> {
+	handle_interrupts()?;
+
+	let mut statuses = vec![None; children.len()];
+
+	loop {
+		for (child, status) in children.iter_mut().zip(&mut statuses) {
+			if status.is_none() {
+				*status = child.try_wait()?;
+			}
+		}
+
+		if statuses.iter().all(Option::is_some) {
+			return Ok(Some(statuses.into_iter().flatten().collect()));
+		}
+
+		if INTERRUPTED.swap(false, Ordering::SeqCst) {
+			FORWARDED.store(true, Ordering::SeqCst);
+
+			for (child, _) in children.iter_mut().zip(&statuses).filter(|(_, status)| status.is_none()) {
+				interrupt(child)?;
+			}
+		}
+
+		if stop(children)? {
+			for (child, _) in children.iter_mut().zip(&statuses).filter(|(_, status)| status.is_none()) {
+				kill(child)?;
+				child.wait()?;
+			}
+
+			return Ok(None);
+		}
+
+		thread::sleep(POLL_INTERVAL);
+	}
+}
+
+#[cfg(unix)]
+fn interrupt(child: &mut Child) -> io::Result<()> {
+	if ATTACHED.load(Ordering::SeqCst) {
+		return kill(child);
+	}
+
+	// SAFETY: `kill` has no memory safety requirements, and the pid belongs to
+	// a child we haven't reaped yet.
+	if unsafe { libc::kill(child.id() as libc::pid_t, libc::SIGINT) } == 0 {
+		Ok(())
+	} else {
+		Err(io::Error::last_os_error())
+	}
+}
+
+/// Kills the child. An attached child leads its own process group, which is
+/// killed as a whole.
+fn kill(child: &mut Child) -> io::Result<()> {
+	#[cfg(unix)]
+	if ATTACHED.load(Ordering::SeqCst) {
+		// SAFETY: `kill` has no memory safety requirements, and the child leads
+		// the process group and hasn't been reaped yet.
+		return if unsafe { libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL) } == 0 {
+			Ok(())
+		} else {
+			Err(io::Error::last_os_error())
+		};
+	}
+
+	child.kill()
+}
+
+#[cfg(not(unix))]
+fn interrupt(child: &mut Child) -> io::Result<()> {
+	// Studio is a GUI application without a console, so it can't receive a
+	// console control event. Terminating it is the closest equivalent.
+	child.kill()
+}
+
+/// Catches Ctrl-C, and on Unix `SIGTERM` and `SIGHUP` like when the terminal is
+/// closed, instead of letting them terminate the CLI, so changes made for a
+/// session are reverted even when they come before Studio is waited on.
+/// A Ctrl-C caught before then is forwarded to Studio once it's waited on, and
+/// cuts the waits done while starting it short, see [`interrupt_pending`].
+pub fn handle_interrupts() -> io::Result<()> {
+	// The handler can only be set once, and children can be waited on again,
+	// like when Studio is restarted.
+	if !HANDLER_SET.load(Ordering::SeqCst) {
+		ctrlc::set_handler(|| INTERRUPTED.store(true, Ordering::SeqCst))
+			.map_err(io::Error::other)?;
+		HANDLER_SET.store(true, Ordering::SeqCst);
+	}
+
+	Ok(())
+}
+
+/// Whether Ctrl-C was pressed and hasn't been forwarded to a child yet.
+pub fn interrupt_pending() -> bool {
+	INTERRUPTED.load(Ordering::SeqCst)
+}
+
+/// Whether the user pressed Ctrl-C while a child was waited on, so its exit
+/// was asked for rather than a crash.
+pub fn interrupted() -> bool {
+	FORWARDED.load(Ordering::SeqCst)
+}
+
+/// Converts a child's exit status into an exit code for this process. Codes
+/// that don't fit in an exit code are reported as a generic failure, and
+/// processes killed by a signal follow the shell convention of 128 + signal.
+pub fn exit_code(status: ExitStatus) -> ExitCode {
+	if let Some(code) = status.code() {
+		return u8::try_from(code).map_or(ExitCode::FAILURE, ExitCode::from);
+	}
+
+	#[cfg(unix)]
+	{
+		use std::os::unix::process::ExitStatusExt;
+
+		if let Some(signal) = status.signal() {
+			return u8::try_from(128 + signal).map_or(ExitCode::FAILURE, ExitCode::from);
+		}
+	}
+
+	ExitCode::FAILURE
+}
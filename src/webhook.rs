@@ -0,0 +1,87 @@
+//! Status messages POSTed to the webhook of the global configuration, for
+//! unattended runs. The payload has both the `text` field of Slack's incoming
+//! webhooks and the `content` field of Discord's, which each ignore the other.
+
+use std::{
+	thread::{self, JoinHandle},
+	time::Duration,
+};
+
+use serde::Serialize;
+use tracing::{debug, warn};
+
+use roblox_studio::http;
+
+/// How long to wait for the webhook to respond, so a slow server doesn't hold up
+/// the CLI once it's done.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Serialize)]
+struct Payload<'a> {
+	text: &'a str,
+	content: &'a str,
+}
+
+/// Where to post messages, when a webhook is configured. Messages are posted in
+/// the background, and dropping the webhook waits for the ones still being
+/// posted.
+pub struct Webhook {
+	url: Option<String>,
+	posts: Vec<JoinHandle<()>>,
+}
+
+impl Webhook {
+	pub fn new(url: Option<&str>) -> Webhook {
+		Webhook {
+			url: url.map(str::to_owned),
+			posts: Vec::new(),
+		}
+	}
+
+	/// Starts posting the message. The session goes on when it can't be posted,
+	/// so failures are only reported.
+	pub fn send(&mut self, message: &str) {
+		let Some(url) = self.url.clone() else {
+			return;
+		};
+
+		// The URL is a secret, so it's left out of what's logged.
+		debug!("Posting to the webhook: {}", message);
+
+		let message = message.to_owned();
+
+		self.posts.retain(|post| !post.is_finished());
+		self.posts.push(thread::spawn(move || {
+			if let Err(err) = post(&url, &message) {
+				warn!("Couldn't post to the webhook: {}", err);
+			}
+		}));
+	}
+}
+
+impl Drop for Webhook {
+	fn drop(&mut self) {
+		for post in self.posts.drain(..) {
+			let _ = post.join();
+		}
+	}
+}
+
+fn post(url: &str, message: &str) -> Result<(), String> {
+	let agent = http::agent().map_err(|err| err.to_string())?;
+
+	agent
+		.post(url)
+		.timeout(TIMEOUT)
+		.send_json(Payload {
+			text: message,
+			content: message,
+		})
+		.map(|_| ())
+		.map_err(|err| match err {
+			ureq::Error::Status(code, response) => {
+				format!("server responded with {} {}", code, response.status_text())
+			}
+			ureq::Error::Transport(transport) => transport.kind().to_string(),
+		})
+}
@@ -0,0 +1,608 @@
+//! Moves and resizes Studio's window once it appears, with the platform's window
+//! APIs: the Win32 API on Windows, `wmctrl` and `xdotool` for Wine under X11, and
+//! System Events on MacOS.
+
+use std::{
+	fmt, io,
+	process::Child,
+	str::FromStr,
+	thread,
+	time::{Duration, Instant},
+};
+
+use tracing::debug;
+
+use crate::supervisor;
+
+/// How long Studio's window is waited for. Studio can take a while to start,
+/// especially the first time after an update.
+const WINDOW_TIMEOUT: Duration = Duration::from_secs(120);
+
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// A window size and position, like X11's `WxH+X+Y` geometry strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Geometry {
+	pub width: u32,
+	pub height: u32,
+	pub x: i32,
+	pub y: i32,
+}
+
+impl FromStr for Geometry {
+	type Err = String;
+
+	/// Parses `WxH+X+Y`, where the offsets can be negative (`WxH-X-Y`) or left
+	/// out (`WxH`, at the top left corner).
+	fn from_str(value: &str) -> Result<Geometry, String> {
+		let error = || format!("invalid geometry `{}`, expected WIDTHxHEIGHT+X+Y like 1280x720+0+0", value);
+
+		let offset_start = value.find(['+', '-']).unwrap_or(value.len());
+		let (size, offsets) = value.split_at(offset_start);
+		let (width, height) = size.split_once(['x', 'X']).ok_or_else(error)?;
+
+		let (x, y) = if offsets.is_empty() {
+			(0, 0)
+		} else {
+			let y_start = offsets[1..].find(['+', '-']).ok_or_else(error)? + 1;
+			let (x, y) = offsets.split_at(y_start);
+			(x.parse().map_err(|_| error())?, y.parse().map_err(|_| error())?)
+		};
+
+		Ok(Geometry {
+			width: width.parse().map_err(|_| error())?,
+			height: height.parse().map_err(|_| error())?,
+			x,
+			y,
+		})
+	}
+}
+
+impl fmt::Display for Geometry {
+	fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(formatter, "{}x{}{:+}{:+}", self.width, self.height, self.x, self.y)
+	}
+}
+
+/// Where Studio's window goes.
+#[derive(Debug, Default, Clone)]
+pub struct Placement {
+	pub minimized: bool,
+	pub maximized: bool,
+
+	/// Index of the monitor, starting at 1. The geometry is relative to it.
+	pub monitor: Option<usize>,
+
+	pub geometry: Option<Geometry>,
+}
+
+impl Placement {
+	pub fn is_empty(&self) -> bool {
+		!self.minimized && !self.maximized && self.monitor.is_none() && self.geometry.is_none()
+	}
+}
+
+/// A monitor's area, in virtual screen coordinates.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(target_os = "macos", allow(dead_code))]
+struct Area {
+	x: i32,
+	y: i32,
+	width: u32,
+	height: u32,
+}
+
+/// Waits for Studio to show its main window, then places it. Gives up when
+/// Studio exits before that.
+pub fn place(child: &mut Child, placement: &Placement) -> io::Result<()> {
+	let window = wait_for(child)?;
+
+	let monitor = match placement.monitor {
+		Some(index) => {
+			let monitors = platform::monitors()?;
+			let monitor = monitors.get(index.wrapping_sub(1)).copied().ok_or_else(|| {
+				io::Error::new(
+					io::ErrorKind::InvalidInput,
+					format!("there is no monitor {}, only {} are connected", index, monitors.len()),
+				)
+			})?;
+
+			Some(monitor)
+		}
+		None => None,
+	};
+
+	let geometry = match (placement.geometry, monitor) {
+		(Some(geometry), Some(monitor)) => Some(Geometry {
+			x: monitor.x + geometry.x,
+			y: monitor.y + geometry.y,
+			..geometry
+		}),
+		(geometry, _) => geometry,
+	};
+
+	platform::apply(&window, geometry, monitor, placement)
+}
+
+/// The position of a window of this size centered on the monitor, keeping it
+/// on the monitor when it's larger.
+#[cfg(not(target_os = "macos"))]
+fn centered(width: u32, height: u32, monitor: Area) -> (i32, i32) {
+	let x = monitor.x + (monitor.width.saturating_sub(width) / 2) as i32;
+	let y = monitor.y + (monitor.height.saturating_sub(height) / 2) as i32;
+
+	(x, y)
+}
+
+/// Waits for Studio to show its main window, which means it's done starting.
+pub fn wait_for_window(child: &mut Child) -> io::Result<()> {
+	wait_for(child).map(|_| ())
+}
+
+/// The title of Studio's main window, or `None` while it isn't shown yet.
+pub fn title(child: &Child) -> io::Result<Option<String>> {
+	match platform::find_window(child.id())? {
+		Some(window) => platform::title(&window).map(Some),
+		None => Ok(None),
+	}
+}
+
+fn wait_for(child: &mut Child) -> io::Result<platform::Window> {
+	let started = Instant::now();
+
+	loop {
+		if let Some(window) = platform::find_window(child.id())? {
+			debug!("Found Roblox Studio's window after {:?}", started.elapsed());
+			return Ok(window);
+		}
+
+		if child.try_wait()?.is_some() {
+			return Err(io::Error::other("Roblox Studio exited before its window appeared"));
+		}
+
+		if started.elapsed() > WINDOW_TIMEOUT {
+			return Err(io::Error::new(io::ErrorKind::TimedOut, "Roblox Studio's window didn't appear"));
+		}
+
+		if supervisor::interrupt_pending() {
+			return Err(io::Error::new(io::ErrorKind::Interrupted, "interrupted"));
+		}
+
+		thread::sleep(POLL_INTERVAL);
+	}
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+	use std::{io, mem, ptr};
+
+	use windows_sys::Win32::Foundation::{BOOL, HWND, LPARAM, RECT};
+	use windows_sys::Win32::Graphics::Gdi::{EnumDisplayMonitors, GetMonitorInfoW, HDC, HMONITOR, MONITORINFO};
+	use windows_sys::Win32::UI::WindowsAndMessaging::{
+		EnumWindows, GW_OWNER, GetWindow, GetWindowRect, GetWindowTextW, GetWindowThreadProcessId, IsWindowVisible,
+		SW_MAXIMIZE, SW_MINIMIZE, SW_RESTORE, SWP_NOACTIVATE, SWP_NOSIZE, SWP_NOZORDER, SetWindowPos, ShowWindow,
+	};
+
+	use super::{Area, Geometry, Placement, centered};
+
+	pub struct Window(HWND);
+
+	struct Search {
+		pid: u32,
+		window: HWND,
+	}
+
+	/// Finds the process's visible top-level window that isn't owned by another
+	/// one, like a splash screen or a dialog would be.
+	pub fn find_window(pid: u32) -> io::Result<Option<Window>> {
+		unsafe extern "system" fn visit(window: HWND, search: LPARAM) -> BOOL {
+			// SAFETY: `search` is the pointer to the `Search` given to EnumWindows,
+			// which outlives the enumeration.
+			let search = unsafe { &mut *(search as *mut Search) };
+			let mut pid = 0;
+
+			// SAFETY: `window` is a valid window handle given by EnumWindows.
+			unsafe {
+				GetWindowThreadProcessId(window, &mut pid);
+
+				if pid == search.pid && IsWindowVisible(window) != 0 && GetWindow(window, GW_OWNER).is_null() {
+					search.window = window;
+					return 0;
+				}
+			}
+
+			1
+		}
+
+		let mut search = Search {
+			pid,
+			window: ptr::null_mut(),
+		};
+
+		// SAFETY: the callback only dereferences `search` during the call.
+		unsafe {
+			EnumWindows(Some(visit), &mut search as *mut Search as LPARAM);
+		}
+
+		Ok((!search.window.is_null()).then_some(Window(search.window)))
+	}
+
+	pub fn title(window: &Window) -> io::Result<String> {
+		let mut title = [0u16; 512];
+
+		// SAFETY: the buffer's length is passed along with it.
+		let length = unsafe { GetWindowTextW(window.0, title.as_mut_ptr(), title.len() as i32) };
+
+		Ok(String::from_utf16_lossy(&title[..length.max(0) as usize]))
+	}
+
+	/// The work areas of the monitors, in the order Windows enumerates them.
+	pub fn monitors() -> io::Result<Vec<Area>> {
+		unsafe extern "system" fn visit(monitor: HMONITOR, _: HDC, _: *mut RECT, areas: LPARAM) -> BOOL {
+			// SAFETY: `areas` is the pointer to the vector given to
+			// EnumDisplayMonitors, which outlives the enumeration.
+			let areas = unsafe { &mut *(areas as *mut Vec<Area>) };
+
+			// SAFETY: MONITORINFO is plain data, and its size is set as required.
+			let mut info: MONITORINFO = unsafe { mem::zeroed() };
+			info.cbSize = mem::size_of::<MONITORINFO>() as u32;
+
+			// SAFETY: `monitor` is a valid monitor handle given by EnumDisplayMonitors.
+			if unsafe { GetMonitorInfoW(monitor, &mut info) } != 0 {
+				let work = info.rcWork;
+
+				areas.push(Area {
+					x: work.left,
+					y: work.top,
+					width: (work.right - work.left) as u32,
+					height: (work.bottom - work.top) as u32,
+				});
+			}
+
+			1
+		}
+
+		let mut areas: Vec<Area> = Vec::new();
+
+		// SAFETY: the callback only dereferences `areas` during the call.
+		let result = unsafe {
+			EnumDisplayMonitors(ptr::null_mut(), ptr::null(), Some(visit), &mut areas as *mut Vec<Area> as LPARAM)
+		};
+
+		if result == 0 {
+			return Err(io::Error::last_os_error());
+		}
+
+		Ok(areas)
+	}
+
+	pub fn apply(window: &Window, geometry: Option<Geometry>, monitor: Option<Area>, placement: &Placement) -> io::Result<()> {
+		let Window(window) = *window;
+
+		// SAFETY: `window` is a window handle found by `find_window`. If the
+		// window was closed since, the calls fail without side effects.
+		unsafe {
+			// A maximized window has to be restored before it can be moved.
+			ShowWindow(window, SW_RESTORE);
+
+			let moved = match (geometry, monitor) {
+				(Some(geometry), _) => SetWindowPos(
+					window,
+					ptr::null_mut(),
+					geometry.x,
+					geometry.y,
+					geometry.width as i32,
+					geometry.height as i32,
+					SWP_NOZORDER | SWP_NOACTIVATE,
+				),
+				(None, Some(monitor)) => {
+					let mut rect: RECT = mem::zeroed();
+					GetWindowRect(window, &mut rect);
+
+					let (x, y) = centered((rect.right - rect.left) as u32, (rect.bottom - rect.top) as u32, monitor);
+
+					SetWindowPos(window, ptr::null_mut(), x, y, 0, 0, SWP_NOSIZE | SWP_NOZORDER | SWP_NOACTIVATE)
+				}
+				(None, None) => 1,
+			};
+
+			if moved == 0 {
+				return Err(io::Error::last_os_error());
+			}
+
+			if placement.maximized {
+				ShowWindow(window, SW_MAXIMIZE);
+			} else if placement.minimized {
+				ShowWindow(window, SW_MINIMIZE);
+			}
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+mod platform {
+	use std::{collections::HashSet, fs, io, process::Command};
+
+	use tracing::debug;
+
+	use super::{Area, Geometry, Placement, centered};
+
+	/// An X11 window ID, like `0x04000007`, and the window's size and title.
+	pub struct Window {
+		id: String,
+		width: u32,
+		height: u32,
+		title: String,
+	}
+
+	/// Finds Studio's window with `wmctrl`, among the windows of the processes
+	/// started by the child, whose ID is read from their `_NET_WM_PID`. Wine
+	/// doesn't always set it, so a window without one is matched by title, but
+	/// only when no other Studio is running, since it could be that one's.
+	pub fn find_window(pid: u32) -> io::Result<Option<Window>> {
+		if roblox_studio::roblox_install::wsl_version().is_some() {
+			return Err(io::Error::new(
+				io::ErrorKind::Unsupported,
+				"the window of Roblox Studio can't be placed from WSL",
+			));
+		}
+
+		// Lines are like `0x04000007  0 4242   0 0 1280 720  host Roblox Studio`.
+		let windows = output("wmctrl", &["-l", "-p", "-G"])?;
+		let tree = process_tree(pid);
+		let mut others_running = None;
+
+		let window = windows.lines().find_map(|line| {
+			let fields: Vec<&str> = line.split_whitespace().collect();
+			let title = fields.get(8..)?.join(" ");
+			let window_pid: u32 = fields[2].parse().ok()?;
+
+			let matches = if window_pid == 0 {
+				title.ends_with("Roblox Studio")
+					&& !*others_running.get_or_insert_with(|| {
+						roblox_studio::process::running()
+							.iter()
+							.any(|process| !tree.contains(&process.pid))
+					})
+			} else {
+				tree.contains(&window_pid)
+			};
+
+			if !matches {
+				return None;
+			}
+
+			Some(Window {
+				id: fields[0].to_owned(),
+				width: fields[5].parse().ok()?,
+				height: fields[6].parse().ok()?,
+				title,
+			})
+		});
+
+		Ok(window)
+	}
+
+	/// The process and its descendants, from the parent IDs in `/proc`, since
+	/// launchers and Wine can start Studio in a child process.
+	fn process_tree(pid: u32) -> HashSet<u32> {
+		let parents: Vec<(u32, u32)> = fs::read_dir("/proc")
+			.into_iter()
+			.flatten()
+			.filter_map(|entry| {
+				let child: u32 = entry.ok()?.file_name().to_str()?.parse().ok()?;
+				let stat = fs::read_to_string(format!("/proc/{}/stat", child)).ok()?;
+
+				// The command name in parentheses can hold spaces, the parent ID
+				// is the second field after it.
+				let (_, rest) = stat.rsplit_once(')')?;
+				let parent = rest.split_whitespace().nth(1)?.parse().ok()?;
+
+				Some((child, parent))
+			})
+			.collect();
+
+		let mut tree = HashSet::from([pid]);
+		let mut grew = true;
+
+		while grew {
+			grew = false;
+
+			for (child, parent) in &parents {
+				if tree.contains(parent) && tree.insert(*child) {
+					grew = true;
+				}
+			}
+		}
+
+		tree
+	}
+
+	pub fn title(window: &Window) -> io::Result<String> {
+		Ok(window.title.clone())
+	}
+
+	/// The monitors, from `xrandr --listmonitors` lines like
+	/// ` 0: +*eDP-1 1920/344x1080/194+0+0  eDP-1`.
+	pub fn monitors() -> io::Result<Vec<Area>> {
+		let monitors = output("xrandr", &["--listmonitors"])?;
+
+		Ok(monitors
+			.lines()
+			.skip(1)
+			.filter_map(|line| {
+				let geometry = line.split_whitespace().nth(2)?;
+				let (width, rest) = geometry.split_once('/')?;
+				let (_, rest) = rest.split_once('x')?;
+				let (height, rest) = rest.split_once('/')?;
+				let offsets = &rest[rest.find(['+', '-'])?..];
+				let geometry: Geometry = format!("{}x{}{}", width, height, offsets).parse().ok()?;
+
+				Some(Area {
+					x: geometry.x,
+					y: geometry.y,
+					width: geometry.width,
+					height: geometry.height,
+				})
+			})
+			.collect())
+	}
+
+	pub fn apply(window: &Window, geometry: Option<Geometry>, monitor: Option<Area>, placement: &Placement) -> io::Result<()> {
+		let id = &window.id;
+
+		run("wmctrl", &["-i", "-r", id, "-b", "remove,maximized_vert,maximized_horz"])?;
+
+		let position = match (geometry, monitor) {
+			(Some(geometry), _) => Some(format!(
+				"0,{},{},{},{}",
+				geometry.x, geometry.y, geometry.width, geometry.height
+			)),
+			(None, Some(monitor)) => {
+				let (x, y) = centered(window.width, window.height, monitor);
+				Some(format!("0,{},{},-1,-1", x, y))
+			}
+			(None, None) => None,
+		};
+
+		if let Some(position) = position {
+			run("wmctrl", &["-i", "-r", id, "-e", &position])?;
+		}
+
+		if placement.maximized {
+			run("wmctrl", &["-i", "-r", id, "-b", "add,maximized_vert,maximized_horz"])?;
+		} else if placement.minimized {
+			run("xdotool", &["windowminimize", id])?;
+		}
+
+		Ok(())
+	}
+
+	fn output(program: &str, args: &[&str]) -> io::Result<String> {
+		debug!("Running {} {}", program, args.join(" "));
+
+		let output = Command::new(program)
+			.args(args)
+			.output()
+			.map_err(|err| io::Error::new(err.kind(), format!("couldn't run {}: {}", program, err)))?;
+
+		if !output.status.success() {
+			return Err(io::Error::other(format!(
+				"{} failed: {}",
+				program,
+				String::from_utf8_lossy(&output.stderr).trim()
+			)));
+		}
+
+		Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+	}
+
+	fn run(program: &str, args: &[&str]) -> io::Result<()> {
+		output(program, args).map(|_| ())
+	}
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+	use std::{io, process::Command};
+
+	use super::{Area, Geometry, Placement};
+
+	/// The process ID of the application whose front window is placed.
+	pub struct Window(u32);
+
+	pub fn find_window(pid: u32) -> io::Result<Option<Window>> {
+		let count = osascript(&format!(
+			"tell application \"System Events\" to count windows of (first process whose unix id is {})",
+			pid
+		))?;
+
+		Ok((count.trim().parse::<u32>().unwrap_or(0) > 0).then_some(Window(pid)))
+	}
+
+	pub fn title(window: &Window) -> io::Result<String> {
+		let Window(pid) = *window;
+
+		osascript(&format!(
+			"tell application \"System Events\" to get name of window 1 of (first process whose unix id is {})",
+			pid
+		))
+		.map(|title| title.trim().to_owned())
+	}
+
+	pub fn monitors() -> io::Result<Vec<Area>> {
+		Err(io::Error::new(
+			io::ErrorKind::Unsupported,
+			"choosing a monitor isn't supported on MacOS",
+		))
+	}
+
+	pub fn apply(window: &Window, geometry: Option<Geometry>, _monitor: Option<Area>, placement: &Placement) -> io::Result<()> {
+		let Window(pid) = *window;
+		let mut commands = Vec::new();
+
+		if let Some(geometry) = geometry {
+			commands.push(format!("set position of window 1 to {{{}, {}}}", geometry.x, geometry.y));
+			commands.push(format!("set size of window 1 to {{{}, {}}}", geometry.width, geometry.height));
+		}
+
+		if placement.maximized {
+			commands.push("set value of attribute \"AXFullScreen\" of window 1 to true".to_owned());
+		} else if placement.minimized {
+			commands.push("set value of attribute \"AXMinimized\" of window 1 to true".to_owned());
+		}
+
+		osascript(&format!(
+			"tell application \"System Events\" to tell (first process whose unix id is {})\n{}\nend tell",
+			pid,
+			commands.join("\n")
+		))
+		.map(|_| ())
+	}
+
+	fn osascript(script: &str) -> io::Result<String> {
+		let output = Command::new("osascript").arg("-e").arg(script).output()?;
+
+		if !output.status.success() {
+			return Err(io::Error::other(format!(
+				"osascript failed: {}",
+				String::from_utf8_lossy(&output.stderr).trim()
+			)));
+		}
+
+		Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn geometry(width: u32, height: u32, x: i32, y: i32) -> Geometry {
+		Geometry { width, height, x, y }
+	}
+
+	#[test]
+	fn parses_geometry() {
+		assert_eq!("1280x720+0+0".parse(), Ok(geometry(1280, 720, 0, 0)));
+		assert_eq!("1280X720+1920+40".parse(), Ok(geometry(1280, 720, 1920, 40)));
+		assert_eq!("800x600-10-20".parse(), Ok(geometry(800, 600, -10, -20)));
+		assert_eq!("800x600".parse(), Ok(geometry(800, 600, 0, 0)));
+	}
+
+	#[test]
+	fn rejects_malformed_geometry() {
+		for value in ["", "800", "800x", "x600", "800x600+10", "800x600+a+b", "-800x600", "800x600+10+20+30"] {
+			assert!(value.parse::<Geometry>().is_err(), "{:?}", value);
+		}
+	}
+
+	#[test]
+	fn displays_geometry() {
+		assert_eq!(geometry(1280, 720, 0, 0).to_string(), "1280x720+0+0");
+		assert_eq!(geometry(800, 600, -10, 20).to_string(), "800x600-10+20");
+		assert_eq!(geometry(800, 600, 0, 0).to_string().parse(), Ok(geometry(800, 600, 0, 0)));
+	}
+}